@@ -27,6 +27,10 @@ use crate::utils::get_storage_var_address;
 const ACCOUNT_CLASS_HASH_HEX_FOR_ADDRESS_COMPUTATION: &str =
     "0x3FCBF77B28C96F4F2FB5BD2D176AB083A12A5E123ADEB0DE955D7EE228C9854";
 
+/// A storage slot of a contract and the value written to it
+pub(crate) type StorageWrite =
+    (starknet_api::core::ContractAddress, starknet_api::state::StorageKey, Felt);
+
 pub enum FeeToken {
     ETH,
     STRK,
@@ -35,11 +39,13 @@ pub enum FeeToken {
 #[derive(Clone)]
 pub struct Account {
     pub public_key: Key,
-    pub private_key: Key,
+    /// Unknown if the account was specified by its address and public key
+    pub private_key: Option<Key>,
     pub account_address: ContractAddress,
-    pub initial_balance: Balance,
+    pub eth_initial_balance: Balance,
+    pub strk_initial_balance: Balance,
     pub class_hash: ClassHash,
-    pub(crate) contract_class: ContractClass,
+    pub(crate) contract_class: Arc<ContractClass>,
     pub(crate) eth_fee_token_address: ContractAddress,
     pub(crate) strk_fee_token_address: ContractAddress,
 }
@@ -56,41 +62,46 @@ impl Account {
         let initial_balance = Balance::from(u128::MAX);
         Ok(Self {
             public_key: Key::from_hex(CHARGEABLE_ACCOUNT_PUBLIC_KEY).unwrap(),
-            private_key: Key::from_hex(CHARGEABLE_ACCOUNT_PRIVATE_KEY).unwrap(),
+            private_key: Some(Key::from_hex(CHARGEABLE_ACCOUNT_PRIVATE_KEY).unwrap()),
             account_address: ContractAddress::new(
                 felt_from_prefixed_hex(CHARGEABLE_ACCOUNT_ADDRESS).unwrap(),
             )
             .unwrap(),
-            initial_balance,
+            eth_initial_balance: initial_balance.clone(),
+            strk_initial_balance: initial_balance,
             class_hash,
-            contract_class: account_contract_class.into(),
+            contract_class: Arc::new(account_contract_class.into()),
             eth_fee_token_address,
             strk_fee_token_address,
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
-        initial_balance: Balance,
+        eth_initial_balance: Balance,
+        strk_initial_balance: Balance,
         public_key: Key,
-        private_key: Key,
+        private_key: Option<Key>,
+        account_address: ContractAddress,
         class_hash: ClassHash,
-        contract_class: ContractClass,
+        contract_class: Arc<ContractClass>,
         eth_fee_token_address: ContractAddress,
         strk_fee_token_address: ContractAddress,
-    ) -> DevnetResult<Self> {
-        Ok(Self {
-            initial_balance,
+    ) -> Self {
+        Self {
+            eth_initial_balance,
+            strk_initial_balance,
             public_key,
             private_key,
+            account_address,
             class_hash,
             contract_class,
-            account_address: Account::compute_account_address(&public_key)?,
             eth_fee_token_address,
             strk_fee_token_address,
-        })
+        }
     }
 
-    fn compute_account_address(public_key: &Key) -> DevnetResult<ContractAddress> {
+    pub(crate) fn compute_account_address(public_key: &Key) -> DevnetResult<ContractAddress> {
         let account_address = calculate_contract_address(
             ContractAddressSalt(felt!(20u32)),
             starknet_api::core::ClassHash(felt_from_prefixed_hex(
@@ -104,27 +115,46 @@ impl Account {
         Ok(ContractAddress::from(account_address))
     }
 
+    /// The storage of the fee tokens holding the initial balances of the account
+    pub(crate) fn initial_balance_storage(&self) -> DevnetResult<Vec<StorageWrite>> {
+        let storage_var_address_low =
+            get_storage_var_address("ERC20_balances", &[Felt::from(self.account_address)])?;
+        let storage_var_address_high = next_storage_key(&storage_var_address_low.try_into()?)?;
+
+        let mut storage = vec![];
+        for (fee_token_address, initial_balance) in [
+            (self.eth_fee_token_address, &self.eth_initial_balance),
+            (self.strk_fee_token_address, &self.strk_initial_balance),
+        ] {
+            let (high, low) = split_biguint(initial_balance.clone());
+            storage.push((fee_token_address.try_into()?, storage_var_address_low.try_into()?, low));
+            storage.push((fee_token_address.try_into()?, storage_var_address_high, high));
+        }
+
+        Ok(storage)
+    }
+
     // simulate constructor logic (register interfaces and set public key), as done in
     // https://github.com/OpenZeppelin/cairo-contracts/blob/89a450a88628ec3b86273f261b2d8d1ca9b1522b/src/account/account.cairo#L207-L211
-    fn simulate_constructor(&self, state: &mut StarknetState) -> DevnetResult<()> {
+    pub(crate) fn constructor_storage(&self) -> DevnetResult<Vec<StorageWrite>> {
         let core_address = self.account_address.try_into()?;
 
         let interface_storage_var = get_storage_var_address(
             "SRC5_supported_interfaces",
             &[felt_from_prefixed_hex(ISRC6_ID_HEX)?],
         )?;
-        state.state.state.set_storage_at(
-            core_address,
-            interface_storage_var.try_into()?,
-            Felt::ONE,
-        )?;
-
         let public_key_storage_var = get_storage_var_address("Account_public_key", &[])?;
-        state.state.state.set_storage_at(
-            core_address,
-            public_key_storage_var.try_into()?,
-            self.public_key,
-        )?;
+
+        Ok(vec![
+            (core_address, interface_storage_var.try_into()?, Felt::ONE),
+            (core_address, public_key_storage_var.try_into()?, self.public_key),
+        ])
+    }
+
+    fn simulate_constructor(&self, state: &mut StarknetState) -> DevnetResult<()> {
+        for (contract_address, key, value) in self.constructor_storage()? {
+            state.state.state.set_storage_at(contract_address, key, value)?;
+        }
 
         Ok(())
     }
@@ -151,20 +181,8 @@ impl Deployed for Account {
 
 impl Accounted for Account {
     fn set_initial_balance(&self, state: &mut DictState) -> DevnetResult<()> {
-        let storage_var_address_low =
-            get_storage_var_address("ERC20_balances", &[Felt::from(self.account_address)])?;
-        let storage_var_address_high = next_storage_key(&storage_var_address_low.try_into()?)?;
-
-        let (high, low) = split_biguint(self.initial_balance.clone());
-
-        for fee_token_address in [self.eth_fee_token_address, self.strk_fee_token_address] {
-            state.set_storage_at(
-                fee_token_address.try_into()?,
-                storage_var_address_low.try_into()?,
-                low,
-            )?;
-
-            state.set_storage_at(fee_token_address.try_into()?, storage_var_address_high, high)?;
+        for (fee_token_address, key, value) in self.initial_balance_storage()? {
+            state.set_storage_at(fee_token_address, key, value)?;
         }
 
         Ok(())
@@ -185,6 +203,8 @@ impl Accounted for Account {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use starknet_rs_core::types::Felt;
     use starknet_types::contract_address::ContractAddress;
     use starknet_types::felt::felt_from_prefixed_hex;
@@ -250,7 +270,8 @@ mod tests {
     fn account_get_balance_should_return_correct_value() {
         let (mut account, mut state) = setup();
         let expected_balance = Balance::from(100_u8);
-        account.initial_balance = expected_balance.clone();
+        account.eth_initial_balance = expected_balance.clone();
+        account.strk_initial_balance = expected_balance.clone();
         account.deploy(&mut state).unwrap();
         let generated_balance = account.get_balance(&mut state, FeeToken::ETH).unwrap();
 
@@ -287,17 +308,19 @@ mod tests {
             )
             .unwrap();
 
+        let public_key = Felt::from(13431515);
         (
             Account::new(
                 Balance::from(10_u8),
-                Felt::from(13431515),
-                Felt::from(11),
+                Balance::from(10_u8),
+                public_key,
+                Some(Felt::from(11)),
+                Account::compute_account_address(&public_key).unwrap(),
                 dummy_felt(),
-                dummy_cairo_0_contract_class().into(),
+                Arc::new(dummy_cairo_0_contract_class().into()),
                 fee_token_address,
                 fee_token_address,
-            )
-            .unwrap(),
+            ),
             state,
         )
     }
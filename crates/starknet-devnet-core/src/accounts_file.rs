@@ -0,0 +1,201 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde_json::json;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::Key;
+use starknet_types::rpc::state::Balance;
+
+use crate::contract_class_choice::{AccountClassWrapper, AccountContractClassChoice};
+use crate::error::{DevnetResult, Error};
+use crate::utils::fingerprint;
+
+/// Accounts to be predeployed instead of the ones generated from the seed, as specified in an
+/// accounts file.
+#[derive(Clone, Debug, Default)]
+pub struct AccountsFile {
+    pub accounts: Vec<AccountsFileEntry>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AccountsFileEntry {
+    pub keys: AccountKeys,
+    /// If not provided, the class of seed-generated accounts is used
+    pub class: Option<AccountClassWrapper>,
+    /// If not provided, the initial balance of seed-generated accounts is used
+    pub eth_balance: Option<Balance>,
+    /// If not provided, the initial balance of seed-generated accounts is used
+    pub strk_balance: Option<Balance>,
+}
+
+#[derive(Clone, Debug)]
+pub enum AccountKeys {
+    /// The public key and the address are derived from the private key, as with seed-generated
+    /// accounts
+    PrivateKey(Key),
+    /// The private key is unknown to Devnet, e.g. if it is held by an external signer
+    AddressAndPublicKey { address: ContractAddress, public_key: Key },
+}
+
+/// The structure of an entry in an accounts file; classes are specified as `cairo0`, `cairo1` or
+/// as a path to a Sierra artifact
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawAccountsFileEntry {
+    private_key: Option<Key>,
+    address: Option<ContractAddress>,
+    public_key: Option<Key>,
+    class: Option<String>,
+    eth_balance: Option<String>,
+    strk_balance: Option<String>,
+}
+
+fn parse_balance(balance: Option<String>, position: usize) -> DevnetResult<Option<Balance>> {
+    balance
+        .map(|balance| {
+            Balance::from_str(&balance).map_err(|_| Error::InvalidAccountsFile {
+                msg: format!("Account #{position}: expected a decimal string, got: {balance}"),
+            })
+        })
+        .transpose()
+}
+
+impl RawAccountsFileEntry {
+    fn resolve(self, base_dir: &Path, position: usize) -> DevnetResult<AccountsFileEntry> {
+        let keys = match (self.private_key, self.address, self.public_key) {
+            (Some(private_key), None, None) => AccountKeys::PrivateKey(private_key),
+            (None, Some(address), Some(public_key)) => {
+                AccountKeys::AddressAndPublicKey { address, public_key }
+            }
+            _ => {
+                return Err(Error::InvalidAccountsFile {
+                    msg: format!(
+                        "Account #{position}: specify either private_key or both address and \
+                         public_key"
+                    ),
+                });
+            }
+        };
+
+        let class = match self.class {
+            None => None,
+            Some(class) => Some(match AccountContractClassChoice::from_str(&class, true) {
+                Ok(class_choice) => class_choice.get_class_wrapper()?,
+                Err(_) => AccountClassWrapper::from_str(&base_dir.join(&class).to_string_lossy())
+                    .map_err(|err| Error::InvalidAccountsFile {
+                    msg: format!("Account #{position}: {err}"),
+                })?,
+            }),
+        };
+
+        Ok(AccountsFileEntry {
+            keys,
+            class,
+            eth_balance: parse_balance(self.eth_balance, position)?,
+            strk_balance: parse_balance(self.strk_balance, position)?,
+        })
+    }
+}
+
+impl FromStr for AccountsFile {
+    type Err = Error;
+
+    /// Parses the accounts file at `path`, which is in YAML if its extension is `.yaml` or `.yml`
+    /// and in JSON otherwise. Relative paths of custom classes are resolved against the directory
+    /// of the accounts file.
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let path = Path::new(path);
+        let content = std::fs::read_to_string(path)
+            .map_err(|source| Error::ReadFileError { source, path: path.display().to_string() })?;
+
+        let is_yaml = matches!(path.extension().and_then(|ext| ext.to_str()), Some("yaml" | "yml"));
+        let raw_entries: Vec<RawAccountsFileEntry> = if is_yaml {
+            serde_yaml::from_str(&content)
+                .map_err(|err| Error::InvalidAccountsFile { msg: err.to_string() })?
+        } else {
+            serde_json::from_str(&content)
+                .map_err(|err| Error::InvalidAccountsFile { msg: err.to_string() })?
+        };
+
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        let accounts = raw_entries
+            .into_iter()
+            .enumerate()
+            .map(|(i, raw_entry)| raw_entry.resolve(base_dir, i + 1))
+            .collect::<DevnetResult<_>>()?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl AccountsFile {
+    /// Identifies the accounts, with classes identified by their hashes
+    pub(crate) fn fingerprint(&self) -> String {
+        let accounts: Vec<_> = self
+            .accounts
+            .iter()
+            .map(|AccountsFileEntry { keys, class, eth_balance, strk_balance }| {
+                let keys = match keys {
+                    AccountKeys::PrivateKey(private_key) => json!({ "private_key": private_key }),
+                    AccountKeys::AddressAndPublicKey { address, public_key } => {
+                        json!({ "address": address, "public_key": public_key })
+                    }
+                };
+                json!({
+                    "keys": keys,
+                    "class_hash": class.as_ref().map(|class| class.class_hash),
+                    "eth_balance": eth_balance.as_ref().map(|balance| balance.to_string()),
+                    "strk_balance": strk_balance.as_ref().map(|balance| balance.to_string()),
+                })
+            })
+            .collect();
+
+        fingerprint(&json!(accounts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use super::{AccountKeys, AccountsFile, RawAccountsFileEntry};
+    use crate::error::Error;
+
+    const ACCOUNTS_FILE_PATH: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/test_artifacts/accounts_file.json");
+
+    #[test]
+    fn accounts_file_is_parsed() {
+        let accounts_file = AccountsFile::from_str(ACCOUNTS_FILE_PATH).unwrap();
+        assert_eq!(accounts_file.accounts.len(), 2);
+
+        let first = &accounts_file.accounts[0];
+        assert!(matches!(first.keys, AccountKeys::PrivateKey(_)));
+        assert!(first.class.is_some());
+        assert_eq!(first.eth_balance, Some(10_u32.pow(9).into()));
+        assert_eq!(first.strk_balance, Some(2_u32.into()));
+
+        let second = &accounts_file.accounts[1];
+        assert!(matches!(second.keys, AccountKeys::AddressAndPublicKey { .. }));
+        assert!(second.class.is_none());
+        assert_eq!(second.eth_balance, None);
+    }
+
+    #[test]
+    fn parsing_entry_with_missing_keys_fails() {
+        let raw_entries: Vec<RawAccountsFileEntry> =
+            serde_yaml::from_str("- address: \"0x1\"\n  eth_balance: \"1\"\n").unwrap();
+        let raw_entry = raw_entries.into_iter().next().unwrap();
+
+        match raw_entry.resolve(Path::new(""), 1) {
+            Err(Error::InvalidAccountsFile { msg }) => assert_eq!(
+                msg,
+                "Account #1: specify either private_key or both address and public_key"
+            ),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+}
@@ -90,6 +90,31 @@ impl StarknetBlocks {
         self.hash_to_state.insert(block_hash, state);
     }
 
+    /// Returns a copy of the collection without the archived states (`hash_to_state`). The states
+    /// of a block don't change once archived, so `restore` reuses them instead of copying.
+    pub(crate) fn clone_without_states(&self) -> Self {
+        Self {
+            num_to_hash: self.num_to_hash.clone(),
+            hash_to_block: self.hash_to_block.clone(),
+            pending_block: self.pending_block.clone(),
+            last_block_hash: self.last_block_hash,
+            hash_to_state_diff: self.hash_to_state_diff.clone(),
+            hash_to_state: HashMap::new(),
+            aborted_blocks: self.aborted_blocks.clone(),
+            starting_block_number: self.starting_block_number,
+        }
+    }
+
+    /// Replaces the collection with the one obtained via `clone_without_states`, keeping only the
+    /// archived states of the blocks present in it.
+    pub(crate) fn restore(&mut self, mut blocks: Self) {
+        let mut hash_to_state = std::mem::take(&mut self.hash_to_state);
+        hash_to_state.retain(|block_hash, _| blocks.hash_to_block.contains_key(block_hash));
+        blocks.hash_to_state = hash_to_state;
+
+        *self = blocks;
+    }
+
     fn get_by_latest_hash(&self) -> Option<&StarknetBlock> {
         if let Some(hash) = self.last_block_hash { self.get_by_hash(hash) } else { None }
     }
@@ -273,6 +298,10 @@ impl StarknetBlock {
     pub(crate) fn set_timestamp(&mut self, timestamp: BlockTimestamp) {
         self.header.timestamp = timestamp;
     }
+
+    pub(crate) fn set_state_root(&mut self, state_root: Felt) {
+        self.header.state_root = starknet_api::core::GlobalRoot(state_root);
+    }
 }
 
 impl HashProducer for StarknetBlock {
@@ -57,6 +57,11 @@ pub const ISRC6_ID_HEX: &str = "0x2ceccef7f994940b3962a6c67e0ba4fcd37df7d131417c
 
 pub const STARKNET_VERSION: &str = "0.13.2";
 
+/// The version of the JSON-RPC specification implemented by the types of Devnet
+pub const RPC_SPEC_VERSION: &str = "0.7.1";
+
+pub const DEVNET_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 /// ERC20 contracts storage variables
 /// taken from starkcan urls:
 /// https://testnet.starkscan.co/token/0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7#read-write-contract
@@ -68,7 +73,9 @@ pub const STRK_ERC20_SYMBOL: &str = "STRK";
 
 pub const DEVNET_DEFAULT_SEED: u32 = 123;
 pub const DEVNET_DEFAULT_TEST_SEED: u32 = 123;
-pub const DEVNET_DEFAULT_TOTAL_ACCOUNTS: u8 = 10;
+pub const DEVNET_DEFAULT_TOTAL_ACCOUNTS: u32 = 10;
+/// The path used by Starknet wallets; the index of the account is appended to it
+pub const DEVNET_DEFAULT_DERIVATION_PATH: &str = "m/44'/9004'/0'/0";
 pub const DEVNET_DEFAULT_INITIAL_BALANCE: u128 = 1_000_000_000_000_000_000_000;
 pub const DEVNET_DEFAULT_GAS_PRICE: NonZeroU128 = nonzero!(100_000_000_000u128);
 pub const DEVNET_DEFAULT_DATA_GAS_PRICE: NonZeroU128 = nonzero!(100_000_000_000u128);
@@ -28,6 +28,8 @@ pub enum Error {
     FileNotFound,
     #[error("Contract not found")]
     ContractNotFound,
+    #[error("A contract is already deployed at address {contract_address:x}")]
+    ContractAlreadyDeployed { contract_address: ContractAddress },
     #[error(transparent)]
     SignError(#[from] starknet_rs_signers::local_wallet::SignError),
     #[error("{msg}")]
@@ -40,6 +42,12 @@ pub enum Error {
     FormatError,
     #[error("No transaction found")]
     NoTransaction,
+    #[error("The dump is incompatible with this Devnet: {msg}")]
+    IncompatibleDump { msg: String },
+    #[error("The dump does not reach the requested point: {msg}")]
+    LoadUntilOutOfRange { msg: String },
+    #[error("No snapshot with id {snapshot_id}")]
+    NoSnapshot { snapshot_id: u64 },
     #[error("Invalid transaction index in a block")]
     InvalidTransactionIndexInBlock,
     #[error("Unsupported transaction type")]
@@ -48,6 +56,12 @@ pub enum Error {
     UnsupportedAction { msg: String },
     #[error("Unexpected internal error: {msg}")]
     UnexpectedInternalError { msg: String },
+    #[error("Invalid genesis: {msg}")]
+    InvalidGenesis { msg: String },
+    #[error("Invalid accounts file: {msg}")]
+    InvalidAccountsFile { msg: String },
+    #[error("Invalid mnemonic: {msg}")]
+    InvalidMnemonic { msg: String },
     #[error("Failed to load ContractClass: {0}")]
     ContractClassLoadError(String),
     #[error("Deserialization error: {origin}")]
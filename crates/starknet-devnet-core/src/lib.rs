@@ -1,9 +1,11 @@
 pub mod account;
+pub mod accounts_file;
 mod blocks;
 pub mod constants;
 pub mod contract_class_choice;
 pub mod error;
 pub mod messaging;
+pub mod mnemonic;
 mod predeployed_accounts;
 pub mod raw_execution;
 pub mod starknet;
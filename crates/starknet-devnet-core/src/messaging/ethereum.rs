@@ -6,7 +6,7 @@ use ethers::prelude::*;
 use ethers::providers::{Http, Provider, ProviderError};
 use ethers::types::{Address, BlockNumber, Log};
 use k256::ecdsa::SigningKey;
-use starknet_rs_core::types::Felt;
+use starknet_rs_core::types::{Felt, Hash256};
 use starknet_types::felt::felt_from_prefixed_hex;
 use starknet_types::rpc::contract_address::ContractAddress;
 use starknet_types::rpc::messaging::{MessageToL1, MessageToL2};
@@ -335,6 +335,7 @@ impl EthereumMessaging {
 ///
 /// * `log` - The log to be converted.
 pub fn message_to_l2_from_log(log: Log) -> DevnetResult<MessageToL2> {
+    let l1_transaction_hash = log.transaction_hash.map(|hash| Hash256::from_bytes(hash.0));
     let parsed_log = <LogMessageToL2 as EthLogDecode>::decode_log(&log.into()).map_err(|e| {
         Error::MessagingError(MessagingError::EthersError(format!("Log parsing failed {}", e)))
     })?;
@@ -357,6 +358,7 @@ pub fn message_to_l2_from_log(log: Log) -> DevnetResult<MessageToL2> {
         payload,
         paid_fee_on_l1,
         nonce,
+        l1_transaction_hash,
     })
 }
 
@@ -416,6 +418,8 @@ mod tests {
         let selector = "0x02f15cff7b0eed8b9beb162696cf4e3e0e35fa7032af69cd1b7d2ac67a13f40f";
         let nonce = 783082_u128;
         let fee = 30000_u128;
+        let l1_transaction_hash =
+            "0x2f0a3b9c8d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8";
 
         // Payload two values: [1, 2].
         let payload_buf = hex::decode("000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000bf2ea0000000000000000000000000000000000000000000000000000000000007530000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002").unwrap();
@@ -434,6 +438,7 @@ mod tests {
                 H256::from_str(selector).unwrap(),
             ],
             data: payload_buf.into(),
+            transaction_hash: Some(H256::from_str(l1_transaction_hash).unwrap()),
             ..Default::default()
         };
 
@@ -448,6 +453,7 @@ mod tests {
             payload,
             nonce: nonce.into(),
             paid_fee_on_l1: fee.into(),
+            l1_transaction_hash: Some(Hash256::from_hex(l1_transaction_hash).unwrap()),
         };
 
         let message = message_to_l2_from_log(log).unwrap();
@@ -63,6 +63,10 @@ pub struct MessagingBroker {
     pub l2_to_l1_messages_hashes: HashMap<String, u64>,
     /// This list of messages that will be sent to L1 node at the next `postman/flush`.
     pub l2_to_l1_messages_to_flush: Vec<MessageToL1>,
+    /// Mapping of L1 transaction hash to the hashes of the `L1HandlerTransaction`s generated from
+    /// the messages sent by that L1 transaction. Using `String` keys for the same reason as in
+    /// `l2_to_l1_messages_hashes`.
+    pub l1_to_l2_tx_hashes: HashMap<String, Vec<TransactionHash>>,
 }
 
 impl MessagingBroker {
@@ -97,6 +101,24 @@ impl MessagingBroker {
 
         Ok(self.ethereum.as_mut().unwrap())
     }
+
+    /// Returns a copy of the messages and message hashes, without the ethereum broker. L1 is not
+    /// reverted together with Devnet, so `restore` keeps the currently configured broker.
+    pub(crate) fn clone_without_ethereum(&self) -> Self {
+        Self {
+            ethereum: None,
+            last_local_block: self.last_local_block,
+            l2_to_l1_messages_hashes: self.l2_to_l1_messages_hashes.clone(),
+            l2_to_l1_messages_to_flush: self.l2_to_l1_messages_to_flush.clone(),
+            l1_to_l2_tx_hashes: self.l1_to_l2_tx_hashes.clone(),
+        }
+    }
+
+    /// Replaces the messages and message hashes with the ones obtained via
+    /// `clone_without_ethereum`.
+    pub(crate) fn restore(&mut self, messaging: Self) {
+        *self = Self { ethereum: self.ethereum.take(), ..messaging };
+    }
 }
 
 impl Starknet {
@@ -230,13 +252,50 @@ impl Starknet {
         for message in messages {
             let transaction = L1HandlerTransaction::try_from_message_to_l2(message.clone())?;
 
-            let transaction_hash = self.add_l1_handler_transaction(transaction)?;
+            let transaction_hash =
+                self.add_l1_handler_transaction(transaction, message.l1_transaction_hash)?;
+
             transactions_hashes.push(transaction_hash);
         }
 
         Ok(transactions_hashes)
     }
 
+    /// Associates the `L1HandlerTransaction` with the L1 transaction which sent its message.
+    ///
+    /// # Arguments
+    ///
+    /// * `l1_transaction_hash` - The hash of the L1 transaction.
+    /// * `l2_transaction_hash` - The hash of the generated `L1HandlerTransaction`.
+    pub(crate) fn register_l1_handler_transaction(
+        &mut self,
+        l1_transaction_hash: Hash256,
+        l2_transaction_hash: TransactionHash,
+    ) {
+        self.messaging
+            .l1_to_l2_tx_hashes
+            .entry(format!("{l1_transaction_hash}"))
+            .or_default()
+            .push(l2_transaction_hash);
+    }
+
+    /// Returns the hashes of the `L1HandlerTransaction`s generated from the messages sent by the
+    /// given L1 transaction, an error if no such transaction is known.
+    ///
+    /// # Arguments
+    ///
+    /// * `l1_transaction_hash` - The hash of the L1 transaction.
+    pub fn get_l1_handler_transaction_hashes(
+        &self,
+        l1_transaction_hash: Hash256,
+    ) -> DevnetResult<Vec<TransactionHash>> {
+        self.messaging
+            .l1_to_l2_tx_hashes
+            .get(&format!("{l1_transaction_hash}"))
+            .cloned()
+            .ok_or(Error::NoTransaction)
+    }
+
     /// Collects all messages for all the transactions of the given block.
     ///
     /// # Arguments
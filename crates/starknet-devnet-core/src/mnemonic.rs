@@ -0,0 +1,249 @@
+use coins_bip32::xkeys::XPriv;
+use ethers::core::k256::ecdsa::SigningKey;
+use ethers::signers::coins_bip39::{English, Mnemonic};
+use num_bigint::BigUint;
+use serde::Serialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use starknet_types::felt::Key;
+
+use crate::constants::DEVNET_DEFAULT_DERIVATION_PATH;
+use crate::error::{DevnetResult, Error};
+use crate::utils::fingerprint;
+
+/// Order of the Stark curve
+const STARK_CURVE_ORDER_HEX: &str =
+    "0800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f";
+
+/// The path of the Ethereum key from which Argent derives Stark keys
+const ARGENT_ETHEREUM_KEY_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// How the keys of predeployed accounts are derived from a mnemonic, as done by different wallets
+#[derive(Default, Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MnemonicScheme {
+    /// Derived from the mnemonic's master key
+    #[default]
+    Braavos,
+    /// Derived from a master key whose seed is the Ethereum key of the mnemonic
+    Argent,
+}
+
+/// A BIP-39 mnemonic phrase from which the keys of predeployed accounts are derived. The key of
+/// the account with index `i` is derived at `<derivation_path>/<i>` and ground to the Stark curve.
+/// With the Braavos scheme, the derivation starts from the master key of the phrase. With the
+/// Argent scheme, it starts from a master key seeded with the Ethereum key derived at
+/// `m/44'/60'/0'/0/0`.
+#[derive(Clone, Debug)]
+pub struct AccountMnemonic {
+    phrase: String,
+    derivation_path: String,
+    scheme: MnemonicScheme,
+}
+
+impl AccountMnemonic {
+    /// Validates the phrase and the derivation path; defaults to `m/44'/9004'/0'/0` if no path is
+    /// provided
+    pub fn new(
+        phrase: &str,
+        derivation_path: Option<&str>,
+        scheme: MnemonicScheme,
+    ) -> DevnetResult<Self> {
+        let derivation_path = derivation_path.unwrap_or(DEVNET_DEFAULT_DERIVATION_PATH);
+        let mnemonic = Self {
+            phrase: phrase.split_whitespace().collect::<Vec<_>>().join(" "),
+            derivation_path: derivation_path.trim_end_matches('/').to_string(),
+            scheme,
+        };
+        mnemonic.derive_private_keys(1)?;
+
+        Ok(mnemonic)
+    }
+
+    pub fn derivation_path(&self) -> &str {
+        &self.derivation_path
+    }
+
+    pub fn scheme(&self) -> MnemonicScheme {
+        self.scheme
+    }
+
+    /// Identifies the phrase, the derivation path and the scheme without revealing them
+    pub(crate) fn fingerprint(&self) -> String {
+        fingerprint(&json!({
+            "phrase": self.phrase,
+            "derivation_path": self.derivation_path,
+            "scheme": self.scheme,
+        }))
+    }
+
+    pub(crate) fn derive_private_keys(&self, number_of_keys: u32) -> DevnetResult<Vec<Key>> {
+        Ok(self
+            .derive_secp256k1_keys(number_of_keys)?
+            .iter()
+            .map(|key_seed| grind_key(key_seed))
+            .collect())
+    }
+
+    /// Derives the BIP-32 keys which are ground to Stark keys
+    fn derive_secp256k1_keys(&self, number_of_keys: u32) -> DevnetResult<Vec<Vec<u8>>> {
+        let mnemonic = Mnemonic::<English>::new_from_phrase(&self.phrase)
+            .map_err(|err| Error::InvalidMnemonic { msg: err.to_string() })?;
+        let master_key = mnemonic
+            .master_key(None)
+            .map_err(|err| Error::InvalidMnemonic { msg: err.to_string() })?;
+
+        let root_key = match self.scheme {
+            MnemonicScheme::Braavos => master_key,
+            MnemonicScheme::Argent => {
+                // the key is used as a seed the way ethers.js encodes it, i.e. as a number
+                // without leading zero bytes
+                let ethereum_key = derive_key(&master_key, ARGENT_ETHEREUM_KEY_PATH)?;
+                let first_non_zero =
+                    ethereum_key.iter().position(|byte| *byte != 0).unwrap_or(ethereum_key.len());
+                XPriv::root_from_seed(&ethereum_key[first_non_zero..], None)
+                    .map_err(|err| Error::InvalidMnemonic { msg: err.to_string() })?
+            }
+        };
+
+        (0..number_of_keys)
+            .map(|index| derive_key(&root_key, &format!("{}/{index}", self.derivation_path)))
+            .collect()
+    }
+}
+
+/// Derives the private key at `path` from `root_key`
+fn derive_key(root_key: &XPriv, path: &str) -> DevnetResult<Vec<u8>> {
+    let child_key = root_key
+        .derive_path(path)
+        .map_err(|err| Error::InvalidMnemonic { msg: err.to_string() })?;
+    let signing_key: &SigningKey = child_key.as_ref();
+    Ok(signing_key.to_bytes().to_vec())
+}
+
+/// Maps a secp256k1 private key to a Stark private key without bias, by hashing it with an
+/// increasing index until the hash is below the largest multiple of the curve order
+fn grind_key(key_seed: &[u8]) -> Key {
+    let curve_order = BigUint::parse_bytes(STARK_CURVE_ORDER_HEX.as_bytes(), 16).unwrap();
+    let sha256_space = BigUint::from(1_u8) << 256;
+    let max_allowed_value = &sha256_space - (&sha256_space % &curve_order);
+
+    let mut index = BigUint::from(0_u8);
+    loop {
+        let hash =
+            Sha256::new().chain_update(key_seed).chain_update(index.to_bytes_be()).finalize();
+        let key = BigUint::from_bytes_be(&hash);
+        if key < max_allowed_value {
+            return Key::from_bytes_be_slice(&(key % curve_order).to_bytes_be());
+        }
+
+        index += 1_u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_types::felt::Key;
+
+    use super::{grind_key, AccountMnemonic, MnemonicScheme};
+    use crate::error::Error;
+
+    const PHRASE: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn equal_mnemonics_derive_equal_keys() {
+        let keys = AccountMnemonic::new(PHRASE, None, MnemonicScheme::Braavos)
+            .unwrap()
+            .derive_private_keys(3)
+            .unwrap();
+        let keys_again = AccountMnemonic::new(
+            &format!("  {PHRASE} "),
+            Some("m/44'/9004'/0'/0/"),
+            MnemonicScheme::Braavos,
+        )
+        .unwrap()
+        .derive_private_keys(3)
+        .unwrap();
+
+        assert_eq!(keys, keys_again);
+        assert_ne!(keys[0], keys[1]);
+        assert_ne!(keys[1], keys[2]);
+    }
+
+    #[test]
+    fn different_derivation_paths_derive_different_keys() {
+        let keys = AccountMnemonic::new(PHRASE, None, MnemonicScheme::Braavos)
+            .unwrap()
+            .derive_private_keys(1)
+            .unwrap();
+        let other_keys =
+            AccountMnemonic::new(PHRASE, Some("m/44'/9004'/1'/0"), MnemonicScheme::Braavos)
+                .unwrap()
+                .derive_private_keys(1)
+                .unwrap();
+
+        assert_ne!(keys, other_keys);
+    }
+
+    #[test]
+    fn derives_known_secp256k1_key() {
+        // the first account of the default Hardhat mnemonic, derived at the Ethereum path
+        let keys = AccountMnemonic::new(PHRASE, Some("m/44'/60'/0'/0"), MnemonicScheme::Braavos)
+            .unwrap()
+            .derive_secp256k1_keys(1)
+            .unwrap();
+
+        assert_eq!(
+            hex::encode(&keys[0]),
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        );
+    }
+
+    #[test]
+    fn derives_known_stark_keys_of_each_scheme() {
+        // the Ethereum key of the phrase (ac0974...ff80) seeds the Argent derivation
+        for (scheme, expected_key) in [
+            (
+                MnemonicScheme::Braavos,
+                "0x6a9c4ecd67b5a868c4e1ff108da4735c573881681e83aae0d2c9382a410857f",
+            ),
+            (
+                MnemonicScheme::Argent,
+                "0xeb2609ea42924e99d0d5aa50ef056210a985a69bc53b50e48cfa3ae954ffc0",
+            ),
+        ] {
+            let keys =
+                AccountMnemonic::new(PHRASE, None, scheme).unwrap().derive_private_keys(1).unwrap();
+            assert_eq!(keys[0], Key::from_hex(expected_key).unwrap(), "{scheme:?}");
+        }
+    }
+
+    #[test]
+    fn different_schemes_have_different_fingerprints() {
+        let fingerprint =
+            |scheme| AccountMnemonic::new(PHRASE, None, scheme).unwrap().fingerprint();
+        assert_ne!(fingerprint(MnemonicScheme::Braavos), fingerprint(MnemonicScheme::Argent));
+    }
+
+    #[test]
+    fn grinds_known_stark_key() {
+        // the vector of `grindKey` in starknet.js
+        let key_seed =
+            hex::decode("86F3E7293141F20A8BAFF320E8EE4ACCB9D4A4BF2B4D295E8CEE784DB46E0519")
+                .unwrap();
+
+        assert_eq!(
+            grind_key(&key_seed),
+            Key::from_hex("0x5c8c8683596c732541a59e03007b2d30dbbbb873556fe65b5fb63c16688f941")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_phrase_is_rejected() {
+        match AccountMnemonic::new("not a valid phrase", None, MnemonicScheme::Braavos) {
+            Err(Error::InvalidMnemonic { .. }) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+}
@@ -1,3 +1,8 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use blockifier::state::cached_state::StorageEntry;
+use starknet_rs_core::types::Felt;
 use starknet_rs_signers::SigningKey;
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_class::ContractClass;
@@ -5,13 +10,16 @@ use starknet_types::felt::{ClassHash, Key};
 use starknet_types::rpc::state::Balance;
 
 use crate::account::Account;
-use crate::error::DevnetResult;
+use crate::accounts_file::{AccountKeys, AccountsFile};
+use crate::error::{DevnetResult, Error};
+use crate::mnemonic::AccountMnemonic;
 use crate::traits::AccountGenerator;
 use crate::utils::random_number_generator::generate_u128_random_numbers;
 
 #[derive(Default)]
 pub(crate) struct PredeployedAccounts {
     seed: u32,
+    mnemonic: Option<AccountMnemonic>,
     initial_balance: Balance,
     eth_fee_token_address: ContractAddress,
     strk_fee_token_address: ContractAddress,
@@ -21,12 +29,14 @@ pub(crate) struct PredeployedAccounts {
 impl PredeployedAccounts {
     pub(crate) fn new(
         seed: u32,
+        mnemonic: Option<AccountMnemonic>,
         initial_balance: Balance,
         eth_fee_token_address: ContractAddress,
         strk_fee_token_address: ContractAddress,
     ) -> Self {
         Self {
             seed,
+            mnemonic,
             initial_balance,
             eth_fee_token_address,
             strk_fee_token_address,
@@ -36,9 +46,13 @@ impl PredeployedAccounts {
 }
 
 impl PredeployedAccounts {
-    fn generate_private_keys(&self, number_of_accounts: u8) -> Vec<Key> {
+    fn generate_private_keys(&self, number_of_accounts: u32) -> DevnetResult<Vec<Key>> {
+        if let Some(mnemonic) = &self.mnemonic {
+            return mnemonic.derive_private_keys(number_of_accounts);
+        }
+
         let random_numbers = generate_u128_random_numbers(self.seed, number_of_accounts);
-        random_numbers.into_iter().map(Key::from).collect::<Vec<Key>>()
+        Ok(random_numbers.into_iter().map(Key::from).collect::<Vec<Key>>())
     }
 
     fn generate_public_key(&self, private_key: &Key) -> Key {
@@ -48,6 +62,58 @@ impl PredeployedAccounts {
     pub fn get_accounts(&self) -> &Vec<Account> {
         &self.accounts
     }
+
+    /// Generates the accounts specified in `accounts_file`. Entries without a class or without
+    /// balances use `class_hash`, `contract_class` and the initial balance of seed-generated
+    /// accounts.
+    pub(crate) fn generate_accounts_from_file(
+        &mut self,
+        accounts_file: &AccountsFile,
+        class_hash: ClassHash,
+        contract_class: &ContractClass,
+    ) -> DevnetResult<&Vec<Account>> {
+        let contract_class = Arc::new(contract_class.clone());
+        let mut addresses = HashSet::new();
+        for entry in &accounts_file.accounts {
+            let (public_key, private_key, account_address) = match entry.keys {
+                AccountKeys::PrivateKey(private_key) => {
+                    let public_key = self.generate_public_key(&private_key);
+                    let account_address = Account::compute_account_address(&public_key)?;
+                    (public_key, Some(private_key), account_address)
+                }
+                AccountKeys::AddressAndPublicKey { address, public_key } => {
+                    (public_key, None, address)
+                }
+            };
+
+            let (class_hash, contract_class) = match &entry.class {
+                Some(class_wrapper) => {
+                    (class_wrapper.class_hash, Arc::new(class_wrapper.contract_class.clone()))
+                }
+                None => (class_hash, contract_class.clone()),
+            };
+
+            if !addresses.insert(account_address) {
+                return Err(Error::InvalidAccountsFile {
+                    msg: format!("More than one account at address {account_address:x}"),
+                });
+            }
+
+            self.accounts.push(Account::new(
+                entry.eth_balance.clone().unwrap_or_else(|| self.initial_balance.clone()),
+                entry.strk_balance.clone().unwrap_or_else(|| self.initial_balance.clone()),
+                public_key,
+                private_key,
+                account_address,
+                class_hash,
+                contract_class,
+                self.eth_fee_token_address,
+                self.strk_fee_token_address,
+            ));
+        }
+
+        Ok(&self.accounts)
+    }
 }
 
 impl AccountGenerator for PredeployedAccounts {
@@ -55,22 +121,27 @@ impl AccountGenerator for PredeployedAccounts {
 
     fn generate_accounts(
         &mut self,
-        number_of_accounts: u8,
+        number_of_accounts: u32,
         class_hash: ClassHash,
         contract_class: &ContractClass,
     ) -> DevnetResult<&Vec<Self::Acc>> {
-        let private_keys = self.generate_private_keys(number_of_accounts);
+        let private_keys = self.generate_private_keys(number_of_accounts)?;
+        // shared by all accounts, which may be numerous
+        let contract_class = Arc::new(contract_class.clone());
 
         for private_key in private_keys {
+            let public_key = self.generate_public_key(&private_key);
             let account = Account::new(
                 self.initial_balance.clone(),
-                self.generate_public_key(&private_key),
-                private_key,
+                self.initial_balance.clone(),
+                public_key,
+                Some(private_key),
+                Account::compute_account_address(&public_key)?,
                 class_hash,
                 contract_class.clone(),
                 self.eth_fee_token_address,
                 self.strk_fee_token_address,
-            )?;
+            );
             self.accounts.push(account);
         }
 
@@ -78,6 +149,52 @@ impl AccountGenerator for PredeployedAccounts {
     }
 }
 
+/// The class hashes and storage of lazily predeployed accounts. Instead of being written to the
+/// state on startup, they are supplied by `StarknetDefaulter` when read for the first time, so
+/// the state only contains the accounts that have been used.
+#[derive(Debug, Default)]
+pub(crate) struct LazyAccountsState {
+    class_hashes: HashMap<starknet_api::core::ContractAddress, starknet_api::core::ClassHash>,
+    storage: HashMap<StorageEntry, Felt>,
+}
+
+impl LazyAccountsState {
+    /// Computes the state of all `accounts` upfront, so this takes time linear in their number;
+    /// only writing it to the state, and thus committing to it, is avoided
+    pub(crate) fn new(accounts: &[Account]) -> DevnetResult<Self> {
+        let mut lazy_state = Self::default();
+        for account in accounts {
+            lazy_state.class_hashes.insert(
+                account.account_address.try_into()?,
+                starknet_api::core::ClassHash(account.class_hash),
+            );
+
+            for (contract_address, key, value) in
+                account.initial_balance_storage()?.into_iter().chain(account.constructor_storage()?)
+            {
+                lazy_state.storage.insert((contract_address, key), value);
+            }
+        }
+
+        Ok(lazy_state)
+    }
+
+    pub(crate) fn get_storage_at(
+        &self,
+        contract_address: starknet_api::core::ContractAddress,
+        key: starknet_api::state::StorageKey,
+    ) -> Option<Felt> {
+        self.storage.get(&(contract_address, key)).copied()
+    }
+
+    pub(crate) fn get_class_hash_at(
+        &self,
+        contract_address: starknet_api::core::ContractAddress,
+    ) -> Option<starknet_api::core::ClassHash> {
+        self.class_hashes.get(&contract_address).copied()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::{thread_rng, Rng};
@@ -93,19 +210,23 @@ mod tests {
 
             let private_key1 = PredeployedAccounts::new(
                 seed,
+                None,
                 Balance::from(1_u8),
                 dummy_contract_address(),
                 dummy_contract_address(),
             )
-            .generate_private_keys(1)[0];
+            .generate_private_keys(1)
+            .unwrap()[0];
 
             let private_key2 = PredeployedAccounts::new(
                 seed,
+                None,
                 Balance::from(1_u8),
                 dummy_contract_address(),
                 dummy_contract_address(),
             )
-            .generate_private_keys(1)[0];
+            .generate_private_keys(1)
+            .unwrap()[0];
 
             assert_eq!(private_key1, private_key2);
         }
@@ -130,19 +251,23 @@ mod tests {
 
             let private_key1 = PredeployedAccounts::new(
                 seed1,
+                None,
                 Balance::from(1_u8),
                 dummy_contract_address(),
                 dummy_contract_address(),
             )
-            .generate_private_keys(1)[0];
+            .generate_private_keys(1)
+            .unwrap()[0];
 
             let private_key2 = PredeployedAccounts::new(
                 seed2,
+                None,
                 Balance::from(1_u8),
                 dummy_contract_address(),
                 dummy_contract_address(),
             )
-            .generate_private_keys(1)[0];
+            .generate_private_keys(1)
+            .unwrap()[0];
 
             assert_ne!(private_key1, private_key2);
         }
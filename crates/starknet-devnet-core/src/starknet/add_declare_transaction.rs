@@ -12,7 +12,7 @@ use super::dump::DumpEvent;
 use crate::error::{DevnetResult, Error};
 use crate::starknet::Starknet;
 use crate::state::CustomState;
-use crate::utils::calculate_casm_hash;
+use crate::utils::{calculate_casm_hash, compile_sierra_contract};
 
 pub fn add_declare_transaction(
     starknet: &mut Starknet,
@@ -115,15 +115,7 @@ fn assert_casm_hash_is_valid(
     match (contract_class, received_casm_hash) {
         (ContractClass::Cairo0(_), None) => Ok(()), // if cairo0, casm_hash expected to be None
         (ContractClass::Cairo1(cairo_lang_contract_class), Some(received_casm_hash)) => {
-            let casm_json = usc::compile_contract(
-                serde_json::to_value(cairo_lang_contract_class)
-                    .map_err(|err| Error::SerializationError { origin: err.to_string() })?,
-            )
-            .map_err(|err| {
-                let reason = err.to_string();
-                Error::TypesError(starknet_types::error::Error::SierraCompilationError { reason })
-            })?;
-
+            let casm_json = compile_sierra_contract(cairo_lang_contract_class)?;
             let calculated_casm_hash = calculate_casm_hash(casm_json)?;
             if calculated_casm_hash == received_casm_hash {
                 Ok(())
@@ -66,6 +66,7 @@ pub fn add_invoke_transaction(
 mod tests {
 
     use core::panic;
+    use std::sync::Arc;
 
     use blockifier::state::state_api::StateReader;
     use nonzero_ext::nonzero;
@@ -489,15 +490,16 @@ mod tests {
             account_without_validations_contract_class.generate_hash().unwrap();
 
         let account = Account::new(
+            Balance::from(10000_u32),
             Balance::from(10000_u32),
             dummy_felt(),
-            dummy_felt(),
+            Some(dummy_felt()),
+            Account::compute_account_address(&dummy_felt()).unwrap(),
             account_without_validations_class_hash,
-            ContractClass::Cairo0(account_without_validations_contract_class),
+            Arc::new(ContractClass::Cairo0(account_without_validations_contract_class)),
             eth_erc_20_contract.get_address(),
             strk_erc_20_contract.get_address(),
-        )
-        .unwrap();
+        );
 
         account.deploy(&mut starknet.pending_state).unwrap();
 
@@ -1,4 +1,5 @@
 use blockifier::transaction::transactions::ExecutableTransaction;
+use starknet_rs_core::types::Hash256;
 use starknet_types::felt::TransactionHash;
 use starknet_types::rpc::transactions::l1_handler_transaction::L1HandlerTransaction;
 use starknet_types::rpc::transactions::{Transaction, TransactionWithHash};
@@ -6,11 +7,12 @@ use tracing::trace;
 
 use super::Starknet;
 use crate::error::DevnetResult;
-use crate::starknet::dump::DumpEvent;
+use crate::starknet::dump::{DumpEvent, DumpedL1HandlerTransaction};
 
 pub fn add_l1_handler_transaction(
     starknet: &mut Starknet,
     transaction: L1HandlerTransaction,
+    l1_transaction_hash: Option<Hash256>,
 ) -> DevnetResult<TransactionHash> {
     let blockifier_transaction =
         transaction.create_blockifier_transaction(starknet.chain_id().to_felt())?;
@@ -34,7 +36,13 @@ pub fn add_l1_handler_transaction(
         TransactionWithHash::new(transaction_hash, Transaction::L1Handler(transaction.clone())),
         blockifier_execution_result,
     )?;
-    starknet.handle_dump_event(DumpEvent::AddL1HandlerTransaction(transaction))?;
+    if let Some(l1_transaction_hash) = l1_transaction_hash {
+        starknet.register_l1_handler_transaction(l1_transaction_hash, transaction_hash);
+    }
+    starknet.handle_dump_event(DumpEvent::AddL1HandlerTransaction(DumpedL1HandlerTransaction {
+        transaction,
+        l1_transaction_hash,
+    }))?;
 
     Ok(transaction_hash)
 }
@@ -44,6 +52,8 @@ mod tests {
     // Constants taken from test_estimate_message_fee.rs.
     const WHITELISTED_L1_ADDRESS: &str = "0x8359E4B0152ed5A731162D3c7B0D8D56edB165A0";
 
+    use std::sync::Arc;
+
     use blockifier::execution::errors::{EntryPointExecutionError, PreExecutionError};
     use blockifier::transaction::errors::TransactionExecutionError::ExecutionError;
     use nonzero_ext::nonzero;
@@ -110,7 +120,7 @@ mod tests {
             vec![Felt::from(11), Felt::from(9999)],
         );
 
-        let transaction_hash = starknet.add_l1_handler_transaction(transaction).unwrap();
+        let transaction_hash = starknet.add_l1_handler_transaction(transaction, None).unwrap();
 
         let state_transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
 
@@ -132,7 +142,7 @@ mod tests {
             vec![Felt::from(11), Felt::from(9999)],
         );
 
-        let result = starknet.add_l1_handler_transaction(transaction);
+        let result = starknet.add_l1_handler_transaction(transaction, None);
 
         match result {
             Err(crate::error::Error::BlockifierTransactionError(ExecutionError {
@@ -196,15 +206,16 @@ mod tests {
             account_without_validations_contract_class.generate_hash().unwrap();
 
         let account = Account::new(
+            Balance::from(10000_u32),
             Balance::from(10000_u32),
             dummy_felt(),
-            dummy_felt(),
+            Some(dummy_felt()),
+            Account::compute_account_address(&dummy_felt()).unwrap(),
             account_without_validations_class_hash,
-            ContractClass::Cairo0(account_without_validations_contract_class),
+            Arc::new(ContractClass::Cairo0(account_without_validations_contract_class)),
             eth_erc_20_contract.get_address(),
             strk_erc_20_contract.get_address(),
-        )
-        .unwrap();
+        );
 
         account.deploy(&mut starknet.pending_state).unwrap();
 
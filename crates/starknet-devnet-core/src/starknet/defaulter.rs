@@ -1,4 +1,5 @@
 use std::io::Read;
+use std::sync::Arc;
 
 use blockifier::execution::contract_class::ContractClass;
 use blockifier::state::errors::StateError;
@@ -10,6 +11,7 @@ use starknet_types::contract_class::convert_codegen_to_blockifier_compiled_class
 use tracing::debug;
 
 use super::starknet_config::ForkConfig;
+use crate::predeployed_accounts::LazyAccountsState;
 
 #[derive(thiserror::Error, Debug)]
 enum OriginError {
@@ -88,10 +90,12 @@ impl BlockingOriginReader {
 }
 
 /// Used for forking - reads from the origin if Some(origin_reader); otherwise returns the default
-/// or Err, depending on the method
+/// or Err, depending on the method. Before that, the state of lazily predeployed accounts is
+/// checked, if there is any.
 #[derive(Clone, Debug, Default)]
 pub struct StarknetDefaulter {
     origin_reader: Option<BlockingOriginReader>,
+    lazy_accounts: Option<Arc<LazyAccountsState>>,
 }
 
 impl StarknetDefaulter {
@@ -102,7 +106,23 @@ impl StarknetDefaulter {
             } else {
                 None
             };
-        Self { origin_reader }
+        Self { origin_reader, lazy_accounts: None }
+    }
+
+    pub(crate) fn set_lazy_accounts(&mut self, lazy_accounts: Arc<LazyAccountsState>) {
+        self.lazy_accounts = Some(lazy_accounts);
+    }
+
+    pub(crate) fn is_lazily_predeployed(&self, contract_address: ContractAddress) -> bool {
+        self.lazily_predeployed_class_hash(contract_address).is_some()
+    }
+
+    /// Unlike `get_class_hash_at`, never reads from the origin
+    pub(crate) fn lazily_predeployed_class_hash(
+        &self,
+        contract_address: ContractAddress,
+    ) -> Option<ClassHash> {
+        self.lazy_accounts.as_ref().and_then(|lazy| lazy.get_class_hash_at(contract_address))
     }
 
     pub fn get_storage_at(
@@ -110,6 +130,12 @@ impl StarknetDefaulter {
         contract_address: ContractAddress,
         key: StorageKey,
     ) -> StateResult<Felt> {
+        if let Some(value) =
+            self.lazy_accounts.as_ref().and_then(|lazy| lazy.get_storage_at(contract_address, key))
+        {
+            return Ok(value);
+        }
+
         if let Some(origin) = &self.origin_reader {
             origin.get_storage_at(contract_address, key)
         } else {
@@ -134,6 +160,12 @@ impl StarknetDefaulter {
     }
 
     pub fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        if let Some(class_hash) =
+            self.lazy_accounts.as_ref().and_then(|lazy| lazy.get_class_hash_at(contract_address))
+        {
+            return Ok(class_hash);
+        }
+
         if let Some(origin) = &self.origin_reader {
             origin.get_class_hash_at(contract_address)
         } else {
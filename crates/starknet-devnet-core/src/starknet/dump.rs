@@ -1,17 +1,34 @@
-use std::fs::{self, File, OpenOptions};
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
+use std::num::NonZeroU128;
 use std::path::Path;
 
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use serde::{Deserialize, Serialize};
+use starknet_rs_core::types::{BlockId, Felt, Hash256};
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::{ClassHash, Nonce};
+use starknet_types::patricia_key::StorageKey;
+use starknet_types::rpc::gas_modification::GasModificationRequest;
+use starknet_types::rpc::state::StorageEntry;
 use starknet_types::rpc::transactions::l1_handler_transaction::L1HandlerTransaction;
 use starknet_types::rpc::transactions::{
     BroadcastedDeclareTransaction, BroadcastedDeployAccountTransaction,
     BroadcastedInvokeTransaction,
 };
+use tracing::warn;
 
-use super::starknet_config::StarknetConfig;
+use super::genesis::Genesis;
+use super::starknet_config::{BlockGenerationOn, DumpFormat, LoadUntil, StarknetConfig};
+use super::state_dump::StateDump;
 use super::{DumpOn, Starknet};
+use crate::accounts_file::AccountsFile;
+use crate::constants::{DEVNET_VERSION, RPC_SPEC_VERSION, STARKNET_VERSION};
 use crate::error::{DevnetResult, Error};
+use crate::mnemonic::AccountMnemonic;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum DumpEvent {
@@ -21,19 +38,378 @@ pub enum DumpEvent {
     AddDeclareTransaction(BroadcastedDeclareTransaction),
     AddInvokeTransaction(BroadcastedInvokeTransaction),
     AddDeployAccountTransaction(BroadcastedDeployAccountTransaction),
-    AddL1HandlerTransaction(L1HandlerTransaction),
+    AddL1HandlerTransaction(DumpedL1HandlerTransaction),
+    SetGasPrice(GasModificationRequest),
+    /// The timestamp of a generated block; recorded before the event that generated the block, so
+    /// that re-executing the event generates a block with the same timestamp
+    BlockTimestamp(u64),
+    SetStorageAt {
+        contract_address: ContractAddress,
+        key: StorageKey,
+        value: Felt,
+    },
+    SetNonce {
+        contract_address: ContractAddress,
+        nonce: Nonce,
+    },
+    ReplaceClass {
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+    },
+    DeployAt {
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+        storage: Vec<StorageEntry>,
+    },
+    /// Recorded with the hash of the starting block, even if it was specified differently
+    AcceptOnL1 {
+        starting_block_id: BlockId,
+    },
+}
+
+impl DumpEvent {
+    /// Returns false for events recorded by Devnet itself rather than requested
+    fn is_requested(&self) -> bool {
+        !matches!(self, DumpEvent::BlockTimestamp(_))
+    }
+
+    fn is_transaction(&self) -> bool {
+        matches!(
+            self,
+            DumpEvent::AddDeclareTransaction(_)
+                | DumpEvent::AddInvokeTransaction(_)
+                | DumpEvent::AddDeployAccountTransaction(_)
+                | DumpEvent::AddL1HandlerTransaction(_)
+        )
+    }
+}
+
+/// An `L1HandlerTransaction` together with the hash of the L1 transaction which sent its message,
+/// if known. Serialized as the transaction with an additional field, so that the events of dumps
+/// without the hash can still be loaded.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DumpedL1HandlerTransaction {
+    #[serde(flatten)]
+    pub transaction: L1HandlerTransaction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_transaction_hash: Option<Hash256>,
+}
+
+/// Describes the Devnet that created a dump: its version and the configuration that determines
+/// the predeployed contracts and how transactions are executed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DumpHeader {
+    pub devnet_version: String,
+    pub rpc_spec_version: String,
+    pub starknet_version: String,
+    pub chain_id: String,
+    pub seed: u32,
+    pub account_class_hash: Felt,
+    pub total_accounts: u32,
+    /// Only validated for state dumps, which don't contain the state of lazily predeployed
+    /// accounts
+    #[serde(default)]
+    pub lazy_predeployment: bool,
+    pub predeployed_accounts_initial_balance: String,
+    pub gas_price_wei: NonZeroU128,
+    pub gas_price_fri: NonZeroU128,
+    pub data_gas_price_wei: NonZeroU128,
+    pub data_gas_price_fri: NonZeroU128,
+    pub block_generation_on: BlockGenerationOn,
+    pub fork_url: Option<String>,
+    pub fork_block_number: Option<u64>,
+    /// Identifies the genesis file without storing it in the dump; `None` if not used
+    #[serde(default)]
+    pub genesis_fingerprint: Option<String>,
+    /// Identifies the accounts file without storing it in the dump; `None` if not used
+    #[serde(default)]
+    pub accounts_file_fingerprint: Option<String>,
+    /// Identifies the mnemonic without revealing it; `None` if not used
+    #[serde(default)]
+    pub mnemonic_fingerprint: Option<String>,
+    /// Not validated; blocks generated by re-execution are based on the genesis block, so it is
+    /// recreated with this timestamp if necessary
+    pub genesis_block_timestamp: u64,
+}
+
+impl DumpHeader {
+    pub fn new(config: &StarknetConfig, genesis_block_timestamp: u64) -> DevnetResult<Self> {
+        Ok(Self {
+            devnet_version: DEVNET_VERSION.to_string(),
+            rpc_spec_version: RPC_SPEC_VERSION.to_string(),
+            starknet_version: STARKNET_VERSION.to_string(),
+            chain_id: config.chain_id.to_string(),
+            seed: config.seed,
+            account_class_hash: config.account_contract_class_hash,
+            total_accounts: config.total_accounts,
+            lazy_predeployment: config.lazy_predeployment,
+            predeployed_accounts_initial_balance: config
+                .predeployed_accounts_initial_balance
+                .to_str_radix(10),
+            gas_price_wei: config.gas_price_wei,
+            gas_price_fri: config.gas_price_fri,
+            data_gas_price_wei: config.data_gas_price_wei,
+            data_gas_price_fri: config.data_gas_price_fri,
+            block_generation_on: config.block_generation_on,
+            fork_url: config.fork_config.url.as_ref().map(|url| url.to_string()),
+            fork_block_number: config.fork_config.block_number,
+            genesis_fingerprint: config.genesis.as_ref().map(Genesis::fingerprint).transpose()?,
+            accounts_file_fingerprint: config.accounts_file.as_ref().map(AccountsFile::fingerprint),
+            mnemonic_fingerprint: config.mnemonic.as_ref().map(AccountMnemonic::fingerprint),
+            genesis_block_timestamp,
+        })
+    }
+
+    /// Checks if a dump with this header can be loaded into a Devnet described by `current`.
+    /// `events` are the dumped events, or `None` for a state dump. The versions are not compared if
+    /// `ignore_version` is true.
+    fn validate(
+        &self,
+        current: &DumpHeader,
+        events: Option<&[DumpEvent]>,
+        ignore_version: bool,
+    ) -> DevnetResult<()> {
+        let is_state_dump = events.is_none();
+        let mut mismatches = vec![];
+        let mut check =
+            |name: &str, cli_param: Option<&str>, dumped: &dyn Display, current: &dyn Display| {
+                let (dumped, current) = (dumped.to_string(), current.to_string());
+                if dumped != current {
+                    let hint =
+                        cli_param.map_or(String::new(), |param| format!(" (set via {param})"));
+                    mismatches.push(format!(
+                        "{name} is {dumped} in the dump, but {current} in this Devnet{hint}"
+                    ));
+                }
+            };
+        let display_fork_block =
+            |block_number: Option<u64>| block_number.map_or("none".to_string(), |n| n.to_string());
+        // patch releases don't change how transactions are executed
+        let versions_differ = major_minor(&self.devnet_version)
+            != major_minor(&current.devnet_version)
+            || major_minor(&self.rpc_spec_version) != major_minor(&current.rpc_spec_version)
+            || self.starknet_version != current.starknet_version;
+        let compare_versions = versions_differ && !is_state_dump && !ignore_version;
+
+        check("chain id", Some("--chain-id"), &self.chain_id, &current.chain_id);
+        // accounts of a mnemonic or of an accounts file don't depend on the seed
+        let accounts_use_seed = |header: &DumpHeader| {
+            header.mnemonic_fingerprint.is_none() && header.accounts_file_fingerprint.is_none()
+        };
+        if accounts_use_seed(self) && accounts_use_seed(current) {
+            check("seed", Some("--seed"), &self.seed, &current.seed);
+        }
+        check(
+            "account class hash",
+            Some("--account-class or --account-class-custom"),
+            &self.account_class_hash.to_hex_string(),
+            &current.account_class_hash.to_hex_string(),
+        );
+        check(
+            "number of accounts",
+            Some("--accounts"),
+            &self.total_accounts,
+            &current.total_accounts,
+        );
+        check(
+            "initial balance",
+            Some("--initial-balance"),
+            &self.predeployed_accounts_initial_balance,
+            &current.predeployed_accounts_initial_balance,
+        );
+        check(
+            "forking URL",
+            Some("--fork-network"),
+            &self.fork_url.as_deref().unwrap_or("none"),
+            &current.fork_url.as_deref().unwrap_or("none"),
+        );
+        check(
+            "forking block number",
+            Some("--fork-block"),
+            &display_fork_block(self.fork_block_number),
+            &display_fork_block(current.fork_block_number),
+        );
+        check(
+            "genesis fingerprint",
+            Some("--genesis"),
+            &self.genesis_fingerprint.as_deref().unwrap_or("none"),
+            &current.genesis_fingerprint.as_deref().unwrap_or("none"),
+        );
+        check(
+            "accounts file fingerprint",
+            Some("--accounts-file"),
+            &self.accounts_file_fingerprint.as_deref().unwrap_or("none"),
+            &current.accounts_file_fingerprint.as_deref().unwrap_or("none"),
+        );
+        check(
+            "mnemonic fingerprint",
+            Some("--mnemonic"),
+            &self.mnemonic_fingerprint.as_deref().unwrap_or("none"),
+            &current.mnemonic_fingerprint.as_deref().unwrap_or("none"),
+        );
+
+        // a state dump is restored as it is, while re-executing events depends on the version of
+        // Devnet and on the configuration of the blocks the events are executed in
+        if is_state_dump {
+            check(
+                "lazy predeployment",
+                Some("--lazy-predeployment"),
+                &self.lazy_predeployment,
+                &current.lazy_predeployment,
+            );
+        } else {
+            if compare_versions {
+                check("Devnet version", None, &self.devnet_version, &current.devnet_version);
+                check("RPC spec version", None, &self.rpc_spec_version, &current.rpc_spec_version);
+                check("Starknet version", None, &self.starknet_version, &current.starknet_version);
+            }
+            check(
+                "L1 gas price in wei",
+                Some("--gas-price"),
+                &self.gas_price_wei,
+                &current.gas_price_wei,
+            );
+            check(
+                "L1 gas price in fri",
+                Some("--gas-price-fri"),
+                &self.gas_price_fri,
+                &current.gas_price_fri,
+            );
+            check(
+                "L1 data gas price in wei",
+                Some("--data-gas-price"),
+                &self.data_gas_price_wei,
+                &current.data_gas_price_wei,
+            );
+            check(
+                "L1 data gas price in fri",
+                Some("--data-gas-price-fri"),
+                &self.data_gas_price_fri,
+                &current.data_gas_price_fri,
+            );
+            // blocks are otherwise created by the dumped events, so the mode only matters if it
+            // makes each re-executed transaction end up in a block of its own, to which the
+            // recorded block timestamps and gas prices would then be applied
+            let block_per_transaction =
+                |header: &DumpHeader| header.block_generation_on == BlockGenerationOn::Transaction;
+            let replays_transactions =
+                events.is_some_and(|events| events.iter().any(DumpEvent::is_transaction));
+            if replays_transactions && block_per_transaction(self) != block_per_transaction(current)
+            {
+                check(
+                    "block generation",
+                    Some("--block-generation-on"),
+                    &self.block_generation_on,
+                    &current.block_generation_on,
+                );
+            }
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            let mut msg = mismatches.join("; ");
+            if compare_versions {
+                msg.push_str(". Versions are not compared with --ignore-dump-version");
+            }
+            Err(Error::IncompatibleDump { msg })
+        }
+    }
+}
+
+/// Returns the major and the minor part of `version`, e.g. "0.2" of "0.2.0-rc.1"
+fn major_minor(version: &str) -> &str {
+    match version.match_indices('.').nth(1) {
+        Some((index, _)) => &version[..index],
+        None => version,
+    }
+}
+
+/// The content of a dump, depending on the format it was created in
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DumpContent {
+    Events(Vec<DumpEvent>),
+    State(Box<StateDump>),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Dump {
+    /// `None` only for legacy dumps, created before the header was introduced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<DumpHeader>,
+    pub content: DumpContent,
+}
+
+/// Dumps to paths with this extension are compressed
+pub const COMPRESSED_DUMP_EXTENSION: &str = ".gz";
+/// The suffix of the temporary file a dump is written to before replacing the previous dump
+const TEMPORARY_DUMP_SUFFIX: &str = ".tmp";
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+/// The ending of an uncompressed dump of events, which are the last field of the dump
+const EVENTS_DUMP_ENDING: &[u8] = b"]}}";
+
+/// Writes `dump` to `path`, compressing it if the path ends with `.gz`. The dump is written to a
+/// temporary file first and then renamed, so a failed write doesn't leave a partial dump behind.
+fn write_dump_file<T: Serialize>(path: &str, dump: &T) -> DevnetResult<()> {
+    let serialized = serde_json::to_vec(dump)
+        .map_err(|e| Error::SerializationError { origin: e.to_string() })?;
+
+    let content = if path.ends_with(COMPRESSED_DUMP_EXTENSION) {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&serialized)?;
+        encoder.finish()?
+    } else {
+        serialized
+    };
+
+    let temporary_path = format!("{path}{TEMPORARY_DUMP_SUFFIX}");
+    fs::write(Path::new(&temporary_path), content)?;
+    fs::rename(Path::new(&temporary_path), Path::new(path))?;
+
+    Ok(())
+}
+
+/// Reads the content of the file at `path`, decompressing it if it is compressed with gzip
+fn read_dump_file(path: &str) -> DevnetResult<Vec<u8>> {
+    let content = fs::read(Path::new(path))?;
+    if !content.starts_with(&GZIP_MAGIC_BYTES) {
+        return Ok(content);
+    }
+
+    let mut decompressed = Vec::new();
+    GzDecoder::new(content.as_slice()).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+fn deserialize_dump(content: &[u8]) -> DevnetResult<Dump> {
+    let deserialization_error =
+        |e: serde_json::Error| Error::DeserializationError { origin: e.to_string() };
+
+    // dumps created before the header was introduced are plain lists of events
+    if content.iter().find(|byte| !byte.is_ascii_whitespace()) == Some(&b'[') {
+        warn!(
+            "The dump has no header, so it was created by an older version of Devnet. Loading it \
+             without checking its compatibility with this Devnet."
+        );
+        let events = serde_json::from_slice(content).map_err(deserialization_error)?;
+        return Ok(Dump { header: None, content: DumpContent::Events(events) });
+    }
+
+    serde_json::from_slice(content).map_err(deserialization_error)
 }
 
 impl Starknet {
-    /// Create an instance of Starknet with the state generated from the events loaded from `path`.
+    /// Create an instance of Starknet with the state loaded from the dump at `path`.
     pub fn load(config: &StarknetConfig, path: &str) -> DevnetResult<Self> {
         let mut this = Self::new(config)?;
 
-        // Try to load events from the path. Since the same CLI parameter is used for dump and load
-        // path, it may be the case that there is no file at the path. This means that the file will
-        // be created during Devnet's lifetime via dumping, so its non-existence is here ignored.
-        match this.load_events(path) {
-            Ok(events) => this.re_execute(events)?,
+        // Try to load the dump from the path. Since the same CLI parameter is used for dump and
+        // load path, it may be the case that there is no file at the path. This means that the
+        // file will be created during Devnet's lifetime via dumping, so its non-existence is here
+        // ignored.
+        match this.load_dump(path) {
+            Ok(dump) => this.apply_dump(dump, config.load_until)?,
             Err(Error::FileNotFound) => {}
             Err(err) => return Err(err),
         };
@@ -41,8 +417,104 @@ impl Starknet {
         Ok(this)
     }
 
-    pub fn re_execute(&mut self, events: Vec<DumpEvent>) -> DevnetResult<()> {
+    /// Returns the header describing this instance, as written to its dumps
+    pub fn get_dump_header(&self) -> DevnetResult<DumpHeader> {
+        let genesis_block = self.get_block(&BlockId::Number(self.blocks.starting_block_number))?;
+        DumpHeader::new(&self.config, genesis_block.timestamp().0)
+    }
+
+    /// Applies the dump to a freshly created (or restarted) instance: events are re-executed,
+    /// while a state dump replaces the state directly. Fails without changing anything if the dump
+    /// was created by an incompatible Devnet. If `load_until` is provided, events are re-executed
+    /// only up to the specified point.
+    pub fn apply_dump(&mut self, dump: Dump, load_until: Option<LoadUntil>) -> DevnetResult<()> {
+        let is_state_dump = matches!(dump.content, DumpContent::State(_));
+        let current_header = self.get_dump_header()?;
+        if let Some(header) = &dump.header {
+            let events = match &dump.content {
+                DumpContent::Events(events) => Some(events.as_slice()),
+                DumpContent::State(_) => None,
+            };
+            header.validate(&current_header, events, self.config.ignore_dump_version)?;
+        }
+
+        if is_state_dump && load_until.is_some() {
+            let msg = "Loading until a block or an event is only supported for dumps of events.";
+            return Err(Error::UnsupportedAction { msg: msg.into() });
+        }
+
+        match dump.content {
+            DumpContent::Events(events) => {
+                if let Some(DumpHeader { genesis_block_timestamp, .. }) = dump.header {
+                    if genesis_block_timestamp != current_header.genesis_block_timestamp {
+                        self.recreate_with_genesis_block_timestamp(genesis_block_timestamp)?;
+                    }
+                }
+
+                self.re_execute(events, load_until)
+            }
+            DumpContent::State(state_dump) => {
+                // the events of the loaded state are unknown, so dumping events would be incomplete
+                if self.config.dump_on.is_some() && self.config.dump_format == DumpFormat::Events {
+                    let msg = "Loading a state dump is not supported when dumping events; use \
+                               --dump-format state.";
+                    return Err(Error::UnsupportedAction { msg: msg.into() });
+                }
+
+                self.load_state_dump(*state_dump)?;
+                if self.config.dump_on == Some(DumpOn::Block) {
+                    self.dump()?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Replaces this freshly created instance with one whose genesis block has `timestamp`
+    fn recreate_with_genesis_block_timestamp(&mut self, timestamp: u64) -> DevnetResult<()> {
+        let config = self.config.clone();
+        let notifier = self.notifier.clone();
+        *self = Starknet::new(&StarknetConfig { start_time: Some(timestamp), ..config.clone() })?;
+        self.config = config;
+        self.notifier = notifier;
+
+        Ok(())
+    }
+
+    /// Re-executes `events` in order. If `load_until` is provided, stops after the specified block
+    /// is generated or after the event with the specified index is re-executed. Only requested
+    /// events are indexed; the others are re-executed together with the requested event after them.
+    pub fn re_execute(
+        &mut self,
+        mut events: Vec<DumpEvent>,
+        load_until: Option<LoadUntil>,
+    ) -> DevnetResult<()> {
+        if let Some(LoadUntil::EventIndex(event_index)) = load_until {
+            let requested_positions = events
+                .iter()
+                .enumerate()
+                .filter_map(|(position, event)| event.is_requested().then_some(position));
+            match requested_positions.clone().nth(event_index) {
+                Some(position) => events.truncate(position + 1),
+                None => {
+                    return Err(Error::LoadUntilOutOfRange {
+                        msg: format!(
+                            "event index is {event_index}, but the dump contains {} events",
+                            requested_positions.count()
+                        ),
+                    });
+                }
+            }
+        }
+
         for event in events.into_iter() {
+            if let Some(LoadUntil::BlockNumber(block_number)) = load_until {
+                if self.get_latest_block()?.block_number().0 >= block_number {
+                    return Ok(());
+                }
+            }
+
             match event {
                 DumpEvent::AddDeclareTransaction(tx) => {
                     self.add_declare_transaction(tx)?;
@@ -53,8 +525,11 @@ impl Starknet {
                 DumpEvent::AddInvokeTransaction(tx) => {
                     self.add_invoke_transaction(tx)?;
                 }
-                DumpEvent::AddL1HandlerTransaction(tx) => {
-                    self.add_l1_handler_transaction(tx)?;
+                DumpEvent::AddL1HandlerTransaction(DumpedL1HandlerTransaction {
+                    transaction,
+                    l1_transaction_hash,
+                }) => {
+                    self.add_l1_handler_transaction(transaction, l1_transaction_hash)?;
                 }
                 DumpEvent::CreateBlock => {
                     self.create_block_dump_event(None)?;
@@ -65,28 +540,75 @@ impl Starknet {
                 DumpEvent::IncreaseTime(time_shift) => {
                     self.increase_time(time_shift)?;
                 }
+                DumpEvent::SetGasPrice(gas_prices) => {
+                    self.set_next_block_gas(gas_prices)?;
+                }
+                DumpEvent::BlockTimestamp(timestamp) => {
+                    self.set_next_block_timestamp(timestamp);
+                }
+                DumpEvent::SetStorageAt { contract_address, key, value } => {
+                    self.set_storage_at(contract_address, key, value)?;
+                }
+                DumpEvent::SetNonce { contract_address, nonce } => {
+                    self.set_nonce(contract_address, nonce)?;
+                }
+                DumpEvent::ReplaceClass { contract_address, class_hash } => {
+                    self.replace_class(contract_address, class_hash)?;
+                }
+                DumpEvent::AcceptOnL1 { starting_block_id } => {
+                    self.accept_on_l1(starting_block_id)?;
+                }
+                DumpEvent::DeployAt { contract_address, class_hash, storage } => {
+                    self.deploy_at(contract_address, class_hash, storage)?;
+                }
             };
         }
 
+        if let Some(LoadUntil::BlockNumber(block_number)) = load_until {
+            let latest_block_number = self.get_latest_block()?.block_number().0;
+            if latest_block_number < block_number {
+                return Err(Error::LoadUntilOutOfRange {
+                    msg: format!(
+                        "block number is {block_number}, but the dump contains blocks up to \
+                         {latest_block_number}"
+                    ),
+                });
+            }
+        }
+
         Ok(())
     }
 
     // add starknet dump event
     pub fn handle_dump_event(&mut self, event: DumpEvent) -> DevnetResult<()> {
-        match self.config.dump_on {
-            Some(DumpOn::Block) => self.dump_event(event),
-            Some(DumpOn::Request | DumpOn::Exit) => {
+        match (self.config.dump_on, self.config.dump_format) {
+            (Some(DumpOn::Block), DumpFormat::Events) => self.dump_event(event),
+            // the state is dumped after the event that generated the block
+            (Some(DumpOn::Block), DumpFormat::State)
+                if matches!(event, DumpEvent::BlockTimestamp(_)) =>
+            {
+                Ok(())
+            }
+            // the state is dumped as a whole, so it is rewritten on each change
+            (Some(DumpOn::Block), DumpFormat::State) => self.dump(),
+            (Some(DumpOn::Request | DumpOn::Exit), DumpFormat::Events) => {
                 self.dump_events.push(event);
 
                 Ok(())
             }
-            None => Ok(()),
+            (Some(DumpOn::Request | DumpOn::Exit), DumpFormat::State) | (None, _) => Ok(()),
         }
     }
 
     /// attach starknet event to end of existing file
     pub fn dump_event(&self, event: DumpEvent) -> DevnetResult<()> {
         match &self.config.dump_path {
+            // rewriting the whole compressed file for every event would get ever slower
+            Some(path) if path.ends_with(COMPRESSED_DUMP_EXTENSION) => {
+                Err(Error::UnsupportedAction {
+                    msg: "Compressed dumps of events cannot be appended to".into(),
+                })
+            }
             Some(path) => {
                 let file_path = Path::new(path);
                 if file_path.exists() {
@@ -98,26 +620,25 @@ impl Starknet {
                         .read(true)
                         .open(file_path)
                         .map_err(Error::IoError)?;
-                    let mut buffer = [0; 1];
-                    file.seek(SeekFrom::End(-1))?;
+                    let mut buffer = [0; EVENTS_DUMP_ENDING.len()];
+                    file.seek(SeekFrom::End(-(EVENTS_DUMP_ENDING.len() as i64)))?;
                     file.read_exact(&mut buffer)?;
-                    if String::from_utf8_lossy(&buffer).into_owned() == "]" {
-                        // if the last character is "]", remove it and add event at the end
+                    if buffer == EVENTS_DUMP_ENDING {
+                        // if the file ends with the list of events, remove the ending and add the
+                        // event at the end of the list
                         let length = file.seek(SeekFrom::End(0)).map_err(Error::IoError)?;
-                        file.set_len(length - 1).map_err(Error::IoError)?; // remove last "]" with set_len
-                        file.write_all(format!(", {event_dump}]").as_bytes())
+                        file.set_len(length - EVENTS_DUMP_ENDING.len() as u64)
+                            .map_err(Error::IoError)?;
+                        file.write_all(format!(", {event_dump}]}}}}").as_bytes())
                             .map_err(Error::IoError)?;
                     } else {
-                        // if the last character is not "]" it means that it's a wrongly formatted
-                        // file
+                        // if the file doesn't end with the list of events, it means that it's a
+                        // wrongly formatted file
                         return Err(Error::FormatError);
                     }
                 } else {
                     // create file
-                    let events = vec![event];
-                    let events_dump = serde_json::to_string(&events)
-                        .map_err(|e| Error::SerializationError { origin: e.to_string() })?;
-                    fs::write(Path::new(&path), events_dump)?;
+                    write_dump_file(path, &self.create_events_dump(vec![event])?)?;
                 }
 
                 Ok(())
@@ -126,51 +647,341 @@ impl Starknet {
         }
     }
 
-    pub fn dump_events(&self) -> DevnetResult<()> {
-        self.dump_events_custom_path(None)
+    pub fn dump(&self) -> DevnetResult<()> {
+        self.dump_custom_path(None)
     }
 
-    /// save starknet events to file
-    pub fn dump_events_custom_path(&self, custom_path: Option<String>) -> DevnetResult<()> {
+    /// save the dump in the configured format to file
+    pub fn dump_custom_path(&self, custom_path: Option<String>) -> DevnetResult<()> {
         let dump_path = if custom_path.is_some() { &custom_path } else { &self.config.dump_path };
         match dump_path {
-            Some(path) => {
-                let events = &self.dump_events;
-
+            Some(path) => match self.get_dump()? {
                 // dump only if there are events to dump
-                if !events.is_empty() {
-                    let events_dump = serde_json::to_string(events)
-                        .map_err(|e| Error::SerializationError { origin: e.to_string() })?;
-                    fs::write(Path::new(&path), events_dump)?;
-                }
-
-                Ok(())
-            }
+                Dump { content: DumpContent::Events(events), .. } if events.is_empty() => Ok(()),
+                dump => write_dump_file(path, &dump),
+            },
             None => Err(Error::FormatError),
         }
     }
 
-    pub fn read_dump_events(&self) -> &Vec<DumpEvent> {
-        &self.dump_events
+    /// Returns the dump in the configured format, without writing it to a file
+    pub fn get_dump(&self) -> DevnetResult<Dump> {
+        let content = match self.config.dump_format {
+            DumpFormat::Events => DumpContent::Events(self.dump_events.clone()),
+            DumpFormat::State => DumpContent::State(Box::new(self.create_state_dump()?)),
+        };
+
+        Ok(Dump { header: Some(self.get_dump_header()?), content })
     }
 
-    /// Returns Devnet events from the provided `path`
-    pub fn load_events(&self, path: &str) -> DevnetResult<Vec<DumpEvent>> {
+    fn create_events_dump(&self, events: Vec<DumpEvent>) -> DevnetResult<Dump> {
+        Ok(Dump { header: Some(self.get_dump_header()?), content: DumpContent::Events(events) })
+    }
+
+    /// Returns the Devnet dump from the provided `path`. The format of the dump is determined by
+    /// its content, so it doesn't have to match the configured format.
+    pub fn load_dump(&self, path: &str) -> DevnetResult<Dump> {
         let file_path = Path::new(path);
         if path.is_empty() || !file_path.exists() {
             return Err(Error::FileNotFound);
         }
 
-        let file = File::open(file_path).map_err(Error::IoError)?;
-        let events: Vec<DumpEvent> = serde_json::from_reader(file)
-            .map_err(|e| Error::DeserializationError { origin: e.to_string() })?;
+        let dump = deserialize_dump(&read_dump_file(path)?)?;
 
         // to avoid doublets in block mode during load, we need to remove the file
-        // because they will be re-executed and saved again
-        if self.config.dump_on == Some(DumpOn::Block) {
+        // because the events will be re-executed and saved again
+        if let (DumpContent::Events(events), Some(DumpOn::Block), DumpFormat::Events) =
+            (&dump.content, self.config.dump_on, self.config.dump_format)
+        {
+            // an incompatible dump is not going to be re-executed, so it must not be removed
+            if let Some(header) = &dump.header {
+                header.validate(
+                    &self.get_dump_header()?,
+                    Some(events),
+                    self.config.ignore_dump_version,
+                )?;
+            }
             fs::remove_file(file_path).map_err(Error::IoError)?;
         }
 
-        Ok(events)
+        Ok(dump)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use nonzero_ext::nonzero;
+    use serde_json::json;
+    use starknet_api::block::BlockStatus;
+    use starknet_rs_core::types::{BlockId, BlockTag, Felt, Hash256};
+    use starknet_types::rpc::gas_modification::GasModificationRequest;
+
+    use super::{deserialize_dump, DumpEvent, DumpHeader, DumpedL1HandlerTransaction};
+    use crate::error::Error;
+    use crate::mnemonic::{AccountMnemonic, MnemonicScheme};
+    use crate::starknet::starknet_config::{BlockGenerationOn, DumpOn, LoadUntil, StarknetConfig};
+    use crate::starknet::Starknet;
+
+    #[test]
+    fn header_of_same_config_is_valid() {
+        let header = DumpHeader::new(&StarknetConfig::default(), 0).unwrap();
+        header.validate(&header.clone(), Some(&[]), false).unwrap();
+    }
+
+    #[test]
+    fn header_validation_reports_all_mismatches() {
+        let dumped_header =
+            DumpHeader::new(&StarknetConfig { seed: 42, ..Default::default() }, 0).unwrap();
+        let current_header =
+            DumpHeader::new(&StarknetConfig { total_accounts: 3, ..Default::default() }, 0)
+                .unwrap();
+
+        match dumped_header.validate(&current_header, None, false) {
+            Err(Error::IncompatibleDump { msg }) => assert_eq!(
+                msg,
+                "seed is 42 in the dump, but 123 in this Devnet (set via --seed); number of \
+                 accounts is 10 in the dump, but 3 in this Devnet (set via --accounts)"
+            ),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn version_mismatch_is_invalid_only_for_events() {
+        let current_header = DumpHeader::new(&StarknetConfig::default(), 0).unwrap();
+        let dumped_header =
+            DumpHeader { devnet_version: "0.0.1".to_string(), ..current_header.clone() };
+
+        dumped_header.validate(&current_header, None, false).unwrap();
+        match dumped_header.validate(&current_header, Some(&[]), false) {
+            Err(Error::IncompatibleDump { msg }) => {
+                assert!(msg.starts_with("Devnet version is 0.0.1 in the dump"), "{msg}");
+                assert!(msg.ends_with("Versions are not compared with --ignore-dump-version"));
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        dumped_header.validate(&current_header, Some(&[]), true).unwrap();
+    }
+
+    #[test]
+    fn versions_differing_only_in_patch_are_compatible() {
+        let current_header = DumpHeader {
+            devnet_version: "0.2.0".to_string(),
+            rpc_spec_version: "0.7.1".to_string(),
+            ..DumpHeader::new(&StarknetConfig::default(), 0).unwrap()
+        };
+        let dumped_header = DumpHeader {
+            devnet_version: "0.2.1-rc.1".to_string(),
+            rpc_spec_version: "0.7.0".to_string(),
+            ..current_header.clone()
+        };
+        dumped_header.validate(&current_header, Some(&[]), false).unwrap();
+
+        let dumped_header =
+            DumpHeader { devnet_version: "0.3.0".to_string(), ..current_header.clone() };
+        assert!(dumped_header.validate(&current_header, Some(&[]), false).is_err());
+    }
+
+    #[test]
+    fn block_generation_mismatch_is_invalid_only_for_replayed_transactions() {
+        let current_header = DumpHeader::new(&StarknetConfig::default(), 0).unwrap();
+        let dumped_header =
+            DumpHeader { block_generation_on: BlockGenerationOn::Demand, ..current_header.clone() };
+        let events = [
+            DumpEvent::AddL1HandlerTransaction(DumpedL1HandlerTransaction {
+                transaction: Default::default(),
+                l1_transaction_hash: None,
+            }),
+            DumpEvent::CreateBlock,
+        ];
+
+        dumped_header.validate(&current_header, None, false).unwrap();
+        dumped_header.validate(&current_header, Some(&events[1..]), false).unwrap();
+        match dumped_header.validate(&current_header, Some(&events), false) {
+            Err(Error::IncompatibleDump { msg }) => assert_eq!(
+                msg,
+                "block generation is demand in the dump, but transaction in this Devnet (set via \
+                 --block-generation-on)"
+            ),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        // in both modes, transactions are added to the pending block
+        let current_header =
+            DumpHeader { block_generation_on: BlockGenerationOn::Interval(2), ..current_header };
+        dumped_header.validate(&current_header, Some(&events), false).unwrap();
+    }
+
+    #[test]
+    fn mnemonic_mismatch_is_reported_without_checking_seed() {
+        let mnemonic = |phrase: &str| {
+            Some(AccountMnemonic::new(phrase, None, MnemonicScheme::Braavos).unwrap())
+        };
+        let dumped_header = DumpHeader::new(
+            &StarknetConfig {
+                seed: 42,
+                mnemonic: mnemonic(
+                    "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                     abandon abandon about",
+                ),
+                ..Default::default()
+            },
+            0,
+        )
+        .unwrap();
+        let current_header = DumpHeader::new(
+            &StarknetConfig {
+                mnemonic: mnemonic(
+                    "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                ),
+                ..Default::default()
+            },
+            0,
+        )
+        .unwrap();
+
+        match dumped_header.validate(&current_header, None, false) {
+            Err(Error::IncompatibleDump { msg }) => {
+                assert!(msg.starts_with("mnemonic fingerprint is "), "{msg}");
+                assert!(msg.ends_with("in this Devnet (set via --mnemonic)"), "{msg}");
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn legacy_dump_without_header_is_loaded_without_validation() {
+        let dump = deserialize_dump(br#" ["CreateBlock", "CreateBlock"]"#).unwrap();
+        assert!(dump.header.is_none());
+
+        // would be incompatible with the default configuration if the header were present
+        let config = StarknetConfig { seed: 42, ..Default::default() };
+        let mut starknet = Starknet::new(&config).unwrap();
+        starknet.apply_dump(dump, None).unwrap();
+        assert_eq!(starknet.get_latest_block().unwrap().block_number().0, 2);
+    }
+
+    #[test]
+    fn l1_handler_transaction_event_with_and_without_l1_transaction_hash() {
+        let transaction = json!({
+            "version": "0x0",
+            "nonce": "0x1",
+            "contract_address": "0x2",
+            "entry_point_selector": "0x3",
+            "calldata": ["0x4"],
+            "paid_fee_on_l1": "0x5"
+        });
+        let legacy_event = json!({ "AddL1HandlerTransaction": transaction });
+        match serde_json::from_value(legacy_event).unwrap() {
+            DumpEvent::AddL1HandlerTransaction(DumpedL1HandlerTransaction {
+                l1_transaction_hash: None,
+                ..
+            }) => (),
+            other => panic!("Unexpected event: {other:?}"),
+        }
+
+        let l1_transaction_hash = Hash256::from_felt(&Felt::from(0xabc_u32));
+        let mut transaction_with_hash = transaction.clone();
+        transaction_with_hash["l1_transaction_hash"] = json!(l1_transaction_hash);
+        let event: DumpEvent = serde_json::from_value(
+            json!({ "AddL1HandlerTransaction": transaction_with_hash.clone() }),
+        )
+        .unwrap();
+        match &event {
+            DumpEvent::AddL1HandlerTransaction(dumped_transaction) => {
+                assert_eq!(dumped_transaction.l1_transaction_hash, Some(l1_transaction_hash));
+                assert_eq!(
+                    serde_json::to_value(&dumped_transaction.transaction).unwrap(),
+                    transaction
+                );
+            }
+            other => panic!("Unexpected event: {other:?}"),
+        }
+        assert_eq!(
+            serde_json::to_value(event).unwrap(),
+            json!({ "AddL1HandlerTransaction": transaction_with_hash })
+        );
+    }
+
+    #[test]
+    fn re_execution_reproduces_blocks() {
+        let config = StarknetConfig { dump_on: Some(DumpOn::Request), ..Default::default() };
+        let mut starknet =
+            Starknet::new(&StarknetConfig { start_time: Some(1000), ..config.clone() }).unwrap();
+        starknet.create_block_dump_event(None).unwrap();
+        starknet.increase_time(100).unwrap();
+        starknet.accept_on_l1(BlockId::Tag(BlockTag::Latest)).unwrap();
+        starknet
+            .set_next_block_gas(GasModificationRequest {
+                gas_price_wei: Some(nonzero!(5u128)),
+                data_gas_price_wei: None,
+                gas_price_fri: None,
+                data_gas_price_fri: Some(nonzero!(7u128)),
+                generate_block: Some(true),
+            })
+            .unwrap();
+        starknet.create_block_dump_event(None).unwrap();
+        let dump = starknet.get_dump().unwrap();
+
+        // blocks generated at a later time by re-execution must still be the same
+        thread::sleep(Duration::from_secs(1));
+        let mut loaded_starknet = Starknet::new(&config).unwrap();
+        loaded_starknet.apply_dump(dump, None).unwrap();
+
+        let latest_block_number = starknet.get_latest_block().unwrap().block_number().0;
+        assert_eq!(latest_block_number, 4);
+        for block_number in 0..=latest_block_number {
+            let block_id = BlockId::Number(block_number);
+            let loaded_block = loaded_starknet.get_block(&block_id).unwrap();
+            let block = starknet.get_block(&block_id).unwrap();
+            assert_eq!(loaded_block.header, block.header);
+            assert_eq!(loaded_block.status, block.status);
+        }
+        assert_eq!(
+            loaded_starknet.get_block(&BlockId::Number(2)).unwrap().status,
+            BlockStatus::AcceptedOnL1
+        );
+        assert_eq!(loaded_starknet.get_dump_header().unwrap().genesis_block_timestamp, 1000);
+    }
+
+    #[test]
+    fn re_execution_stops_at_requested_block_or_event() {
+        let config = StarknetConfig { dump_on: Some(DumpOn::Request), ..Default::default() };
+        let mut starknet = Starknet::new(&config).unwrap();
+        for _ in 0..3 {
+            starknet.create_block_dump_event(None).unwrap();
+        }
+        let dump = starknet.get_dump().unwrap();
+
+        let mut loaded_starknet = Starknet::new(&config).unwrap();
+        loaded_starknet.apply_dump(dump.clone(), Some(LoadUntil::BlockNumber(2))).unwrap();
+        assert_eq!(loaded_starknet.get_latest_block().unwrap().block_number().0, 2);
+
+        // the timestamps recorded with each block creation are not indexed
+        let mut loaded_starknet = Starknet::new(&config).unwrap();
+        loaded_starknet.apply_dump(dump, Some(LoadUntil::EventIndex(1))).unwrap();
+        assert_eq!(loaded_starknet.get_latest_block().unwrap().block_number().0, 2);
+    }
+
+    #[test]
+    fn re_execution_fails_if_requested_block_or_event_is_not_in_dump() {
+        let config = StarknetConfig { dump_on: Some(DumpOn::Request), ..Default::default() };
+        let mut starknet = Starknet::new(&config).unwrap();
+        starknet.create_block_dump_event(None).unwrap();
+        let dump = starknet.get_dump().unwrap();
+
+        for (load_until, expected_msg) in [
+            (LoadUntil::BlockNumber(2), "block number is 2, but the dump contains blocks up to 1"),
+            (LoadUntil::EventIndex(1), "event index is 1, but the dump contains 1 events"),
+        ] {
+            let mut loaded_starknet = Starknet::new(&config).unwrap();
+            match loaded_starknet.apply_dump(dump.clone(), Some(load_until)) {
+                Err(Error::LoadUntilOutOfRange { msg }) => assert_eq!(msg, expected_msg),
+                other => panic!("Unexpected result: {other:?}"),
+            }
+        }
     }
 }
@@ -0,0 +1,388 @@
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use blockifier::abi::sierra_types::next_storage_key;
+use blockifier::context::{BlockContext, TransactionContext};
+use blockifier::execution::entry_point::{ConstructorContext, EntryPointExecutionContext};
+use blockifier::execution::execution_utils::execute_deployment;
+use blockifier::state::state_api::State;
+use serde::{Deserialize, Deserializer};
+use serde_json::json;
+use starknet_api::transaction::Calldata;
+use starknet_rs_core::types::Felt;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::{Cairo0ContractClass, Cairo0Json, ContractClass};
+use starknet_types::felt::{felt_from_prefixed_hex, split_biguint, ClassHash};
+use starknet_types::rpc::state::{Balance, StorageEntry};
+use starknet_types::rpc::transaction_receipt::FeeUnit;
+use starknet_types::traits::HashProducer;
+
+use crate::constants::{ETH_ERC20_CONTRACT_ADDRESS, STRK_ERC20_CONTRACT_ADDRESS};
+use crate::error::{DevnetResult, Error};
+use crate::state::{CustomState, CustomStateReader, StarknetState};
+use crate::utils::{fingerprint, get_storage_var_address};
+
+/// Classes, contracts and token balances to be set up before the genesis block is created, as
+/// specified in a genesis file.
+#[derive(Clone, Debug, Default)]
+pub struct Genesis {
+    pub classes: Vec<ContractClass>,
+    pub contracts: Vec<GenesisContract>,
+    pub balances: Vec<GenesisBalance>,
+}
+
+/// The structure of a genesis file; classes are specified as paths to their artifacts
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisFile {
+    #[serde(default)]
+    classes: Vec<String>,
+    #[serde(default)]
+    contracts: Vec<GenesisContract>,
+    #[serde(default)]
+    balances: Vec<GenesisBalance>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisContract {
+    pub address: ContractAddress,
+    pub class_hash: ClassHash,
+    /// If provided, the constructor is executed with this calldata; otherwise it is skipped
+    pub constructor_calldata: Option<Vec<Felt>>,
+    /// Written after the constructor is executed, so it takes precedence
+    #[serde(default)]
+    pub storage: Vec<StorageEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisBalance {
+    pub address: ContractAddress,
+    #[serde(deserialize_with = "deserialize_decimal_string")]
+    pub amount: Balance,
+    /// Defaults to WEI
+    pub unit: Option<FeeUnit>,
+}
+
+fn deserialize_decimal_string<'de, D>(deserializer: D) -> Result<Balance, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let decimal_string = String::deserialize(deserializer)?;
+    Balance::from_str(&decimal_string).map_err(|_| {
+        serde::de::Error::custom(format!("Expected a decimal string, got: {decimal_string}"))
+    })
+}
+
+/// Loads a Sierra or a Cairo 0 artifact
+fn load_class(path: &Path) -> DevnetResult<ContractClass> {
+    let path_str = path.to_string_lossy();
+    let artifact = std::fs::read_to_string(path)
+        .map_err(|source| Error::ReadFileError { source, path: path_str.to_string() })?;
+
+    let is_sierra = serde_json::from_str::<serde_json::Value>(&artifact)
+        .map_err(|err| Error::ContractClassLoadError(format!("{path_str}: {err}")))?
+        .get("sierra_program")
+        .is_some();
+
+    if is_sierra {
+        Ok(ContractClass::Cairo1(ContractClass::cairo_1_from_sierra_json_str(&artifact)?))
+    } else {
+        let contract_json = Cairo0Json::raw_json_from_json_str(&artifact)?;
+        Ok(ContractClass::Cairo0(Cairo0ContractClass::RawJson(contract_json)))
+    }
+}
+
+impl FromStr for Genesis {
+    type Err = Error;
+
+    /// Parses the genesis file at `path`, which is in YAML if its extension is `.yaml` or `.yml`
+    /// and in JSON otherwise. Relative paths of class artifacts are resolved against the
+    /// directory of the genesis file.
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let path = Path::new(path);
+        let content = std::fs::read_to_string(path)
+            .map_err(|source| Error::ReadFileError { source, path: path.display().to_string() })?;
+
+        let is_yaml = matches!(path.extension().and_then(|ext| ext.to_str()), Some("yaml" | "yml"));
+        let genesis_file: GenesisFile = if is_yaml {
+            serde_yaml::from_str(&content)
+                .map_err(|err| Error::InvalidGenesis { msg: err.to_string() })?
+        } else {
+            serde_json::from_str(&content)
+                .map_err(|err| Error::InvalidGenesis { msg: err.to_string() })?
+        };
+
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        let classes = genesis_file
+            .classes
+            .iter()
+            .map(|class_path| load_class(&base_dir.join(class_path)))
+            .collect::<DevnetResult<_>>()?;
+
+        Ok(Self { classes, contracts: genesis_file.contracts, balances: genesis_file.balances })
+    }
+}
+
+impl Genesis {
+    /// Identifies the classes, contracts and balances, with classes identified by their hashes
+    pub(crate) fn fingerprint(&self) -> DevnetResult<String> {
+        let class_hashes = self
+            .classes
+            .iter()
+            .map(|contract_class| contract_class.generate_hash())
+            .collect::<Result<Vec<_>, _>>()?;
+        let contracts: Vec<_> = self
+            .contracts
+            .iter()
+            .map(|GenesisContract { address, class_hash, constructor_calldata, storage }| {
+                json!({
+                    "address": address,
+                    "class_hash": class_hash,
+                    "constructor_calldata": constructor_calldata,
+                    "storage": storage,
+                })
+            })
+            .collect();
+        let balances: Vec<_> = self
+            .balances
+            .iter()
+            .map(|GenesisBalance { address, amount, unit }| {
+                json!({ "address": address, "amount": amount.to_string(), "unit": unit })
+            })
+            .collect();
+
+        Ok(fingerprint(&json!({
+            "classes": class_hashes,
+            "contracts": contracts,
+            "balances": balances,
+        })))
+    }
+
+    /// Classes are declared in the underlying state, like the classes of other predeployed
+    /// contracts. Contracts and balances are written to the cache, so that they are a part of the
+    /// state diff of the genesis block.
+    pub(crate) fn apply(
+        &self,
+        state: &mut StarknetState,
+        block_context: &BlockContext,
+    ) -> DevnetResult<()> {
+        for contract_class in &self.classes {
+            let class_hash = contract_class.generate_hash()?;
+            if !state.is_contract_declared(class_hash) {
+                state.predeclare_contract_class(class_hash, contract_class.clone())?;
+            }
+        }
+
+        for contract in &self.contracts {
+            Self::deploy_contract(state, block_context, contract)?;
+        }
+
+        for GenesisBalance { address, amount, unit } in &self.balances {
+            let token_address = match unit.unwrap_or(FeeUnit::WEI) {
+                FeeUnit::WEI => ETH_ERC20_CONTRACT_ADDRESS,
+                FeeUnit::FRI => STRK_ERC20_CONTRACT_ADDRESS,
+            };
+            let token_address = felt_from_prefixed_hex(token_address)?.try_into()?;
+
+            let balance_key_low =
+                get_storage_var_address("ERC20_balances", &[Felt::from(*address)])?.try_into()?;
+            let balance_key_high = next_storage_key(&balance_key_low)?;
+            let (high, low) = split_biguint(amount.clone());
+
+            state.set_storage_at(token_address, balance_key_low, low)?;
+            state.set_storage_at(token_address, balance_key_high, high)?;
+        }
+
+        Ok(())
+    }
+
+    fn deploy_contract(
+        state: &mut StarknetState,
+        block_context: &BlockContext,
+        contract: &GenesisContract,
+    ) -> DevnetResult<()> {
+        let GenesisContract { address, class_hash, constructor_calldata, storage } = contract;
+        if state.is_contract_deployed(*address)? {
+            return Err(Error::InvalidGenesis {
+                msg: format!("A contract is already deployed at address {address:x}"),
+            });
+        }
+        if !state.is_contract_declared(*class_hash) {
+            return Err(Error::InvalidGenesis {
+                msg: format!("Class {class_hash:#x} of contract {address:x} is not declared"),
+            });
+        }
+
+        let api_address = (*address).try_into()?;
+        let api_class_hash = starknet_api::core::ClassHash(*class_hash);
+        match constructor_calldata {
+            Some(constructor_calldata) => {
+                let mut execution_context = EntryPointExecutionContext::new(
+                    Arc::new(TransactionContext {
+                        block_context: block_context.clone(),
+                        tx_info: blockifier::transaction::objects::TransactionInfo::Deprecated(
+                            blockifier::transaction::objects::DeprecatedTransactionInfo::default(),
+                        ),
+                    }),
+                    blockifier::execution::common_hints::ExecutionMode::Execute,
+                    true,
+                )?;
+                let ctor_context = ConstructorContext {
+                    class_hash: api_class_hash,
+                    code_address: None,
+                    storage_address: api_address,
+                    caller_address: Default::default(),
+                };
+
+                execute_deployment(
+                    &mut state.state,
+                    &mut Default::default(),
+                    &mut execution_context,
+                    ctor_context,
+                    Calldata(Arc::new(constructor_calldata.clone())),
+                    block_context.versioned_constants().tx_initial_gas(),
+                )
+                .map_err(|err| Error::InvalidGenesis {
+                    msg: format!("Constructor of contract {address:x} failed: {err}"),
+                })?;
+            }
+            None => state.set_class_hash_at(api_address, api_class_hash)?,
+        }
+
+        for StorageEntry { key, value } in storage {
+            state.set_storage_at(api_address, (*key).try_into()?, *value)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use blockifier::state::state_api::StateReader;
+    use starknet_rs_core::types::{BlockId, BlockTag, Felt};
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::contract_class::{Cairo0ContractClass, ContractClass};
+    use starknet_types::felt::{felt_from_prefixed_hex, join_felts};
+    use starknet_types::patricia_key::PatriciaKey;
+    use starknet_types::rpc::state::Balance;
+    use starknet_types::traits::HashProducer;
+
+    use super::{Genesis, GenesisContract};
+    use crate::constants::{
+        CAIRO_1_ACCOUNT_CONTRACT_SIERRA_HASH, ETH_ERC20_CONTRACT_ADDRESS,
+        STRK_ERC20_CONTRACT_ADDRESS,
+    };
+    use crate::error::Error;
+    use crate::starknet::starknet_config::StarknetConfig;
+    use crate::starknet::Starknet;
+    use crate::state::CustomStateReader;
+    use crate::utils::exported_test_utils::dummy_cairo_0_contract_class;
+    use crate::utils::get_storage_var_address;
+
+    const GENESIS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/test_artifacts/genesis.yaml");
+
+    fn get_balance(
+        starknet: &mut Starknet,
+        address: ContractAddress,
+        token_address: &str,
+    ) -> Balance {
+        let (low, high) = starknet
+            .pending_state
+            .get_fee_token_balance(
+                address.try_into().unwrap(),
+                felt_from_prefixed_hex(token_address).unwrap().try_into().unwrap(),
+            )
+            .unwrap();
+        join_felts(&high, &low)
+    }
+
+    #[test]
+    fn genesis_is_applied_before_genesis_block() {
+        let genesis = Genesis::from_str(GENESIS_PATH).unwrap();
+        let mut starknet =
+            Starknet::new(&StarknetConfig { genesis: Some(genesis), ..Default::default() })
+                .unwrap();
+
+        let cairo_0_class_hash =
+            ContractClass::Cairo0(Cairo0ContractClass::RawJson(dummy_cairo_0_contract_class()))
+                .generate_hash()
+                .unwrap();
+        assert!(starknet.pending_state.is_contract_declared(cairo_0_class_hash));
+
+        // deployed with the constructor, which sets the public key
+        let latest = BlockId::Tag(BlockTag::Latest);
+        let constructed_address = ContractAddress::new(Felt::from(0x1234)).unwrap();
+        let public_key_key = get_storage_var_address("Account_public_key", &[]).unwrap();
+        assert_eq!(
+            starknet
+                .contract_storage_at_block(&latest, constructed_address, public_key_key)
+                .unwrap(),
+            Felt::from(0x42)
+        );
+
+        // deployed with raw storage
+        let address_with_storage = ContractAddress::new(Felt::from(0x5678)).unwrap();
+        assert_eq!(
+            starknet
+                .contract_storage_at_block(
+                    &latest,
+                    address_with_storage,
+                    PatriciaKey::new(Felt::from(5)).unwrap()
+                )
+                .unwrap(),
+            Felt::from(0x43)
+        );
+        assert_eq!(
+            starknet.get_class_hash_at(&latest, address_with_storage).unwrap(),
+            felt_from_prefixed_hex(CAIRO_1_ACCOUNT_CONTRACT_SIERRA_HASH).unwrap()
+        );
+
+        assert_eq!(
+            get_balance(&mut starknet, constructed_address, ETH_ERC20_CONTRACT_ADDRESS),
+            Balance::from(10_u128.pow(21))
+        );
+        assert_eq!(
+            get_balance(&mut starknet, constructed_address, STRK_ERC20_CONTRACT_ADDRESS),
+            Balance::from(2 * 10_u128.pow(21))
+        );
+    }
+
+    #[test]
+    fn genesis_with_undeclared_class_fails() {
+        let genesis = Genesis {
+            contracts: vec![GenesisContract {
+                address: ContractAddress::new(Felt::from(0x1234)).unwrap(),
+                class_hash: Felt::from(42),
+                constructor_calldata: None,
+                storage: vec![],
+            }],
+            ..Default::default()
+        };
+
+        match Starknet::new(&StarknetConfig { genesis: Some(genesis), ..Default::default() }) {
+            Err(Error::InvalidGenesis { msg }) => {
+                assert_eq!(msg, "Class 0x2a of contract 0x1234 is not declared")
+            }
+            Err(other) => panic!("Got unexpected error: {other:?}"),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn parsing_genesis_with_unknown_field_fails() {
+        // a contract class artifact is not a valid genesis file
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_artifacts/cairo_0_test.json");
+        match Genesis::from_str(path) {
+            Err(Error::InvalidGenesis { msg }) => {
+                assert!(msg.starts_with("unknown field `abi`"), "Unexpected message: {msg}")
+            }
+            unexpected => panic!("Got unexpected response: {unexpected:?}"),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use blockifier::state::state_api::StateReader;
 use starknet_api::block::BlockStatus;
-use starknet_rs_core::types::BlockId;
+use starknet_rs_core::types::contract::CompiledClass;
+use starknet_rs_core::types::{BlockId, BlockTag};
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_class::ContractClass;
 use starknet_types::felt::ClassHash;
@@ -43,6 +44,28 @@ pub fn get_class_impl(
     }
 }
 
+/// Returns the CASM of the class if it was declared, including the classes declared in the pending
+/// block. Cairo 0 classes have no CASM.
+pub fn get_compiled_casm_impl(
+    starknet: &Starknet,
+    class_hash: ClassHash,
+) -> DevnetResult<CompiledClass> {
+    match get_class_impl(starknet, &BlockId::Tag(BlockTag::Pending), class_hash)? {
+        ContractClass::Cairo1(_) => {
+            let class_storage = starknet.rpc_contract_classes.read();
+            let casm = class_storage
+                .get_casm(&class_hash)
+                .ok_or(Error::StateError(StateError::NoneCasmClass(class_hash)))?;
+            // the CASM is stored at declaration, only its form is converted here
+            let casm_json = serde_json::to_value(casm)
+                .map_err(|err| Error::SerializationError { origin: err.to_string() })?;
+            serde_json::from_value(casm_json)
+                .map_err(|err| Error::DeserializationError { origin: err.to_string() })
+        }
+        ContractClass::Cairo0(_) => Err(Error::StateError(StateError::NoneCasmClass(class_hash))),
+    }
+}
+
 pub fn get_class_at_impl(
     starknet: &mut Starknet,
     block_id: &BlockId,
@@ -54,10 +77,10 @@ pub fn get_class_at_impl(
 
 #[cfg(test)]
 mod tests {
-    use starknet_rs_core::types::BlockId;
+    use starknet_rs_core::types::{BlockId, Felt};
     use starknet_types::contract_class::ContractClass;
 
-    use crate::error::Error;
+    use crate::error::{Error, StateError};
     use crate::starknet::starknet_config::StateArchiveCapacity;
     use crate::starknet::tests::setup_starknet_with_no_signature_check_account_and_state_capacity;
     use crate::utils::test_utils::dummy_broadcasted_declare_transaction_v2;
@@ -135,7 +158,7 @@ mod tests {
         let block_id = BlockId::Number(block_number.0);
 
         let contract_class = starknet.get_class_at(&block_id, account.account_address).unwrap();
-        assert_eq!(contract_class, account.contract_class);
+        assert_eq!(contract_class, *account.contract_class);
     }
 
     #[test]
@@ -155,4 +178,39 @@ mod tests {
             other => panic!("Got unexpected resp: {other:?}"),
         }
     }
+
+    #[test]
+    fn get_compiled_casm_of_declared_class() {
+        let (mut starknet, account) =
+            setup_starknet_with_no_signature_check_account_and_state_capacity(
+                1e8 as u128,
+                StateArchiveCapacity::Full,
+            );
+
+        let declare_txn = dummy_broadcasted_declare_transaction_v2(&account.account_address);
+        let expected_casm_hash = declare_txn.compiled_class_hash;
+        let (_, class_hash) = starknet
+            .add_declare_transaction(
+                starknet_types::rpc::transactions::BroadcastedDeclareTransaction::V2(Box::new(
+                    declare_txn,
+                )),
+            )
+            .unwrap();
+
+        let compiled_casm = starknet.get_compiled_casm(class_hash).unwrap();
+        assert_eq!(compiled_casm.class_hash().unwrap(), expected_casm_hash);
+    }
+
+    #[test]
+    fn get_compiled_casm_of_undeclared_class() {
+        let (starknet, _) = setup_starknet_with_no_signature_check_account_and_state_capacity(
+            1e8 as u128,
+            StateArchiveCapacity::Full,
+        );
+
+        match starknet.get_compiled_casm(Felt::from(0xdead)) {
+            Err(Error::StateError(StateError::NoneClassHash(_))) => (),
+            other => panic!("Got unexpected resp: {other:?}"),
+        }
+    }
 }
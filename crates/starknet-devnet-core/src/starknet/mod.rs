@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::num::NonZeroU128;
 use std::sync::Arc;
 
@@ -5,7 +6,7 @@ use blockifier::blockifier::block::{BlockInfo, GasPrices};
 use blockifier::context::{BlockContext, ChainInfo, TransactionContext};
 use blockifier::execution::entry_point::CallEntryPoint;
 use blockifier::state::cached_state::CachedState;
-use blockifier::state::state_api::StateReader;
+use blockifier::state::state_api::{State, StateReader};
 use blockifier::transaction::account_transaction::AccountTransaction;
 use blockifier::transaction::errors::TransactionPreValidationError;
 use blockifier::transaction::objects::TransactionExecutionInfo;
@@ -16,8 +17,9 @@ use starknet_api::core::SequencerContractAddress;
 use starknet_api::felt;
 use starknet_api::transaction::Fee;
 use starknet_config::BlockGenerationOn;
+use starknet_rs_core::types::contract::CompiledClass;
 use starknet_rs_core::types::{
-    BlockId, BlockTag, ExecutionResult, Felt, MsgFromL1, TransactionExecutionStatus,
+    BlockId, BlockTag, ExecutionResult, Felt, Hash256, MsgFromL1, TransactionExecutionStatus,
     TransactionFinalityStatus,
 };
 use starknet_rs_core::utils::get_selector_from_name;
@@ -27,17 +29,18 @@ use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_class::ContractClass;
 use starknet_types::emitted_event::EmittedEvent;
 use starknet_types::felt::{
-    felt_from_prefixed_hex, split_biguint, BlockHash, ClassHash, TransactionHash,
+    felt_from_prefixed_hex, split_biguint, BlockHash, ClassHash, Nonce, TransactionHash,
 };
 use starknet_types::num_bigint::BigUint;
-use starknet_types::patricia_key::PatriciaKey;
+use starknet_types::patricia_key::{PatriciaKey, StorageKey};
 use starknet_types::rpc::block::{
     Block, BlockHeader, BlockResult, PendingBlock, PendingBlockHeader,
 };
 use starknet_types::rpc::estimate_message_fee::FeeEstimateWrapper;
 use starknet_types::rpc::gas_modification::{GasModification, GasModificationRequest};
 use starknet_types::rpc::state::{
-    PendingStateUpdate, StateUpdate, StateUpdateResult, ThinStateDiff,
+    ContractStorageKeys, PendingStateUpdate, StateUpdate, StateUpdateResult, StorageEntry,
+    StorageProof, ThinStateDiff,
 };
 use starknet_types::rpc::transaction_receipt::{
     DeployTransactionReceipt, L1HandlerTransactionReceipt, TransactionReceipt,
@@ -51,12 +54,15 @@ use starknet_types::rpc::transactions::{
     TransactionWithReceipt, Transactions,
 };
 use starknet_types::traits::HashProducer;
+use tokio::sync::broadcast;
 use tracing::{error, info};
 
 use self::cheats::Cheats;
 use self::defaulter::StarknetDefaulter;
 use self::dump::DumpEvent;
+use self::notifications::{Notifier, StarknetNotification};
 use self::predeployed::initialize_erc20_at_address;
+use self::snapshot::Snapshot;
 use self::starknet_config::{DumpOn, StarknetConfig, StateArchiveCapacity};
 use self::transaction_trace::create_trace;
 use crate::account::Account;
@@ -68,9 +74,9 @@ use crate::constants::{
     STRK_ERC20_NAME, STRK_ERC20_SYMBOL, USE_KZG_DA,
 };
 use crate::contract_class_choice::AccountContractClassChoice;
-use crate::error::{DevnetResult, Error, TransactionValidationError};
+use crate::error::{DevnetResult, Error, StateError, TransactionValidationError};
 use crate::messaging::MessagingBroker;
-use crate::predeployed_accounts::PredeployedAccounts;
+use crate::predeployed_accounts::{LazyAccountsState, PredeployedAccounts};
 use crate::raw_execution::{Call, RawExecution};
 use crate::state::state_diff::StateDiff;
 use crate::state::{CommittedClassStorage, CustomState, CustomStateReader, StarknetState};
@@ -86,10 +92,14 @@ mod cheats;
 pub(crate) mod defaulter;
 pub mod dump;
 mod estimations;
-mod events;
+pub mod events;
+pub mod genesis;
 mod get_class_impls;
+pub mod notifications;
 mod predeployed;
+mod snapshot;
 pub mod starknet_config;
+pub mod state_dump;
 mod state_update;
 pub(crate) mod transaction_trace;
 
@@ -98,6 +108,9 @@ pub struct Starknet {
     pub pending_state: StarknetState,
     /// Contains the diff since the last block
     pending_state_diff: StateDiff,
+    /// Contains the changes written directly to the state on startup (e.g. predeployed contracts),
+    /// which are not a part of any block's state diff
+    initial_state_diff: StateDiff,
     predeployed_accounts: PredeployedAccounts,
     pub(in crate::starknet) block_context: BlockContext,
     // To avoid repeating some logic related to blocks,
@@ -112,6 +125,9 @@ pub struct Starknet {
     pub(crate) dump_events: Vec<DumpEvent>,
     rpc_contract_classes: Arc<RwLock<CommittedClassStorage>>,
     cheats: Cheats,
+    notifier: Notifier,
+    snapshots: BTreeMap<u64, Snapshot>,
+    next_snapshot_id: u64,
 }
 
 impl Default for Starknet {
@@ -130,6 +146,7 @@ impl Default for Starknet {
             latest_state: Default::default(),
             pending_state: Default::default(),
             pending_state_diff: Default::default(),
+            initial_state_diff: Default::default(),
             predeployed_accounts: Default::default(),
             blocks: Default::default(),
             transactions: Default::default(),
@@ -146,6 +163,9 @@ impl Default for Starknet {
             dump_events: Default::default(),
             rpc_contract_classes: Default::default(),
             cheats: Default::default(),
+            notifier: Default::default(),
+            snapshots: Default::default(),
+            next_snapshot_id: 0,
         }
     }
 }
@@ -194,18 +214,38 @@ impl Starknet {
 
         let mut predeployed_accounts = PredeployedAccounts::new(
             config.seed,
+            config.mnemonic.clone(),
             config.predeployed_accounts_initial_balance.clone(),
             eth_erc20_fee_contract.get_address(),
             strk_erc20_fee_contract.get_address(),
         );
 
-        let accounts = predeployed_accounts.generate_accounts(
-            config.total_accounts,
-            config.account_contract_class_hash,
-            &config.account_contract_class,
-        )?;
-        for account in accounts {
-            account.deploy(&mut state)?;
+        let accounts = match &config.accounts_file {
+            Some(accounts_file) => predeployed_accounts.generate_accounts_from_file(
+                accounts_file,
+                config.account_contract_class_hash,
+                &config.account_contract_class,
+            )?,
+            None => predeployed_accounts.generate_accounts(
+                config.total_accounts,
+                config.account_contract_class_hash,
+                &config.account_contract_class,
+            )?,
+        };
+        if config.lazy_predeployment {
+            for account in accounts {
+                account.declare_if_undeclared(
+                    &mut state,
+                    account.class_hash,
+                    &account.contract_class,
+                )?;
+            }
+            let lazy_accounts = LazyAccountsState::new(accounts)?;
+            state.state.state.defaulter_mut().set_lazy_accounts(Arc::new(lazy_accounts));
+        } else {
+            for account in accounts {
+                account.deploy(&mut state)?;
+            }
         }
 
         let chargeable_account = Account::new_chargeable(
@@ -219,23 +259,31 @@ impl Starknet {
         let starting_block_number =
             config.fork_config.block_number.map_or(DEVNET_DEFAULT_STARTING_BLOCK_NUMBER, |n| n + 1);
 
+        let block_context = Self::init_block_context(
+            config.gas_price_wei,
+            config.gas_price_fri,
+            config.data_gas_price_wei,
+            config.data_gas_price_fri,
+            ETH_ERC20_CONTRACT_ADDRESS,
+            STRK_ERC20_CONTRACT_ADDRESS,
+            config.chain_id,
+            starting_block_number,
+        );
+
+        if let Some(genesis) = &config.genesis {
+            genesis.apply(&mut state, &block_context)?;
+        }
+
+        let initial_state_diff = StateDiff::from_dict_state(&state.state.state);
         let pending_state_diff = state.commit_diff(starting_block_number)?;
 
         let mut this = Self {
             latest_state: Default::default(), // temporary - overwritten on genesis block creation
             pending_state: state,
             pending_state_diff,
+            initial_state_diff,
             predeployed_accounts,
-            block_context: Self::init_block_context(
-                config.gas_price_wei,
-                config.gas_price_fri,
-                config.data_gas_price_wei,
-                config.data_gas_price_fri,
-                ETH_ERC20_CONTRACT_ADDRESS,
-                STRK_ERC20_CONTRACT_ADDRESS,
-                config.chain_id,
-                starting_block_number,
-            ),
+            block_context,
             blocks: StarknetBlocks::new(starting_block_number),
             transactions: StarknetTransactions::default(),
             config: config.clone(),
@@ -251,6 +299,9 @@ impl Starknet {
             dump_events: Default::default(),
             rpc_contract_classes,
             cheats: Default::default(),
+            notifier: Default::default(),
+            snapshots: Default::default(),
+            next_snapshot_id: 0,
         };
 
         this.restart_pending_block()?;
@@ -269,12 +320,20 @@ impl Starknet {
     }
 
     pub fn restart(&mut self) -> DevnetResult<()> {
+        // keep the notifier, so that the existing subscribers keep receiving notifications
+        let notifier = self.notifier.clone();
         *self = Starknet::new(&self.config)?;
+        self.notifier = notifier;
         info!("Starknet Devnet restarted");
 
         Ok(())
     }
 
+    /// Returns a receiver of notifications about the changes that happen after this call
+    pub fn subscribe_to_notifications(&self) -> broadcast::Receiver<StarknetNotification> {
+        self.notifier.subscribe()
+    }
+
     pub fn get_predeployed_accounts(&self) -> Vec<Account> {
         self.predeployed_accounts.get_accounts().to_vec()
     }
@@ -319,8 +378,10 @@ impl Starknet {
     pub(crate) fn generate_new_block_and_state(&mut self) -> DevnetResult<Felt> {
         let mut new_block = self.pending_block().clone();
         let new_block_number = self.blocks.next_block_number();
+        let is_genesis_block = self.blocks.last_block_hash.is_none();
 
-        // set new block header
+        // set new block header; the state root has to be known before calculating the block hash
+        new_block.set_state_root(self.pending_state.state_root()?);
         new_block.set_block_hash(if self.config.lite_mode {
             BlockHash::from_hex(&format!("{:#x}", new_block_number.0))?
         } else {
@@ -357,14 +418,48 @@ impl Starknet {
             self.blocks.save_state_at(new_block_hash, clone);
         }
 
+        if let Some(block) = self.blocks.hash_to_block.get(&new_block_hash) {
+            self.notifier.notify(StarknetNotification::NewBlock(Box::new(block.into())));
+
+            let block_id = Some(BlockId::Hash(new_block_hash));
+            match self.get_events(block_id, block_id, None, None, 0, None) {
+                Ok((new_events, _)) if !new_events.is_empty() => {
+                    self.notifier.notify(StarknetNotification::NewEvents(new_events));
+                }
+                Ok(_) => {}
+                Err(err) => error!("Failed getting the events of the new block: {err}"),
+            }
+
+            for transaction_hash in block.get_transactions() {
+                self.notify_transaction_status(transaction_hash);
+            }
+        }
+
         self.generate_pending_block()?;
 
         // for every new block we need to clone pending state into state
         self.latest_state = self.pending_state.clone_historic();
 
+        // the timestamp of the genesis block is a part of the dump header
+        if !is_genesis_block {
+            self.handle_dump_event(DumpEvent::BlockTimestamp(block_timestamp.0))?;
+        }
+
         Ok(new_block_hash)
     }
 
+    /// Notifies the subscribers about the current status of the transaction, if it is present
+    fn notify_transaction_status(&self, transaction_hash: &TransactionHash) {
+        if let Some(tx) = self.transactions.get(transaction_hash) {
+            self.notifier.notify(StarknetNotification::TransactionStatus {
+                transaction_hash: *transaction_hash,
+                finality_status: tx.finality_status,
+                execution_status: tx.execution_result.status(),
+                failure_reason: tx.execution_result.revert_reason().map(String::from),
+            });
+        }
+    }
+
     /// Commits the changes since the last commit. Use it to commit the changes generated by the
     /// last tx. Updates the `pending_state_diff` to accumulate the changes since the last block.
     /// Check `StarknetState::commit_diff` for more info.
@@ -459,10 +554,15 @@ impl Starknet {
         self.blocks.pending_block.add_transaction(*transaction_hash);
 
         self.transactions.insert(transaction_hash, transaction_to_add);
+        let pending_transaction = Box::new(transaction.clone());
+        self.notifier.notify(StarknetNotification::PendingTransaction(pending_transaction));
 
-        // create new block from pending one, only in block-generation-on-transaction mode
+        // create new block from pending one, only in block-generation-on-transaction mode; in
+        // other modes, the status in the pending block is notified right away
         if self.config.block_generation_on == BlockGenerationOn::Transaction {
             self.generate_new_block_and_state()?;
+        } else {
+            self.notify_transaction_status(transaction_hash);
         }
 
         Ok(())
@@ -639,6 +739,10 @@ impl Starknet {
         get_class_impls::get_class_impl(self, block_id, class_hash)
     }
 
+    pub fn get_compiled_casm(&self, class_hash: ClassHash) -> DevnetResult<CompiledClass> {
+        get_class_impls::get_compiled_casm_impl(self, class_hash)
+    }
+
     pub fn get_class_at(
         &mut self,
         block_id: &BlockId,
@@ -741,11 +845,18 @@ impl Starknet {
         add_invoke_transaction::add_invoke_transaction(self, invoke_transaction)
     }
 
+    /// If the hash of the L1 transaction which sent the message is known, the transaction is
+    /// associated with it, so that it's returned by `starknet_getMessagesStatus`.
     pub fn add_l1_handler_transaction(
         &mut self,
         l1_handler_transaction: L1HandlerTransaction,
+        l1_transaction_hash: Option<Hash256>,
     ) -> DevnetResult<TransactionHash> {
-        add_l1_handler_transaction::add_l1_handler_transaction(self, l1_handler_transaction)
+        add_l1_handler_transaction::add_l1_handler_transaction(
+            self,
+            l1_handler_transaction,
+            l1_transaction_hash,
+        )
     }
 
     /// Creates an invoke tx for minting, using the chargeable account.
@@ -823,21 +934,121 @@ impl Starknet {
         }
     }
 
+    /// Returns proofs of the requested classes, contracts and storage entries against the state
+    /// commitment of the block identified by `block_id`. Proofs for older blocks require a full
+    /// state archive. The pending block is not supported, as it has no state commitment yet.
+    pub fn get_storage_proof(
+        &mut self,
+        block_id: &BlockId,
+        class_hashes: &[ClassHash],
+        contract_addresses: &[ContractAddress],
+        contracts_storage_keys: &[ContractStorageKeys],
+    ) -> DevnetResult<StorageProof> {
+        if block_id == &BlockId::Tag(BlockTag::Pending) {
+            return Err(Error::UnsupportedAction {
+                msg: "Storage proofs are not available for the pending block".into(),
+            });
+        }
+
+        let block_hash = self.get_block(block_id)?.block_hash();
+        let state = self.get_mut_state_at(block_id)?;
+        state.storage_proof(block_hash, class_hashes, contract_addresses, contracts_storage_keys)
+    }
+
     pub fn set_next_block_gas(
         &mut self,
         gas_prices: GasModificationRequest,
     ) -> DevnetResult<GasModification> {
         self.next_block_gas.update(gas_prices.clone());
 
-        // If generate_block is true, generate new block, for now custom dump_event is None but in
-        // future it will change to GasSetEvent with self.next_block_gas data
+        // If generate_block is true, generate new block; re-executing the dump event generates it
         if let Some(true) = gas_prices.generate_block {
-            self.create_block_dump_event(None)?
+            self.create_block_dump_event(Some(DumpEvent::SetGasPrice(gas_prices)))?
+        } else {
+            self.handle_dump_event(DumpEvent::SetGasPrice(gas_prices))?;
         }
 
         Ok(self.next_block_gas.clone())
     }
 
+    /// Sets the value at `key` in the storage of the deployed contract at `contract_address`,
+    /// without executing a transaction. The change is a part of the pending state diff.
+    pub fn set_storage_at(
+        &mut self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+        value: Felt,
+    ) -> DevnetResult<()> {
+        self.pending_state.assert_contract_deployed(contract_address)?;
+        self.pending_state.set_storage_at(contract_address.try_into()?, key.try_into()?, value)?;
+        self.commit_diff()?;
+
+        self.handle_dump_event(DumpEvent::SetStorageAt { contract_address, key, value })
+    }
+
+    /// Sets the nonce of the deployed contract at `contract_address`, without executing a
+    /// transaction. The change is a part of the pending state diff.
+    pub fn set_nonce(
+        &mut self,
+        contract_address: ContractAddress,
+        nonce: Nonce,
+    ) -> DevnetResult<()> {
+        self.pending_state.assert_contract_deployed(contract_address)?;
+        self.pending_state.set_nonce(contract_address, nonce)?;
+        self.commit_diff()?;
+
+        self.handle_dump_event(DumpEvent::SetNonce { contract_address, nonce })
+    }
+
+    /// Replaces the class of the deployed contract at `contract_address` with the declared class
+    /// of `class_hash`, without the access checks of the `replace_class` syscall. The change is a
+    /// part of the pending state diff.
+    pub fn replace_class(
+        &mut self,
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+    ) -> DevnetResult<()> {
+        self.pending_state.assert_contract_deployed(contract_address)?;
+        if !self.pending_state.is_contract_declared(class_hash) {
+            return Err(Error::StateError(StateError::NoneClassHash(class_hash)));
+        }
+
+        self.pending_state.set_class_hash_at(
+            contract_address.try_into()?,
+            starknet_api::core::ClassHash(class_hash),
+        )?;
+        self.commit_diff()?;
+
+        self.handle_dump_event(DumpEvent::ReplaceClass { contract_address, class_hash })
+    }
+
+    /// Deploys a contract of the declared class `class_hash` at `contract_address`, without
+    /// executing a transaction or the constructor of the class. The storage of the contract is
+    /// initialized with `storage`. The contract is deployed the same way as predeployed contracts,
+    /// so only its storage is a part of the pending state diff.
+    pub fn deploy_at(
+        &mut self,
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+        storage: Vec<StorageEntry>,
+    ) -> DevnetResult<()> {
+        if self.pending_state.is_contract_deployed(contract_address)? {
+            return Err(Error::ContractAlreadyDeployed { contract_address });
+        }
+        if !self.pending_state.is_contract_declared(class_hash) {
+            return Err(Error::StateError(StateError::NoneClassHash(class_hash)));
+        }
+
+        self.pending_state.predeploy_contract(contract_address, class_hash)?;
+        let api_address = contract_address.try_into()?;
+        for StorageEntry { key, value } in &storage {
+            self.pending_state.set_storage_at(api_address, (*key).try_into()?, *value)?;
+        }
+        self.commit_diff()?;
+
+        self.handle_dump_event(DumpEvent::DeployAt { contract_address, class_hash, storage })
+    }
+
     pub fn abort_blocks(&mut self, mut starting_block_id: BlockId) -> DevnetResult<Vec<Felt>> {
         if self.config.state_archive != StateArchiveCapacity::Full {
             let msg = "The abort blocks feature requires state-archive-capacity set to full.";
@@ -850,6 +1061,10 @@ impl Starknet {
         }
 
         let starting_block_hash = match self.blocks.get_by_block_id(&starting_block_id) {
+            Some(block) if block.status == BlockStatus::AcceptedOnL1 => {
+                let msg = "Block is already accepted on L1";
+                return Err(Error::UnsupportedAction { msg: msg.into() });
+            }
             Some(block) => block.block_hash(),
             None => return Err(Error::NoBlock),
         };
@@ -891,6 +1106,12 @@ impl Starknet {
                         self.transactions.get_by_hash_mut(tx_hash).ok_or(Error::NoTransaction)?;
                     tx.execution_result =
                         ExecutionResult::Reverted { reason: "Block aborted manually".to_string() };
+                    self.notifier.notify(StarknetNotification::TransactionStatus {
+                        transaction_hash: *tx_hash,
+                        finality_status: tx.finality_status,
+                        execution_status: tx.execution_result.status(),
+                        failure_reason: tx.execution_result.revert_reason().map(String::from),
+                    });
                 }
 
                 rpc_contract_classes.remove_classes_at(block.block_number().0);
@@ -926,6 +1147,58 @@ impl Starknet {
         Ok(aborted)
     }
 
+    /// Marks the block identified by `starting_block_id` and all of its predecessors as accepted
+    /// on L1, together with their transactions. Going backwards from the starting block, stops at
+    /// the first block that is already accepted on L1. Returns the hashes of the newly accepted
+    /// blocks, starting with the latest one.
+    pub fn accept_on_l1(&mut self, starting_block_id: BlockId) -> DevnetResult<Vec<BlockHash>> {
+        if starting_block_id == BlockId::Tag(BlockTag::Pending) {
+            let msg = "Pending block cannot be accepted on L1";
+            return Err(Error::UnsupportedAction { msg: msg.into() });
+        }
+
+        let starting_block =
+            self.blocks.get_by_block_id(&starting_block_id).ok_or(Error::NoBlock)?;
+        if starting_block.status == BlockStatus::Rejected {
+            let msg = "Aborted block cannot be accepted on L1";
+            return Err(Error::UnsupportedAction { msg: msg.into() });
+        }
+
+        let starting_block_hash = starting_block.block_hash();
+        let mut next_block_to_accept_hash = starting_block_hash;
+        let mut accepted = vec![];
+        let mut accepted_transactions = vec![];
+
+        // Blocks of the forking origin are not stored locally, so the iteration stops there.
+        while let Some(block) = self.blocks.hash_to_block.get_mut(&next_block_to_accept_hash) {
+            if block.status == BlockStatus::AcceptedOnL1 {
+                break;
+            }
+
+            block.status = BlockStatus::AcceptedOnL1;
+            for tx_hash in block.get_transactions() {
+                let tx = self.transactions.get_by_hash_mut(tx_hash).ok_or(Error::NoTransaction)?;
+                tx.finality_status = TransactionFinalityStatus::AcceptedOnL1;
+                accepted_transactions.push(*tx_hash);
+            }
+
+            accepted.push(block.block_hash());
+            next_block_to_accept_hash = block.parent_hash();
+        }
+
+        for tx_hash in &accepted_transactions {
+            self.notify_transaction_status(tx_hash);
+        }
+        // a request without effect doesn't need to be replayed
+        if !accepted.is_empty() {
+            self.handle_dump_event(DumpEvent::AcceptOnL1 {
+                starting_block_id: BlockId::Hash(starting_block_hash),
+            })?;
+        }
+
+        Ok(accepted)
+    }
+
     pub fn get_block_txs_count(&self, block_id: &BlockId) -> DevnetResult<u64> {
         let block = self.get_block(block_id)?;
         Ok(block.get_transactions().len() as u64)
@@ -1124,6 +1397,15 @@ impl Starknet {
         Ok((transaction.execution_result.status(), transaction.finality_status))
     }
 
+    pub fn get_transaction_execution_result(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> DevnetResult<&ExecutionResult> {
+        let transaction = self.transactions.get(&transaction_hash).ok_or(Error::NoTransaction)?;
+
+        Ok(&transaction.execution_result)
+    }
+
     pub fn simulate_transactions(
         &mut self,
         block_id: &BlockId,
@@ -1402,6 +1684,8 @@ impl Starknet {
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+    use std::sync::Arc;
     use std::thread;
     use std::time::Duration;
 
@@ -1411,22 +1695,31 @@ mod tests {
     use starknet_api::block::{BlockHash, BlockNumber, BlockStatus, BlockTimestamp, GasPrice};
     use starknet_api::core::EntryPointSelector;
     use starknet_rs_core::types::{BlockId, BlockTag, Felt};
-    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_core::utils::{cairo_short_string_to_felt, get_selector_from_name};
     use starknet_types::contract_address::ContractAddress;
     use starknet_types::felt::felt_from_prefixed_hex;
-    use starknet_types::rpc::state::Balance;
+    use starknet_types::patricia_key::PatriciaKey;
+    use starknet_types::rpc::state::{
+        Balance, ContractNonce, DeployedContract, StateUpdateResult, StorageDiff, StorageEntry,
+    };
     use starknet_types::traits::HashProducer;
+    use starknet_types_core::hash::{Poseidon, StarkHash};
 
+    use super::dump::DumpEvent;
+    use super::notifications::StarknetNotification;
     use super::Starknet;
     use crate::account::{Account, FeeToken};
+    use crate::accounts_file::AccountsFile;
     use crate::blocks::StarknetBlock;
     use crate::constants::{
-        DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_INITIAL_BALANCE,
+        CAIRO_1_ERC20_CONTRACT_CLASS_HASH, DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_INITIAL_BALANCE,
         DEVNET_DEFAULT_STARTING_BLOCK_NUMBER, ETH_ERC20_CONTRACT_ADDRESS,
         STRK_ERC20_CONTRACT_ADDRESS,
     };
-    use crate::error::{DevnetResult, Error};
-    use crate::starknet::starknet_config::{StarknetConfig, StateArchiveCapacity};
+    use crate::error::{DevnetResult, Error, StateError};
+    use crate::starknet::starknet_config::{DumpOn, StarknetConfig, StateArchiveCapacity};
+    use crate::state::state_commitment::StateCommitment;
+    use crate::state::CustomStateReader;
     use crate::traits::{Accounted, Deployed, HashIdentified};
     use crate::utils::test_utils::{
         cairo_0_account_without_validations, dummy_contract_address, dummy_declare_transaction_v1,
@@ -1451,15 +1744,16 @@ mod tests {
 
         let account_class = cairo_0_account_without_validations();
         let acc = Account::new(
+            Balance::from(acc_balance),
             Balance::from(acc_balance),
             dummy_felt(),
-            dummy_felt(),
+            Some(dummy_felt()),
+            Account::compute_account_address(&dummy_felt()).unwrap(),
             account_class.generate_hash().unwrap(),
-            account_class.into(),
+            Arc::new(account_class.into()),
             starknet.block_context.chain_info().fee_token_addresses.eth_fee_token_address.into(),
             starknet.block_context.chain_info().fee_token_addresses.strk_fee_token_address.into(),
-        )
-        .unwrap();
+        );
         acc.deploy(&mut starknet.pending_state).unwrap();
 
         starknet.commit_diff().unwrap();
@@ -1497,6 +1791,132 @@ mod tests {
         }
     }
 
+    #[test]
+    fn predeployed_accounts_are_generated_from_accounts_file() {
+        let accounts_file_path =
+            concat!(env!("CARGO_MANIFEST_DIR"), "/test_artifacts/accounts_file.json");
+        let config = StarknetConfig {
+            accounts_file: Some(AccountsFile::from_str(accounts_file_path).unwrap()),
+            ..Default::default()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let accounts = starknet.predeployed_accounts.get_accounts().clone();
+        assert_eq!(accounts.len(), 2);
+
+        let [account_with_private_key, account_with_public_key] = &accounts[..] else {
+            unreachable!()
+        };
+        assert!(account_with_private_key.private_key.is_some());
+        assert_eq!(
+            account_with_private_key
+                .get_balance(&mut starknet.pending_state, FeeToken::ETH)
+                .unwrap(),
+            Balance::from(1_000_000_000_u32)
+        );
+        assert_eq!(
+            account_with_private_key
+                .get_balance(&mut starknet.pending_state, FeeToken::STRK)
+                .unwrap(),
+            Balance::from(2_u32)
+        );
+
+        assert_eq!(account_with_public_key.private_key, None);
+        assert_eq!(
+            account_with_public_key.account_address,
+            ContractAddress::new(Felt::from(0x1234)).unwrap()
+        );
+        assert_eq!(
+            account_with_public_key
+                .get_balance(&mut starknet.pending_state, FeeToken::STRK)
+                .unwrap(),
+            config.predeployed_accounts_initial_balance
+        );
+        assert_eq!(
+            starknet
+                .get_class_hash_at(
+                    &BlockId::Tag(BlockTag::Latest),
+                    account_with_public_key.account_address
+                )
+                .unwrap(),
+            config.account_contract_class_hash
+        );
+    }
+
+    #[test]
+    fn lazily_predeployed_accounts_are_supplied_on_access() {
+        let config = StarknetConfig { lazy_predeployment: true, ..Default::default() };
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let accounts = starknet.get_predeployed_accounts();
+        assert_eq!(accounts.len(), config.total_accounts as usize);
+
+        for account in accounts {
+            // nothing is written to the state on startup
+            let api_address = account.account_address.try_into().unwrap();
+            assert!(!starknet
+                .pending_state
+                .state
+                .state
+                .address_to_class_hash
+                .contains_key(&api_address));
+            assert!(starknet
+                .pending_state
+                .is_contract_deployed_locally(account.account_address)
+                .unwrap());
+
+            assert_eq!(
+                starknet
+                    .get_class_hash_at(&BlockId::Tag(BlockTag::Latest), account.account_address)
+                    .unwrap(),
+                config.account_contract_class_hash
+            );
+            for fee_token in [FeeToken::ETH, FeeToken::STRK] {
+                assert_eq!(
+                    account.get_balance(&mut starknet.pending_state, fee_token).unwrap(),
+                    config.predeployed_accounts_initial_balance
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lazily_predeployed_accounts_are_committed_to_only_once_modified() {
+        let config = StarknetConfig {
+            lazy_predeployment: true,
+            state_archive: StateArchiveCapacity::Full,
+            ..Default::default()
+        };
+        let genesis_root_of = |config: &StarknetConfig| {
+            Starknet::new(config).unwrap().get_latest_block().unwrap().new_root()
+        };
+
+        let mut starknet = Starknet::new(&config).unwrap();
+        let genesis_root = starknet.get_latest_block().unwrap().new_root();
+        assert_eq!(
+            genesis_root,
+            genesis_root_of(&StarknetConfig { total_accounts: 0, ..config.clone() })
+        );
+        assert_ne!(
+            genesis_root,
+            genesis_root_of(&StarknetConfig { lazy_predeployment: false, ..config.clone() })
+        );
+
+        let account_address = starknet.get_predeployed_accounts()[0].account_address;
+        let key = PatriciaKey::new(Felt::from(42)).unwrap();
+        starknet.set_storage_at(account_address, key, Felt::from(123)).unwrap();
+        starknet.create_block().unwrap();
+        assert_ne!(starknet.get_latest_block().unwrap().new_root(), genesis_root);
+
+        let proof = starknet
+            .get_storage_proof(&BlockId::Tag(BlockTag::Latest), &[], &[account_address], &[])
+            .unwrap();
+        assert_eq!(
+            proof.contracts_proof.contract_leaves_data[0].class_hash,
+            config.account_contract_class_hash
+        );
+    }
+
     #[test]
     fn correct_block_context_creation() {
         let fee_token_address =
@@ -1560,6 +1980,31 @@ mod tests {
         assert_eq!(*added_block.get_transactions().first().unwrap(), *tx.get_transaction_hash());
     }
 
+    #[test]
+    fn new_block_notification_received_before_and_after_restart() {
+        let mut starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+        let mut receiver = starknet.subscribe_to_notifications();
+
+        let block_hash = starknet.generate_new_block_and_state().unwrap();
+        match receiver.try_recv() {
+            Ok(StarknetNotification::NewBlock(header)) => {
+                assert_eq!(header.block_hash, block_hash);
+                assert_eq!(header.block_number, BlockNumber(1));
+            }
+            unexpected => panic!("Got unexpected notification: {unexpected:?}"),
+        }
+
+        starknet.restart().unwrap();
+        let block_hash = starknet.generate_new_block_and_state().unwrap();
+        match receiver.try_recv() {
+            Ok(StarknetNotification::NewBlock(header)) => {
+                assert_eq!(header.block_hash, block_hash);
+                assert_eq!(header.block_number, BlockNumber(1));
+            }
+            unexpected => panic!("Got unexpected notification: {unexpected:?}"),
+        }
+    }
+
     #[test]
     fn successful_emptying_of_pending_block() {
         let config = StarknetConfig { start_time: Some(0), ..Default::default() };
@@ -1944,4 +2389,276 @@ mod tests {
             unexpected => panic!("Got unexpected response: {unexpected:?}"),
         }
     }
+
+    #[test]
+    fn accepting_blocks_on_l1_accepts_predecessors_only_once() {
+        let mut starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+        starknet.create_block().unwrap();
+        let first_block_hash = starknet.get_latest_block().unwrap().block_hash();
+        starknet.create_block().unwrap();
+        let second_block_hash = starknet.get_latest_block().unwrap().block_hash();
+
+        let genesis_block_hash = starknet.get_block(&BlockId::Number(0)).unwrap().block_hash();
+        let accepted = starknet.accept_on_l1(BlockId::Hash(first_block_hash)).unwrap();
+        assert_eq!(accepted, vec![first_block_hash, genesis_block_hash]);
+
+        let accepted = starknet.accept_on_l1(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(accepted, vec![second_block_hash]);
+
+        for block_hash in [genesis_block_hash, first_block_hash, second_block_hash] {
+            let block = starknet.get_block(&BlockId::Hash(block_hash)).unwrap();
+            assert_eq!(block.status, BlockStatus::AcceptedOnL1);
+        }
+
+        // nothing left to accept
+        assert!(starknet.accept_on_l1(BlockId::Tag(BlockTag::Latest)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn accepting_blocks_on_l1_without_effect_is_not_dumped() {
+        let config = StarknetConfig { dump_on: Some(DumpOn::Request), ..Default::default() };
+        let mut starknet = Starknet::new(&config).unwrap();
+        starknet.accept_on_l1(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert!(starknet.accept_on_l1(BlockId::Tag(BlockTag::Latest)).unwrap().is_empty());
+
+        let accept_events = starknet
+            .dump_events
+            .iter()
+            .filter(|event| matches!(event, DumpEvent::AcceptOnL1 { .. }))
+            .count();
+        assert_eq!(accept_events, 1);
+    }
+
+    #[test]
+    fn accepting_pending_or_non_existent_block_on_l1_fails() {
+        let mut starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+
+        match starknet.accept_on_l1(BlockId::Tag(BlockTag::Pending)) {
+            Err(Error::UnsupportedAction { .. }) => (),
+            unexpected => panic!("Got unexpected response: {unexpected:?}"),
+        }
+
+        match starknet.accept_on_l1(BlockId::Number(42)) {
+            Err(Error::NoBlock) => (),
+            unexpected => panic!("Got unexpected response: {unexpected:?}"),
+        }
+    }
+
+    #[test]
+    fn storage_proof_roots_match_block_state_root() {
+        let (mut starknet, acc) = setup_starknet_with_no_signature_check_account_and_state_capacity(
+            1e18 as u128,
+            StateArchiveCapacity::Full,
+        );
+        let latest_block = starknet.get_latest_block().unwrap();
+
+        let proof = starknet
+            .get_storage_proof(&BlockId::Tag(BlockTag::Latest), &[], &[acc.account_address], &[])
+            .unwrap();
+        assert_eq!(proof.global_roots.block_hash, latest_block.block_hash());
+        assert_eq!(
+            Poseidon::hash_array(&[
+                cairo_short_string_to_felt("STARKNET_STATE_V0").unwrap(),
+                proof.global_roots.contracts_tree_root,
+                proof.global_roots.classes_tree_root,
+            ]),
+            latest_block.new_root()
+        );
+        assert_eq!(proof.contracts_proof.contract_leaves_data[0].class_hash, acc.class_hash);
+        assert!(!proof.contracts_proof.nodes.is_empty());
+
+        // the proof for an older block is retrieved from the state archive
+        let old_proof = starknet
+            .get_storage_proof(&BlockId::Number(latest_block.block_number().0), &[], &[], &[])
+            .unwrap();
+        starknet.generate_new_block_and_state().unwrap();
+        assert_eq!(
+            starknet
+                .get_storage_proof(&BlockId::Number(latest_block.block_number().0), &[], &[], &[])
+                .unwrap(),
+            old_proof
+        );
+    }
+
+    #[test]
+    fn storage_proof_not_available_for_pending_block_and_without_state_archive() {
+        let (mut starknet, _) = setup_starknet_with_no_signature_check_account(1e18 as u128);
+
+        match starknet.get_storage_proof(&BlockId::Tag(BlockTag::Pending), &[], &[], &[]) {
+            Err(Error::UnsupportedAction { .. }) => (),
+            unexpected => panic!("Got unexpected response: {unexpected:?}"),
+        }
+
+        match starknet.get_storage_proof(&BlockId::Number(0), &[], &[], &[]) {
+            Err(Error::NoStateAtBlock { .. }) => (),
+            unexpected => panic!("Got unexpected response: {unexpected:?}"),
+        }
+    }
+
+    #[test]
+    fn state_root_updated_with_block_diffs_equals_root_of_whole_state() {
+        let (mut starknet, acc) = setup_starknet_with_no_signature_check_account(1e18 as u128);
+
+        for value in 1..=3_u32 {
+            let key = PatriciaKey::new(Felt::from(value)).unwrap();
+            starknet.set_storage_at(acc.account_address, key, Felt::from(value)).unwrap();
+            starknet.create_block().unwrap();
+        }
+
+        let expected_root = StateCommitment::from_state(&starknet.latest_state.state.state)
+            .unwrap()
+            .root()
+            .unwrap();
+        assert_eq!(starknet.get_latest_block().unwrap().new_root(), expected_root);
+    }
+
+    #[test]
+    fn set_storage_at_changes_pending_state_and_state_update() {
+        let mut starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+        let contract_address =
+            ContractAddress::new(felt_from_prefixed_hex(ETH_ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap();
+        let key = PatriciaKey::new(Felt::from(42)).unwrap();
+        let value = Felt::from(123);
+
+        starknet.set_storage_at(contract_address, key, value).unwrap();
+
+        let pending = BlockId::Tag(BlockTag::Pending);
+        assert_eq!(
+            starknet.contract_storage_at_block(&pending, contract_address, key).unwrap(),
+            value
+        );
+        match starknet.block_state_update(&pending).unwrap() {
+            StateUpdateResult::PendingStateUpdate(state_update) => assert_eq!(
+                state_update.state_diff.storage_diffs,
+                vec![StorageDiff {
+                    address: contract_address,
+                    storage_entries: vec![StorageEntry { key, value }]
+                }]
+            ),
+            other => panic!("Unexpected state update: {other:?}"),
+        }
+
+        // the change is included in the next block
+        starknet.create_block().unwrap();
+        let latest = BlockId::Tag(BlockTag::Latest);
+        assert_eq!(
+            starknet.contract_storage_at_block(&latest, contract_address, key).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn set_storage_at_of_undeployed_contract_fails() {
+        let mut starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+        let key = PatriciaKey::new(Felt::from(42)).unwrap();
+
+        match starknet.set_storage_at(dummy_contract_address(), key, Felt::ONE) {
+            Err(Error::ContractNotFound) => (),
+            unexpected => panic!("Got unexpected response: {unexpected:?}"),
+        }
+    }
+
+    #[test]
+    fn set_nonce_changes_pending_state_and_state_update() {
+        let (mut starknet, account) = setup_starknet_with_no_signature_check_account(1e18 as u128);
+        let nonce = Felt::from(7);
+
+        starknet.set_nonce(account.account_address, nonce).unwrap();
+
+        let pending = BlockId::Tag(BlockTag::Pending);
+        assert_eq!(
+            starknet.contract_nonce_at_block(&pending, account.account_address).unwrap(),
+            nonce
+        );
+        match starknet.block_state_update(&pending).unwrap() {
+            StateUpdateResult::PendingStateUpdate(state_update) => assert_eq!(
+                state_update.state_diff.nonces,
+                vec![ContractNonce { contract_address: account.account_address, nonce }]
+            ),
+            other => panic!("Unexpected state update: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn replace_class_changes_class_hash_and_state_update() {
+        let (mut starknet, account) = setup_starknet_with_no_signature_check_account(1e18 as u128);
+        let class_hash = felt_from_prefixed_hex(CAIRO_1_ERC20_CONTRACT_CLASS_HASH).unwrap();
+
+        starknet.replace_class(account.account_address, class_hash).unwrap();
+
+        let pending = BlockId::Tag(BlockTag::Pending);
+        assert_eq!(
+            starknet.get_class_hash_at(&pending, account.account_address).unwrap(),
+            class_hash
+        );
+        match starknet.block_state_update(&pending).unwrap() {
+            StateUpdateResult::PendingStateUpdate(state_update) => assert_eq!(
+                state_update.state_diff.deployed_contracts,
+                vec![DeployedContract { address: account.account_address, class_hash }]
+            ),
+            other => panic!("Unexpected state update: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn replace_class_with_undeclared_class_fails() {
+        let (mut starknet, account) = setup_starknet_with_no_signature_check_account(1e18 as u128);
+        let undeclared_class_hash = Felt::from(42);
+
+        match starknet.replace_class(account.account_address, undeclared_class_hash) {
+            Err(Error::StateError(StateError::NoneClassHash(class_hash))) => {
+                assert_eq!(class_hash, undeclared_class_hash)
+            }
+            unexpected => panic!("Got unexpected response: {unexpected:?}"),
+        }
+    }
+
+    #[test]
+    fn deploy_at_places_contract_with_storage() {
+        let mut starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+        let contract_address = dummy_contract_address();
+        let class_hash = felt_from_prefixed_hex(CAIRO_1_ERC20_CONTRACT_CLASS_HASH).unwrap();
+        let storage = vec![StorageEntry {
+            key: PatriciaKey::new(Felt::from(42)).unwrap(),
+            value: Felt::from(123),
+        }];
+
+        starknet.deploy_at(contract_address, class_hash, storage.clone()).unwrap();
+
+        let pending = BlockId::Tag(BlockTag::Pending);
+        assert_eq!(starknet.get_class_hash_at(&pending, contract_address).unwrap(), class_hash);
+        assert_eq!(
+            starknet.contract_storage_at_block(&pending, contract_address, storage[0].key).unwrap(),
+            storage[0].value
+        );
+        match starknet.block_state_update(&pending).unwrap() {
+            StateUpdateResult::PendingStateUpdate(state_update) => {
+                assert!(state_update.state_diff.deployed_contracts.is_empty());
+                assert_eq!(
+                    state_update.state_diff.storage_diffs,
+                    vec![StorageDiff { address: contract_address, storage_entries: storage }]
+                );
+            }
+            other => panic!("Unexpected state update: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn deploy_at_fails_if_address_is_occupied_or_class_is_undeclared() {
+        let (mut starknet, account) = setup_starknet_with_no_signature_check_account(1e18 as u128);
+        let class_hash = felt_from_prefixed_hex(CAIRO_1_ERC20_CONTRACT_CLASS_HASH).unwrap();
+
+        match starknet.deploy_at(account.account_address, class_hash, vec![]) {
+            Err(Error::ContractAlreadyDeployed { contract_address }) => {
+                assert_eq!(contract_address, account.account_address)
+            }
+            unexpected => panic!("Got unexpected response: {unexpected:?}"),
+        }
+
+        match starknet.deploy_at(dummy_contract_address(), Felt::from(42), vec![]) {
+            Err(Error::StateError(StateError::NoneClassHash(_))) => (),
+            unexpected => panic!("Got unexpected response: {unexpected:?}"),
+        }
+    }
 }
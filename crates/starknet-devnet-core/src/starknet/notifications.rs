@@ -0,0 +1,51 @@
+use starknet_rs_core::types::{TransactionExecutionStatus, TransactionFinalityStatus};
+use starknet_types::emitted_event::EmittedEvent;
+use starknet_types::felt::TransactionHash;
+use starknet_types::rpc::block::BlockHeader;
+use starknet_types::rpc::transactions::TransactionWithHash;
+use tokio::sync::broadcast;
+
+/// The number of notifications that can be buffered for a receiver. If a receiver falls behind by
+/// more than this, it starts missing the oldest notifications.
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 1024;
+
+/// Changes of Starknet that clients can be notified of
+#[derive(Debug, Clone)]
+pub enum StarknetNotification {
+    NewBlock(Box<BlockHeader>),
+    /// A transaction accepted into the pending block
+    PendingTransaction(Box<TransactionWithHash>),
+    /// All events emitted in a new block
+    NewEvents(Vec<EmittedEvent>),
+    /// The finality or the execution status of a transaction has changed
+    TransactionStatus {
+        transaction_hash: TransactionHash,
+        finality_status: TransactionFinalityStatus,
+        execution_status: TransactionExecutionStatus,
+        /// The revert reason, if the transaction was reverted
+        failure_reason: Option<String>,
+    },
+}
+
+/// Broadcasts notifications to all of its receivers
+#[derive(Clone)]
+pub(crate) struct Notifier {
+    sender: broadcast::Sender<StarknetNotification>,
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self { sender: broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY).0 }
+    }
+}
+
+impl Notifier {
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<StarknetNotification> {
+        self.sender.subscribe()
+    }
+
+    pub(crate) fn notify(&self, notification: StarknetNotification) {
+        // sending fails only if there are no receivers, which is not an error
+        let _ = self.sender.send(notification);
+    }
+}
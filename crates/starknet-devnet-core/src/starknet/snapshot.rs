@@ -0,0 +1,150 @@
+use blockifier::context::BlockContext;
+use starknet_types::rpc::gas_modification::GasModification;
+use tracing::info;
+
+use super::cheats::Cheats;
+use super::dump::DumpEvent;
+use super::starknet_config::DumpOn;
+use super::Starknet;
+use crate::blocks::StarknetBlocks;
+use crate::error::{DevnetResult, Error};
+use crate::messaging::MessagingBroker;
+use crate::state::state_diff::StateDiff;
+use crate::state::{CommittedClassStorage, StarknetState};
+use crate::transactions::StarknetTransactionStatus;
+
+/// In-memory copy of the parts of Starknet that change during its lifetime. The configuration,
+/// the predeployed accounts and the notifier are not part of it.
+pub(crate) struct Snapshot {
+    latest_state: StarknetState,
+    pending_state: StarknetState,
+    pending_state_diff: StateDiff,
+    rpc_contract_classes: CommittedClassStorage,
+    block_context: BlockContext,
+    blocks: StarknetBlocks,
+    transaction_statuses: Vec<StarknetTransactionStatus>,
+    pending_block_timestamp_shift: i64,
+    next_block_timestamp: Option<u64>,
+    next_block_gas: GasModification,
+    messaging: MessagingBroker,
+    dump_events: Vec<DumpEvent>,
+    cheats: Cheats,
+}
+
+impl Starknet {
+    /// Stores a copy of the current state in memory and returns the id under which it can be
+    /// reverted to. Ids are increasing, starting from 0.
+    pub fn snapshot(&mut self) -> DevnetResult<u64> {
+        // events dumped on block creation are already written to the file and can't be reverted
+        if self.config.dump_on == Some(DumpOn::Block) {
+            let msg = "Snapshots are not supported when dumping on block creation.";
+            return Err(Error::UnsupportedAction { msg: msg.into() });
+        }
+
+        let snapshot = Snapshot {
+            latest_state: self.latest_state.clone_historic(),
+            pending_state: self.pending_state.clone_historic(),
+            pending_state_diff: self.pending_state_diff.clone(),
+            rpc_contract_classes: self.rpc_contract_classes.read().clone(),
+            block_context: self.block_context.clone(),
+            blocks: self.blocks.clone_without_states(),
+            transaction_statuses: self.transactions.statuses(),
+            pending_block_timestamp_shift: self.pending_block_timestamp_shift,
+            next_block_timestamp: self.next_block_timestamp,
+            next_block_gas: self.next_block_gas.clone(),
+            messaging: self.messaging.clone_without_ethereum(),
+            dump_events: self.dump_events.clone(),
+            cheats: self.cheats.clone(),
+        };
+
+        let snapshot_id = self.next_snapshot_id;
+        self.snapshots.insert(snapshot_id, snapshot);
+        self.next_snapshot_id += 1;
+        info!("Created snapshot {snapshot_id}");
+
+        Ok(snapshot_id)
+    }
+
+    /// Restores the state stored under `snapshot_id`. The snapshot and all snapshots created after
+    /// it are discarded.
+    pub fn revert(&mut self, snapshot_id: u64) -> DevnetResult<()> {
+        let snapshot =
+            self.snapshots.remove(&snapshot_id).ok_or(Error::NoSnapshot { snapshot_id })?;
+        self.snapshots.retain(|id, _| *id < snapshot_id);
+
+        self.latest_state = snapshot.latest_state;
+        self.pending_state = snapshot.pending_state;
+        self.pending_state_diff = snapshot.pending_state_diff;
+        *self.rpc_contract_classes.write() = snapshot.rpc_contract_classes;
+        self.block_context = snapshot.block_context;
+        self.blocks.restore(snapshot.blocks);
+        self.transactions.restore(snapshot.transaction_statuses);
+        self.pending_block_timestamp_shift = snapshot.pending_block_timestamp_shift;
+        self.next_block_timestamp = snapshot.next_block_timestamp;
+        self.next_block_gas = snapshot.next_block_gas;
+        self.messaging.restore(snapshot.messaging);
+        self.dump_events = snapshot.dump_events;
+        self.cheats = snapshot.cheats;
+        info!("Reverted to snapshot {snapshot_id}");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_api::block::BlockStatus;
+    use starknet_rs_core::types::{BlockId, BlockTag};
+
+    use crate::error::Error;
+    use crate::starknet::starknet_config::StarknetConfig;
+    use crate::starknet::Starknet;
+
+    #[test]
+    fn revert_restores_blocks() {
+        let mut starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+        let genesis_block = starknet.get_latest_block().unwrap();
+
+        let snapshot_id = starknet.snapshot().unwrap();
+        starknet.create_block().unwrap();
+        let reverted_block_hash = starknet.get_latest_block().unwrap().block_hash();
+        starknet.accept_on_l1(BlockId::Tag(BlockTag::Latest)).unwrap();
+
+        starknet.revert(snapshot_id).unwrap();
+
+        let latest_block = starknet.get_latest_block().unwrap();
+        assert_eq!(latest_block.block_hash(), genesis_block.block_hash());
+        assert_eq!(latest_block.status, BlockStatus::AcceptedOnL2);
+        assert!(starknet.get_block(&BlockId::Hash(reverted_block_hash)).is_err());
+
+        // the reverted Starknet can still be used
+        starknet.create_block().unwrap();
+        assert_eq!(
+            starknet.get_latest_block().unwrap().block_number(),
+            genesis_block.block_number().next().unwrap()
+        );
+    }
+
+    #[test]
+    fn revert_discards_reverted_and_later_snapshots() {
+        let mut starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+
+        let first_snapshot_id = starknet.snapshot().unwrap();
+        let second_snapshot_id = starknet.snapshot().unwrap();
+        let third_snapshot_id = starknet.snapshot().unwrap();
+        assert_eq!((first_snapshot_id, second_snapshot_id, third_snapshot_id), (0, 1, 2));
+
+        starknet.revert(second_snapshot_id).unwrap();
+
+        for snapshot_id in [second_snapshot_id, third_snapshot_id] {
+            match starknet.revert(snapshot_id) {
+                Err(Error::NoSnapshot { snapshot_id: id }) => assert_eq!(id, snapshot_id),
+                unexpected => panic!("Got unexpected response: {unexpected:?}"),
+            }
+        }
+
+        starknet.revert(first_snapshot_id).unwrap();
+        // ids are not reused
+        assert_eq!(starknet.snapshot().unwrap(), 3);
+    }
+}
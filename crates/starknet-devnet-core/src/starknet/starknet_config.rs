@@ -1,7 +1,7 @@
 use std::num::NonZeroU128;
 
 use clap::Error;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 use starknet_rs_core::types::Felt;
 use starknet_types::chain_id::ChainId;
 use starknet_types::contract_class::ContractClass;
@@ -9,11 +9,14 @@ use starknet_types::rpc::state::Balance;
 use starknet_types::traits::HashProducer;
 use url::Url;
 
+use super::genesis::Genesis;
+use crate::accounts_file::AccountsFile;
 use crate::constants::{
     CAIRO_1_ACCOUNT_CONTRACT_SIERRA, DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_DATA_GAS_PRICE,
     DEVNET_DEFAULT_GAS_PRICE, DEVNET_DEFAULT_INITIAL_BALANCE, DEVNET_DEFAULT_TEST_SEED,
     DEVNET_DEFAULT_TOTAL_ACCOUNTS,
 };
+use crate::mnemonic::AccountMnemonic;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -23,6 +26,25 @@ pub enum DumpOn {
     Request,
 }
 
+#[derive(Default, Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[clap(rename_all = "snake_case")]
+pub enum DumpFormat {
+    #[default]
+    Events,
+    State,
+}
+
+/// The point up to which a dump of events is loaded
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadUntil {
+    /// Stop after the block with this number is generated
+    BlockNumber(u64),
+    /// Stop after the event with this (zero-based) index is re-executed
+    EventIndex(usize),
+}
+
 #[derive(Default, Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum, Serialize)]
 #[serde(rename_all = "snake_case")]
 #[clap(rename_all = "snake_case")]
@@ -32,7 +54,7 @@ pub enum StateArchiveCapacity {
     Full,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum BlockGenerationOn {
     Transaction,
@@ -40,6 +62,16 @@ pub enum BlockGenerationOn {
     Interval(u64),
 }
 
+impl std::fmt::Display for BlockGenerationOn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlockGenerationOn::Transaction => write!(f, "transaction"),
+            BlockGenerationOn::Demand => write!(f, "demand"),
+            BlockGenerationOn::Interval(interval) => write!(f, "{interval}"),
+        }
+    }
+}
+
 impl std::str::FromStr for BlockGenerationOn {
     type Err = Error;
 
@@ -96,7 +128,13 @@ where
 #[derive(Clone, Debug, Serialize)]
 pub struct StarknetConfig {
     pub seed: u32,
-    pub total_accounts: u8,
+    /// If provided, the keys of predeployed accounts are derived from it instead of the seed
+    #[serde(skip_serializing)]
+    pub mnemonic: Option<AccountMnemonic>,
+    pub total_accounts: u32,
+    /// If true, the state of predeployed accounts is materialized on first access instead of on
+    /// startup
+    pub lazy_predeployment: bool,
     #[serde(skip_serializing)]
     pub account_contract_class: ContractClass,
     pub account_contract_class_hash: Felt,
@@ -111,11 +149,20 @@ pub struct StarknetConfig {
     pub chain_id: ChainId,
     pub dump_on: Option<DumpOn>,
     pub dump_path: Option<String>,
+    pub dump_format: DumpFormat,
+    pub load_until: Option<LoadUntil>,
+    /// If true, dumps of events created by other versions of Devnet are loaded as well
+    pub ignore_dump_version: bool,
     pub block_generation_on: BlockGenerationOn,
     pub lite_mode: bool,
     pub state_archive: StateArchiveCapacity,
     pub fork_config: ForkConfig,
     pub disable_account_impersonation: bool,
+    #[serde(skip_serializing)]
+    pub genesis: Option<Genesis>,
+    /// If provided, the accounts are predeployed from it instead of being generated from the seed
+    #[serde(skip_serializing)]
+    pub accounts_file: Option<AccountsFile>,
 }
 
 impl Default for StarknetConfig {
@@ -126,7 +173,9 @@ impl Default for StarknetConfig {
                 .into();
         StarknetConfig {
             seed: DEVNET_DEFAULT_TEST_SEED,
+            mnemonic: None,
             total_accounts: DEVNET_DEFAULT_TOTAL_ACCOUNTS,
+            lazy_predeployment: false,
             account_contract_class_hash: account_contract_class.generate_hash().unwrap(),
             account_contract_class,
             predeployed_accounts_initial_balance: DEVNET_DEFAULT_INITIAL_BALANCE.into(),
@@ -138,11 +187,16 @@ impl Default for StarknetConfig {
             chain_id: DEVNET_DEFAULT_CHAIN_ID,
             dump_on: None,
             dump_path: None,
+            dump_format: DumpFormat::default(),
+            load_until: None,
+            ignore_dump_version: false,
             block_generation_on: BlockGenerationOn::Transaction,
             lite_mode: false,
             state_archive: StateArchiveCapacity::default(),
             fork_config: ForkConfig::default(),
             disable_account_impersonation: false,
+            genesis: None,
+            accounts_file: None,
         }
     }
 }
@@ -0,0 +1,455 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use blockifier::blockifier::block::GasPrices;
+use blockifier::context::BlockContext;
+use blockifier::execution::contract_class::{
+    ContractClass as CompiledContractClass, ContractClassV1,
+};
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::contract_class::ContractClass as SierraContractClass;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{BlockNumber, BlockStatus, BlockTimestamp, GasPricePerToken};
+use starknet_api::core::{GlobalRoot, SequencerContractAddress};
+use starknet_api::data_availability::L1DataAvailabilityMode;
+use starknet_rs_core::types::Felt;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::{
+    Cairo0ContractClass, Cairo0Json, ContractClass, DeprecatedContractClass,
+};
+use starknet_types::felt::{BlockHash, ClassHash, TransactionHash};
+use starknet_types::rpc::gas_modification::GasModification;
+use starknet_types::rpc::messaging::MessageToL1;
+
+use super::starknet_config::StateArchiveCapacity;
+use super::Starknet;
+use crate::blocks::{StarknetBlock, StarknetBlocks};
+use crate::error::{DevnetResult, Error};
+use crate::state::state_commitment::StateCommitment;
+use crate::state::state_diff::StateDiff;
+use crate::state::state_readers::DictState;
+use crate::state::{apply_state_diff, CommittedClassStorage, StarknetState};
+use crate::transactions::StarknetTransactions;
+use crate::utils::{custom_bouncer_config, get_versioned_constants};
+
+/// The state of Devnet written out directly, so that it can be loaded without re-executing the
+/// transactions that generated it. The state at a block is obtained by applying the state diffs
+/// of all the blocks up to it to the initial state diff.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StateDump {
+    initial_state_diff: StateDiff,
+    classes: Vec<DumpedClass>,
+    /// The accepted blocks ordered by block number, followed by the rejected blocks
+    blocks: Vec<DumpedBlock>,
+    pending_block: DumpedBlock,
+    aborted_blocks: Vec<BlockHash>,
+    transactions: StarknetTransactions,
+    gas_prices: GasModification,
+    pending_block_timestamp_shift: i64,
+    next_block_timestamp: Option<u64>,
+    next_block_gas: GasModification,
+    /// Messaging state which can't be derived from the transactions
+    l2_to_l1_messages_hashes: HashMap<String, u64>,
+    l2_to_l1_messages_to_flush: Vec<MessageToL1>,
+    l1_to_l2_tx_hashes: HashMap<String, Vec<TransactionHash>>,
+    last_local_block: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct DumpedClass {
+    class_hash: ClassHash,
+    /// The number of the block in which the class was declared
+    block_number: u64,
+    contract_class: DumpedContractClass,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+enum DumpedContractClass {
+    Cairo0Json(Cairo0Json),
+    Cairo0Rpc(DeprecatedContractClass),
+    /// CASM is stored so that the class doesn't need to be compiled on load
+    Cairo1 {
+        sierra: SierraContractClass,
+        casm: CasmContractClass,
+    },
+}
+
+impl DumpedContractClass {
+    /// The CASM of a Cairo 1 class is taken from `class_storage`, where it was stored on declaration
+    fn new(
+        class_hash: ClassHash,
+        contract_class: &ContractClass,
+        class_storage: &CommittedClassStorage,
+    ) -> DevnetResult<Self> {
+        Ok(match contract_class {
+            ContractClass::Cairo0(Cairo0ContractClass::RawJson(json_class)) => {
+                Self::Cairo0Json(json_class.clone())
+            }
+            ContractClass::Cairo0(Cairo0ContractClass::Rpc(rpc_class)) => {
+                Self::Cairo0Rpc(rpc_class.clone())
+            }
+            ContractClass::Cairo1(sierra) => {
+                let casm = class_storage.get_casm(&class_hash).cloned().ok_or(
+                    Error::UnexpectedInternalError {
+                        msg: format!("No CASM of class {class_hash:#x}"),
+                    },
+                )?;
+                Self::Cairo1 { sierra: sierra.clone(), casm }
+            }
+        })
+    }
+
+    /// Returns the class in the form returned via RPC, in the form used for execution and, if it's
+    /// a Cairo 1 class, its CASM
+    fn into_classes(
+        self,
+    ) -> DevnetResult<(ContractClass, CompiledContractClass, Option<CasmContractClass>)> {
+        let contract_class = match self {
+            Self::Cairo0Json(json_class) => ContractClass::from(json_class),
+            Self::Cairo0Rpc(rpc_class) => ContractClass::from(rpc_class),
+            Self::Cairo1 { sierra, casm } => {
+                let compiled_class = ContractClassV1::try_from(casm.clone())
+                    .map_err(|err| Error::DeserializationError { origin: err.to_string() })?;
+                return Ok((
+                    ContractClass::Cairo1(sierra),
+                    CompiledContractClass::V1(compiled_class),
+                    Some(casm),
+                ));
+            }
+        };
+
+        let compiled_class = contract_class.clone().try_into()?;
+        Ok((contract_class, compiled_class, None))
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct DumpedBlock {
+    block_hash: BlockHash,
+    parent_hash: BlockHash,
+    block_number: BlockNumber,
+    state_root: Felt,
+    sequencer_address: ContractAddress,
+    timestamp: BlockTimestamp,
+    l1_gas_price: GasPricePerToken,
+    l1_data_gas_price: GasPricePerToken,
+    l1_da_mode: L1DataAvailabilityMode,
+    status: BlockStatus,
+    transaction_hashes: Vec<TransactionHash>,
+    state_diff: StateDiff,
+}
+
+impl DumpedBlock {
+    fn new(block: &StarknetBlock, state_diff: StateDiff) -> Self {
+        Self {
+            block_hash: block.block_hash(),
+            parent_hash: block.parent_hash(),
+            block_number: block.block_number(),
+            state_root: block.new_root(),
+            sequencer_address: block.sequencer_address(),
+            timestamp: block.timestamp(),
+            l1_gas_price: block.header.l1_gas_price,
+            l1_data_gas_price: block.header.l1_data_gas_price,
+            l1_da_mode: block.header.l1_da_mode,
+            status: block.status,
+            transaction_hashes: block.get_transactions().clone(),
+            state_diff,
+        }
+    }
+
+    fn into_block(self) -> DevnetResult<(StarknetBlock, StateDiff)> {
+        let mut block = StarknetBlock::create_pending_block();
+        block.header.block_hash = starknet_api::block::BlockHash(self.block_hash);
+        block.header.parent_hash = starknet_api::block::BlockHash(self.parent_hash);
+        block.header.block_number = self.block_number;
+        block.header.state_root = GlobalRoot(self.state_root);
+        block.header.sequencer = SequencerContractAddress(self.sequencer_address.try_into()?);
+        block.header.timestamp = self.timestamp;
+        block.header.l1_gas_price = self.l1_gas_price;
+        block.header.l1_data_gas_price = self.l1_data_gas_price;
+        block.header.l1_da_mode = self.l1_da_mode;
+        block.status = self.status;
+        for transaction_hash in self.transaction_hashes {
+            block.add_transaction(transaction_hash);
+        }
+
+        Ok((block, self.state_diff))
+    }
+}
+
+impl Starknet {
+    pub(crate) fn create_state_dump(&self) -> DevnetResult<StateDump> {
+        let class_storage = self.rpc_contract_classes.read();
+        let mut classes = class_storage
+            .committed_classes()
+            .map(|(class_hash, contract_class, block_number)| {
+                Ok(DumpedClass {
+                    class_hash: *class_hash,
+                    block_number,
+                    contract_class: DumpedContractClass::new(
+                        *class_hash,
+                        contract_class,
+                        &class_storage,
+                    )?,
+                })
+            })
+            .collect::<DevnetResult<Vec<_>>>()?;
+        classes.sort_by_key(|class| (class.block_number, class.class_hash));
+
+        let accepted_blocks =
+            self.blocks.num_to_hash.values().filter_map(|hash| self.blocks.hash_to_block.get(hash));
+        let mut rejected_blocks: Vec<_> = self
+            .blocks
+            .hash_to_block
+            .values()
+            .filter(|block| block.status == BlockStatus::Rejected)
+            .collect();
+        rejected_blocks.sort_by_key(|block| block.block_number());
+
+        let blocks = accepted_blocks
+            .chain(rejected_blocks)
+            .map(|block| {
+                let state_diff = self.blocks.hash_to_state_diff.get(&block.block_hash()).ok_or(
+                    Error::UnexpectedInternalError {
+                        msg: format!("No state diff of block {:#x}", block.block_hash()),
+                    },
+                )?;
+                Ok(DumpedBlock::new(block, state_diff.clone()))
+            })
+            .collect::<DevnetResult<_>>()?;
+
+        let gas_prices = &self.block_context.block_info().gas_prices;
+        Ok(StateDump {
+            initial_state_diff: self.initial_state_diff.clone(),
+            classes,
+            blocks,
+            pending_block: DumpedBlock::new(
+                &self.blocks.pending_block,
+                self.pending_state_diff.clone(),
+            ),
+            aborted_blocks: self.blocks.aborted_blocks.clone(),
+            transactions: self.transactions.clone(),
+            gas_prices: GasModification {
+                gas_price_wei: gas_prices.eth_l1_gas_price,
+                data_gas_price_wei: gas_prices.eth_l1_data_gas_price,
+                gas_price_fri: gas_prices.strk_l1_gas_price,
+                data_gas_price_fri: gas_prices.strk_l1_data_gas_price,
+            },
+            pending_block_timestamp_shift: self.pending_block_timestamp_shift,
+            next_block_timestamp: self.next_block_timestamp,
+            next_block_gas: self.next_block_gas.clone(),
+            l2_to_l1_messages_hashes: self.messaging.l2_to_l1_messages_hashes.clone(),
+            l2_to_l1_messages_to_flush: self.messaging.l2_to_l1_messages_to_flush.clone(),
+            l1_to_l2_tx_hashes: self.messaging.l1_to_l2_tx_hashes.clone(),
+            last_local_block: self.messaging.last_local_block,
+        })
+    }
+
+    /// Replaces the state, the blocks and the transactions with the ones from `dump`. No
+    /// transactions are executed. Expects that no blocks have been created since startup.
+    pub(crate) fn load_state_dump(&mut self, dump: StateDump) -> DevnetResult<()> {
+        let mut compiled_classes = HashMap::new();
+        let mut casm_classes = vec![];
+        let mut classes_per_block = BTreeMap::<u64, Vec<(ClassHash, ContractClass)>>::new();
+        for class in dump.classes {
+            let (contract_class, compiled_class, casm) = class.contract_class.into_classes()?;
+            compiled_classes
+                .insert(starknet_api::core::ClassHash(class.class_hash), compiled_class);
+            casm_classes.extend(casm.map(|casm| (class.class_hash, casm)));
+            classes_per_block
+                .entry(class.block_number)
+                .or_default()
+                .push((class.class_hash, contract_class));
+        }
+
+        let mut class_storage = CommittedClassStorage::default();
+        for (block_number, classes) in classes_per_block {
+            for (class_hash, contract_class) in classes {
+                class_storage.insert(class_hash, contract_class);
+            }
+            class_storage.commit(block_number);
+        }
+        for (class_hash, casm) in casm_classes {
+            class_storage.insert_casm(class_hash, casm);
+        }
+        let rpc_contract_classes = Arc::new(RwLock::new(class_storage));
+
+        let get_compiled_class = |class_hash: starknet_api::core::ClassHash| {
+            compiled_classes.get(&class_hash).cloned().ok_or(Error::DeserializationError {
+                origin: format!("Class {:#x} is not present in the dump", class_hash.0),
+            })
+        };
+
+        // also supplies the state of lazily predeployed accounts, which is not part of the dump
+        let mut state = DictState::new(self.pending_state.state.state.defaulter().clone());
+        apply_state_diff(&mut state, dump.initial_state_diff.clone(), get_compiled_class)?;
+        let mut state_commitment = StateCommitment::from_state(&state)?;
+
+        let mut blocks = StarknetBlocks::new(self.blocks.starting_block_number);
+        for dumped_block in dump.blocks {
+            let (block, state_diff) = dumped_block.into_block()?;
+            let block_hash = block.block_hash();
+
+            if block.status != BlockStatus::Rejected {
+                apply_state_diff(&mut state, state_diff.clone(), get_compiled_class)?;
+                state_commitment.apply_diff(&state_diff, &state)?;
+                if self.config.state_archive == StateArchiveCapacity::Full {
+                    let archived_state = StarknetState::from_historic(
+                        state.clone(),
+                        rpc_contract_classes.clone(),
+                        state_commitment.clone(),
+                    );
+                    blocks.save_state_at(block_hash, archived_state);
+                }
+
+                blocks.num_to_hash.insert(block.block_number(), block_hash);
+                blocks.last_block_hash = Some(block_hash);
+            }
+
+            blocks.hash_to_state_diff.insert(block_hash, state_diff);
+            blocks.hash_to_block.insert(block_hash, block);
+        }
+
+        let (pending_block, pending_state_diff) = dump.pending_block.into_block()?;
+        blocks.pending_block = pending_block;
+        blocks.aborted_blocks = dump.aborted_blocks;
+
+        let latest_block_timestamp = blocks
+            .last_block_hash
+            .and_then(|hash| blocks.hash_to_block.get(&hash))
+            .map_or(BlockTimestamp::default(), |block| block.timestamp());
+
+        self.latest_state = StarknetState::from_historic(
+            state.clone(),
+            rpc_contract_classes.clone(),
+            state_commitment.clone(),
+        );
+        apply_state_diff(&mut state, pending_state_diff.clone(), get_compiled_class)?;
+        state_commitment.apply_diff(&pending_state_diff, &state)?;
+        self.pending_state =
+            StarknetState::from_historic(state, rpc_contract_classes.clone(), state_commitment);
+        self.pending_state_diff = pending_state_diff;
+        self.initial_state_diff = dump.initial_state_diff;
+        self.rpc_contract_classes = rpc_contract_classes;
+        self.blocks = blocks;
+        self.transactions = dump.transactions;
+        self.pending_block_timestamp_shift = dump.pending_block_timestamp_shift;
+        self.next_block_timestamp = dump.next_block_timestamp;
+        self.next_block_gas = dump.next_block_gas;
+        // the configured L1 node, if any, is kept
+        self.messaging.l2_to_l1_messages_hashes = dump.l2_to_l1_messages_hashes;
+        self.messaging.l2_to_l1_messages_to_flush = dump.l2_to_l1_messages_to_flush;
+        self.messaging.l1_to_l2_tx_hashes = dump.l1_to_l2_tx_hashes;
+        self.messaging.last_local_block = dump.last_local_block;
+
+        let mut block_info = self.block_context.block_info().clone();
+        block_info.block_number = self.blocks.pending_block.block_number();
+        block_info.block_timestamp = latest_block_timestamp;
+        block_info.gas_prices = GasPrices {
+            eth_l1_gas_price: dump.gas_prices.gas_price_wei,
+            strk_l1_gas_price: dump.gas_prices.gas_price_fri,
+            eth_l1_data_gas_price: dump.gas_prices.data_gas_price_wei,
+            strk_l1_data_gas_price: dump.gas_prices.data_gas_price_fri,
+        };
+        self.block_context = BlockContext::new(
+            block_info,
+            self.block_context.chain_info().clone(),
+            get_versioned_constants(),
+            custom_bouncer_config(),
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_api::block::BlockStatus;
+    use starknet_rs_core::types::{BlockId, BlockTag, Felt};
+
+    use super::StateDump;
+    use crate::account::FeeToken;
+    use crate::starknet::starknet_config::{StarknetConfig, StateArchiveCapacity};
+    use crate::starknet::Starknet;
+    use crate::traits::Accounted;
+
+    fn dump_and_load(starknet: &Starknet, config: &StarknetConfig) -> Starknet {
+        let serialized_dump =
+            serde_json::to_string(&starknet.create_state_dump().unwrap()).unwrap();
+        let dump: StateDump = serde_json::from_str(&serialized_dump).unwrap();
+
+        let mut loaded_starknet = Starknet::new(config).unwrap();
+        loaded_starknet.load_state_dump(dump).unwrap();
+        loaded_starknet
+    }
+
+    #[test]
+    fn loaded_state_dump_restores_blocks_and_state() {
+        let config =
+            StarknetConfig { state_archive: StateArchiveCapacity::Full, ..Default::default() };
+        let mut starknet = Starknet::new(&config).unwrap();
+        let genesis_block_hash = starknet.get_latest_block().unwrap().block_hash();
+        starknet.create_block().unwrap();
+        starknet.accept_on_l1(BlockId::Tag(BlockTag::Latest)).unwrap();
+        starknet.create_block().unwrap();
+        starknet.messaging.l2_to_l1_messages_hashes.insert("0x1".into(), 1);
+        starknet.messaging.l1_to_l2_tx_hashes.insert("0x2".into(), vec![Felt::THREE]);
+        starknet.messaging.last_local_block = 1;
+
+        let mut loaded_starknet = dump_and_load(&starknet, &config);
+
+        for block_id in [
+            BlockId::Tag(BlockTag::Latest),
+            BlockId::Tag(BlockTag::Pending),
+            BlockId::Hash(genesis_block_hash),
+        ] {
+            let block = starknet.get_block(&block_id).unwrap();
+            let loaded_block = loaded_starknet.get_block(&block_id).unwrap();
+            assert_eq!(loaded_block.block_hash(), block.block_hash());
+            assert_eq!(loaded_block.block_number(), block.block_number());
+            assert_eq!(loaded_block.status, block.status);
+        }
+        assert_eq!(
+            loaded_starknet.get_block(&BlockId::Number(1)).unwrap().status,
+            BlockStatus::AcceptedOnL1
+        );
+
+        let account = starknet.predeployed_accounts.get_accounts()[0].clone();
+        assert_eq!(
+            account.get_balance(&mut loaded_starknet.pending_state, FeeToken::ETH).unwrap(),
+            account.get_balance(&mut starknet.pending_state, FeeToken::ETH).unwrap()
+        );
+
+        // classes are restored and the archived states can be queried
+        let block_id = BlockId::Hash(genesis_block_hash);
+        let class_hash = loaded_starknet.get_class_hash_at(&block_id, account.account_address);
+        assert_eq!(class_hash.unwrap(), account.class_hash);
+        assert_eq!(
+            loaded_starknet.get_class(&block_id, account.class_hash).unwrap(),
+            starknet.get_class(&block_id, account.class_hash).unwrap()
+        );
+        assert_eq!(
+            loaded_starknet.get_compiled_casm(account.class_hash).unwrap().class_hash().unwrap(),
+            starknet.get_compiled_casm(account.class_hash).unwrap().class_hash().unwrap()
+        );
+
+        // messages remain consumable and their statuses queryable
+        assert_eq!(
+            loaded_starknet.messaging.l2_to_l1_messages_hashes,
+            starknet.messaging.l2_to_l1_messages_hashes
+        );
+        assert_eq!(
+            loaded_starknet.messaging.l1_to_l2_tx_hashes,
+            starknet.messaging.l1_to_l2_tx_hashes
+        );
+        assert_eq!(loaded_starknet.messaging.last_local_block, 1);
+
+        // the loaded Starknet can still be used
+        loaded_starknet.create_block().unwrap();
+        assert_eq!(
+            loaded_starknet.get_latest_block().unwrap().block_number(),
+            starknet.get_latest_block().unwrap().block_number().next().unwrap()
+        );
+    }
+}
@@ -1,27 +1,50 @@
+use starknet_api::block::BlockStatus;
 use starknet_rs_core::types::BlockId;
 
 use super::Starknet;
 use crate::error::DevnetResult;
 use crate::state::state_update::StateUpdate;
+use crate::traits::HashIdentified;
 
 pub fn state_update_by_block_id(
     starknet: &Starknet,
     block_id: &BlockId,
 ) -> DevnetResult<StateUpdate> {
     let block = starknet.blocks.get_by_block_id(block_id).ok_or(crate::error::Error::NoBlock)?;
-    let state_diff =
-        starknet.blocks.hash_to_state_diff.get(&block.block_hash()).cloned().unwrap_or_default();
+    // the pending block is linked to its parent only once it is added to the collection of blocks,
+    // and so is its state diff
+    let (parent_hash, state_diff) = match block.status {
+        BlockStatus::Pending => {
+            (starknet.blocks.last_block_hash, starknet.pending_state_diff.clone())
+        }
+        _ => (
+            Some(block.parent_hash()),
+            starknet
+                .blocks
+                .hash_to_state_diff
+                .get(&block.block_hash())
+                .cloned()
+                .unwrap_or_default(),
+        ),
+    };
+    let old_root = parent_hash
+        .and_then(|parent_hash| starknet.blocks.get_by_hash(parent_hash))
+        .map(|parent| parent.new_root())
+        .unwrap_or_default();
 
-    Ok(StateUpdate::new(block.block_hash(), state_diff))
+    Ok(StateUpdate::new(block.block_hash(), old_root, block.new_root(), state_diff))
 }
 
 #[cfg(test)]
 mod tests {
 
+    use blockifier::state::state_api::State;
     use starknet_api::transaction::Fee;
-    use starknet_rs_core::types::{Felt, TransactionExecutionStatus, TransactionFinalityStatus};
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, Felt, TransactionExecutionStatus, TransactionFinalityStatus,
+    };
     use starknet_types::contract_class::ContractClass;
-    use starknet_types::rpc::state::ThinStateDiff;
+    use starknet_types::rpc::state::{StateUpdateResult, ThinStateDiff};
     use starknet_types::rpc::transactions::broadcasted_declare_transaction_v2::BroadcastedDeclareTransactionV2;
     use starknet_types::traits::HashProducer;
 
@@ -29,7 +52,7 @@ mod tests {
     use crate::state::state_diff::StateDiff;
     use crate::traits::HashIdentifiedMut;
     use crate::utils::calculate_casm_hash;
-    use crate::utils::test_utils::dummy_cairo_1_contract_class;
+    use crate::utils::test_utils::{dummy_cairo_1_contract_class, dummy_felt};
 
     #[test]
     /// This test checks that the state update is correct after a declare transaction v2.
@@ -88,4 +111,39 @@ mod tests {
             (expected_state_diff.deprecated_declared_classes, expected_state_diff.declared_classes);
         assert_eq!(class_diff, expected_class_diff);
     }
+
+    #[test]
+    fn state_update_roots_are_linked_with_parent_block() {
+        let (mut starknet, acc) = setup_starknet_with_no_signature_check_account(1e18 as u128);
+        let parent_root = starknet.get_latest_block().unwrap().new_root();
+        assert_ne!(parent_root, Felt::ZERO);
+
+        starknet
+            .pending_state
+            .set_storage_at(
+                acc.account_address.try_into().unwrap(),
+                dummy_felt().try_into().unwrap(),
+                Felt::ONE,
+            )
+            .unwrap();
+        starknet.commit_diff().unwrap();
+        starknet.generate_new_block_and_state().unwrap();
+
+        let new_root = match starknet.block_state_update(&BlockId::Tag(BlockTag::Latest)) {
+            Ok(StateUpdateResult::StateUpdate(state_update)) => {
+                assert_eq!(state_update.old_root, parent_root);
+                assert_ne!(state_update.new_root, parent_root);
+                state_update.new_root
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        };
+        assert_eq!(starknet.get_latest_block().unwrap().new_root(), new_root);
+
+        match starknet.block_state_update(&BlockId::Tag(BlockTag::Pending)) {
+            Ok(StateUpdateResult::PendingStateUpdate(state_update)) => {
+                assert_eq!(state_update.old_root, new_root);
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
 }
@@ -1,21 +1,29 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use blockifier::state::cached_state::CachedState;
+use blockifier::execution::contract_class::{
+    ContractClass as CompiledContractClass, ContractClassV1,
+};
+use blockifier::state::cached_state::{CachedState, StateMaps};
 use blockifier::state::state_api::{State, StateReader};
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use parking_lot::RwLock;
 use starknet_api::core::CompiledClassHash;
 use starknet_rs_core::types::Felt;
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_class::ContractClass;
-use starknet_types::felt::ClassHash;
+use starknet_types::felt::{BlockHash, ClassHash, Nonce};
+use starknet_types::rpc::state::{ContractStorageKeys, StorageProof};
 
+use self::state_commitment::StateCommitment;
 use self::state_diff::StateDiff;
 use self::state_readers::DictState;
 use crate::error::{DevnetResult, Error};
 use crate::starknet::defaulter::StarknetDefaulter;
-use crate::utils::calculate_casm_hash;
+use crate::utils::{calculate_casm_hash, compile_sierra_contract_to_casm};
 
+pub(crate) mod patricia_trie;
+pub(crate) mod state_commitment;
 pub(crate) mod state_diff;
 pub(crate) mod state_readers;
 pub mod state_update;
@@ -57,6 +65,9 @@ pub trait CustomState {
         contract_address: ContractAddress,
         class_hash: ClassHash,
     ) -> DevnetResult<()>;
+
+    /// Set the nonce of the contract at `contract_address`, regardless of its current nonce
+    fn set_nonce(&mut self, contract_address: ContractAddress, nonce: Nonce) -> DevnetResult<()>;
 }
 
 #[derive(Default, Clone)]
@@ -70,6 +81,8 @@ pub struct CommittedClassStorage {
     committed: HashMap<ClassHash, (ContractClass, u64)>,
     /// Remembers all classes committed at a block
     block_number_to_classes: HashMap<u64, Vec<ClassHash>>,
+    /// CASM of Cairo 1 classes, kept from their declaration so that it isn't recompiled on request
+    casm_classes: HashMap<ClassHash, CasmContractClass>,
 }
 
 impl CommittedClassStorage {
@@ -139,10 +152,30 @@ impl CommittedClassStorage {
         }
     }
 
+    /// Returns the committed classes, together with the numbers of the blocks they were committed
+    /// at.
+    pub(crate) fn committed_classes(
+        &self,
+    ) -> impl Iterator<Item = (&ClassHash, &ContractClass, u64)> {
+        self.committed
+            .iter()
+            .map(|(class_hash, (class, block_number))| (class_hash, class, *block_number))
+    }
+
     /// Removes all staged classes.
     pub fn empty_staging(&mut self) {
         self.staging = Default::default();
     }
+
+    /// Stores the CASM of a Cairo 1 class. Since it is determined by the class, it is not removed
+    /// together with the class.
+    pub fn insert_casm(&mut self, class_hash: ClassHash, casm: CasmContractClass) {
+        self.casm_classes.insert(class_hash, casm);
+    }
+
+    pub fn get_casm(&self, class_hash: &ClassHash) -> Option<&CasmContractClass> {
+        self.casm_classes.get(class_hash)
+    }
 }
 
 pub struct StarknetState {
@@ -154,6 +187,11 @@ pub struct StarknetState {
     /// - initially `None`
     /// - indicates the state hasn't yet been cloned for old-state preservation purpose
     historic_state: Option<DictState>,
+    /// Tries used for calculating the global state root; updated with the diff of each commit
+    state_commitment: StateCommitment,
+    /// Predeclaring and predeploying write to the underlying state, bypassing the diff, so the
+    /// tries have to be recreated from the whole state on the next commit
+    has_direct_writes: bool,
 }
 
 impl Default for StarknetState {
@@ -162,6 +200,8 @@ impl Default for StarknetState {
             state: CachedState::new(Default::default()),
             rpc_contract_classes: Default::default(),
             historic_state: Default::default(),
+            state_commitment: Default::default(),
+            has_direct_writes: false,
         }
     }
 }
@@ -175,6 +215,8 @@ impl StarknetState {
             state: CachedState::new(DictState::new(defaulter)),
             rpc_contract_classes,
             historic_state: Default::default(),
+            state_commitment: Default::default(),
+            has_direct_writes: false,
         }
     }
 
@@ -187,12 +229,41 @@ impl StarknetState {
         let new_classes = self.rpc_contract_classes.write().commit(block_number);
 
         let diff = StateDiff::generate(&mut self.state, new_classes)?;
-        let new_historic = self.expand_historic(diff.clone())?;
-        self.state = CachedState::new(new_historic.clone());
+        let new_historic = self.expand_historic(diff.clone())?.clone();
+        if self.has_direct_writes {
+            self.state_commitment = StateCommitment::from_state(&new_historic)?;
+            self.has_direct_writes = false;
+        } else {
+            self.state_commitment.apply_diff(&diff, &new_historic)?;
+        }
+        self.state = CachedState::new(new_historic);
 
         Ok(diff)
     }
 
+    /// Calculates the global state root of the committed (historic) state. Only the local state is
+    /// taken into account, so in forking mode the origin state is not committed to.
+    pub(crate) fn state_root(&mut self) -> DevnetResult<Felt> {
+        self.state_commitment.root()
+    }
+
+    /// Generates a storage proof against the committed (historic) state. Check
+    /// `StateCommitment::storage_proof` for more info.
+    pub(crate) fn storage_proof(
+        &mut self,
+        block_hash: BlockHash,
+        class_hashes: &[ClassHash],
+        contract_addresses: &[ContractAddress],
+        contracts_storage_keys: &[ContractStorageKeys],
+    ) -> DevnetResult<StorageProof> {
+        self.state_commitment.storage_proof(
+            block_hash,
+            class_hashes,
+            contract_addresses,
+            contracts_storage_keys,
+        )
+    }
+
     pub fn assert_contract_deployed(
         &mut self,
         contract_address: ContractAddress,
@@ -206,49 +277,92 @@ impl StarknetState {
     /// Expands the internal historic state copy and returns a reference to it
     fn expand_historic(&mut self, state_diff: StateDiff) -> DevnetResult<&DictState> {
         let mut historic_state = self.state.state.clone();
+        apply_state_diff(&mut historic_state, state_diff, |class_hash| {
+            Ok(self.get_compiled_contract_class(class_hash)?)
+        })?;
 
-        for (address, class_hash) in state_diff.address_to_class_hash {
-            historic_state.set_class_hash_at(
-                address.try_into()?,
-                starknet_api::core::ClassHash(class_hash),
-            )?;
-        }
-        for (class_hash, casm_hash) in state_diff.class_hash_to_compiled_class_hash {
-            historic_state.set_compiled_class_hash(
-                starknet_api::core::ClassHash(class_hash),
-                starknet_api::core::CompiledClassHash(casm_hash),
-            )?;
-        }
-        for (address, _nonce) in state_diff.address_to_nonce {
-            // assuming that historic_state.get_nonce(address) == _nonce - 1
-            historic_state.increment_nonce(address.try_into()?)?;
-        }
-        for (address, storage_updates) in state_diff.storage_updates {
-            let core_address = address.try_into()?;
-            for (key, value) in storage_updates {
-                historic_state.set_storage_at(core_address, key.try_into()?, value)?;
-            }
-        }
-        for class_hash in state_diff.cairo_0_declared_contracts {
-            let class_hash = starknet_api::core::ClassHash(class_hash);
-            let compiled_class = self.get_compiled_contract_class(class_hash)?;
-            historic_state.set_contract_class(class_hash, compiled_class)?;
-        }
-        for class_hash in state_diff.declared_contracts {
-            let class_hash = starknet_api::core::ClassHash(class_hash);
-            let compiled_class = self.get_compiled_contract_class(class_hash)?;
-            historic_state.set_contract_class(class_hash, compiled_class)?;
-        }
         self.historic_state = Some(historic_state);
         Ok(self.historic_state.as_ref().unwrap())
     }
 
+    /// Creates a state whose historic (committed) state is `historic_state`, as if it were
+    /// obtained via `clone_historic`. `state_commitment` has to be in sync with `historic_state`.
+    pub(crate) fn from_historic(
+        historic_state: DictState,
+        rpc_contract_classes: Arc<RwLock<CommittedClassStorage>>,
+        state_commitment: StateCommitment,
+    ) -> Self {
+        Self {
+            state: CachedState::new(historic_state.clone()),
+            rpc_contract_classes,
+            historic_state: Some(historic_state),
+            state_commitment,
+            has_direct_writes: false,
+        }
+    }
+
     pub fn clone_historic(&self) -> Self {
         let historic_state = self.historic_state.as_ref().unwrap().clone();
         Self {
             state: CachedState::new(historic_state),
             rpc_contract_classes: self.rpc_contract_classes.clone(),
             historic_state: Some(self.historic_state.as_ref().unwrap().clone()),
+            state_commitment: self.state_commitment.clone(),
+            has_direct_writes: false,
+        }
+    }
+}
+
+/// Writes the changes of `state_diff` to `state`. The compiled classes of the declared classes are
+/// obtained via `get_compiled_class`.
+pub(crate) fn apply_state_diff<F>(
+    state: &mut DictState,
+    state_diff: StateDiff,
+    get_compiled_class: F,
+) -> DevnetResult<()>
+where
+    F: Fn(starknet_api::core::ClassHash) -> DevnetResult<CompiledContractClass>,
+{
+    for (address, class_hash) in state_diff.address_to_class_hash {
+        state.set_class_hash_at(address.try_into()?, starknet_api::core::ClassHash(class_hash))?;
+    }
+    for (class_hash, casm_hash) in state_diff.class_hash_to_compiled_class_hash {
+        state.set_compiled_class_hash(
+            starknet_api::core::ClassHash(class_hash),
+            starknet_api::core::CompiledClassHash(casm_hash),
+        )?;
+    }
+    for (address, nonce) in state_diff.address_to_nonce {
+        state.set_nonce(address.try_into()?, starknet_api::core::Nonce(nonce))?;
+    }
+    for (address, storage_updates) in state_diff.storage_updates {
+        let core_address = address.try_into()?;
+        for (key, value) in storage_updates {
+            state.set_storage_at(core_address, key.try_into()?, value)?;
+        }
+    }
+    for class_hash in
+        state_diff.cairo_0_declared_contracts.into_iter().chain(state_diff.declared_contracts)
+    {
+        let class_hash = starknet_api::core::ClassHash(class_hash);
+        state.set_contract_class(class_hash, get_compiled_class(class_hash)?)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the class in the form used for execution and, if it's a Cairo 1 class, the CASM it was
+/// created from.
+fn compile_contract_class(
+    contract_class: &ContractClass,
+) -> DevnetResult<(CompiledContractClass, Option<CasmContractClass>)> {
+    match contract_class {
+        ContractClass::Cairo0(_) => Ok((contract_class.clone().try_into()?, None)),
+        ContractClass::Cairo1(sierra_contract_class) => {
+            let casm = compile_sierra_contract_to_casm(sierra_contract_class)?;
+            let compiled_class = ContractClassV1::try_from(casm.clone())
+                .map_err(|_| Error::TypesError(starknet_types::error::Error::ProgramError))?;
+            Ok((CompiledContractClass::V1(compiled_class), Some(casm)))
         }
     }
 }
@@ -364,7 +478,8 @@ impl CustomStateReader for StarknetState {
         contract_address: ContractAddress,
     ) -> DevnetResult<bool> {
         let api_address = contract_address.try_into()?;
-        Ok(self.state.state.address_to_class_hash.contains_key(&api_address))
+        Ok(self.state.state.address_to_class_hash.contains_key(&api_address)
+            || self.state.state.defaulter().is_lazily_predeployed(api_address))
     }
 }
 
@@ -375,28 +490,24 @@ impl CustomState for StarknetState {
         class_hash: ClassHash,
         contract_class: ContractClass,
     ) -> DevnetResult<()> {
-        let compiled_class = contract_class.clone().try_into()?;
+        let (compiled_class, casm) = compile_contract_class(&contract_class)?;
         let class_hash = starknet_api::core::ClassHash(class_hash);
 
-        if let ContractClass::Cairo1(cairo_lang_contract_class) = &contract_class {
-            let casm_json = usc::compile_contract(
-                serde_json::to_value(cairo_lang_contract_class)
-                    .map_err(|err| Error::SerializationError { origin: err.to_string() })?,
-            )
-            .map_err(|err| {
-                Error::TypesError(starknet_types::error::Error::SierraCompilationError {
-                    reason: err.to_string(),
-                })
-            })?;
-
+        if let Some(casm) = &casm {
+            let casm_json = serde_json::to_value(casm)
+                .map_err(|err| Error::SerializationError { origin: err.to_string() })?;
             let casm_hash = starknet_api::core::CompiledClassHash(calculate_casm_hash(casm_json)?);
 
             self.state.state.set_compiled_class_hash(class_hash, casm_hash)?;
         };
+        self.has_direct_writes = true;
 
         self.state.state.set_contract_class(class_hash, compiled_class)?;
         let mut class_storage = self.rpc_contract_classes.write();
         class_storage.insert(*class_hash, contract_class);
+        if let Some(casm) = casm {
+            class_storage.insert_casm(*class_hash, casm);
+        }
         Ok(())
     }
 
@@ -406,7 +517,7 @@ impl CustomState for StarknetState {
         casm_hash: Option<starknet_types::felt::CompiledClassHash>,
         contract_class: ContractClass,
     ) -> DevnetResult<()> {
-        let compiled_class = contract_class.clone().try_into()?;
+        let (compiled_class, casm) = compile_contract_class(&contract_class)?;
 
         let class_hash = starknet_api::core::ClassHash(class_hash);
         if let Some(casm_hash) = casm_hash {
@@ -419,6 +530,9 @@ impl CustomState for StarknetState {
         self.set_contract_class(class_hash, compiled_class)?;
         let mut class_storage = self.rpc_contract_classes.write();
         class_storage.insert(*class_hash, contract_class);
+        if let Some(casm) = casm {
+            class_storage.insert_casm(*class_hash, casm);
+        }
         Ok(())
     }
 
@@ -427,6 +541,7 @@ impl CustomState for StarknetState {
         contract_address: ContractAddress,
         class_hash: ClassHash,
     ) -> DevnetResult<()> {
+        self.has_direct_writes = true;
         self.state
             .state
             .set_class_hash_at(
@@ -435,6 +550,18 @@ impl CustomState for StarknetState {
             )
             .map_err(|e| e.into())
     }
+
+    /// writes to the cache, so that the change is a part of the state diff; the current nonce is
+    /// read first, because the cache expects an initial value for each written nonce
+    fn set_nonce(&mut self, contract_address: ContractAddress, nonce: Nonce) -> DevnetResult<()> {
+        let contract_address = contract_address.try_into()?;
+        self.state.get_nonce_at(contract_address)?;
+
+        let nonces = HashMap::from([(contract_address, starknet_api::core::Nonce(nonce))]);
+        self.state.update_cache(&StateMaps { nonces, ..Default::default() }, HashMap::new());
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -567,6 +694,20 @@ mod tests {
         assert_eq!(nonce, Felt::ONE)
     }
 
+    #[test]
+    fn set_nonce_is_part_of_state_diff() {
+        let mut state = StarknetState::default();
+        let address = dummy_contract_address();
+        state.predeploy_contract(address, dummy_felt()).unwrap();
+
+        state.set_nonce(address, Felt::from(5)).unwrap();
+
+        let nonce = *state.get_nonce_at(address.try_into().unwrap()).unwrap();
+        assert_eq!(nonce, Felt::from(5));
+        let diff = state.commit_diff(1).unwrap();
+        assert_eq!(diff.address_to_nonce.get(&address), Some(&Felt::from(5)));
+    }
+
     #[test]
     fn read_from_storage_returns_correct_result() {
         let (mut state, _) = setup();
@@ -0,0 +1,346 @@
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+
+use indexmap::IndexMap;
+use starknet_rs_core::types::Felt;
+use starknet_types::rpc::state::{BinaryNode, EdgeNode, MerkleNode, NodeHashToNode};
+use starknet_types_core::hash::StarkHash;
+
+/// Height of all Starknet commitment tries
+pub(crate) const TRIE_HEIGHT: usize = 251;
+
+/// Big-endian representation of a trie key; only the lowest `TRIE_HEIGHT` bits are used
+type TrieKey = [u8; 32];
+
+/// Number of unused leading bits in the big-endian representation of a key
+const UNUSED_BITS: usize = 256 - TRIE_HEIGHT;
+
+/// Binary Merkle-Patricia trie of height 251, as used by Starknet for storage, contract and class
+/// commitments. Only non-zero leaves are stored. Hashes of subtrees are memoized and invalidated
+/// along the path of a modified leaf, so recomputing the root after a few modifications is cheap.
+/// Subtrees are looked up as key ranges of the sorted leaves, so the leaves are never copied.
+pub(crate) struct PatriciaTrie<H: StarkHash> {
+    leaves: BTreeMap<TrieKey, Felt>,
+    /// (depth, key prefix of length depth) -> hash of the node at that position
+    node_hashes: HashMap<(usize, TrieKey), Felt>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: StarkHash> Default for PatriciaTrie<H> {
+    fn default() -> Self {
+        Self { leaves: Default::default(), node_hashes: Default::default(), _hasher: PhantomData }
+    }
+}
+
+// implemented manually, since the hasher types don't implement Clone
+impl<H: StarkHash> Clone for PatriciaTrie<H> {
+    fn clone(&self) -> Self {
+        Self {
+            leaves: self.leaves.clone(),
+            node_hashes: self.node_hashes.clone(),
+            _hasher: PhantomData,
+        }
+    }
+}
+
+impl<H: StarkHash> PatriciaTrie<H> {
+    /// Sets the leaf at `key` to `value`. Setting a leaf to zero removes it from the trie.
+    pub(crate) fn set(&mut self, key: Felt, value: Felt) {
+        let key = key.to_bytes_be();
+
+        let changed = if value == Felt::ZERO {
+            self.leaves.remove(&key).is_some()
+        } else {
+            self.leaves.insert(key, value) != Some(value)
+        };
+
+        if changed {
+            for depth in 0..=TRIE_HEIGHT {
+                self.node_hashes.remove(&(depth, key_prefix(&key, depth)));
+            }
+        }
+    }
+
+    pub(crate) fn root(&mut self) -> Felt {
+        self.node_hash(0, &[0; 32])
+    }
+
+    /// Returns the nodes on the paths from the root towards each of `keys`. A path towards a key
+    /// that is not in the trie ends where it diverges from the existing keys, so the returned
+    /// nodes also prove non-membership. Nodes shared by several paths are returned only once.
+    pub(crate) fn proof_nodes(&mut self, keys: &[Felt]) -> Vec<NodeHashToNode> {
+        let mut nodes = IndexMap::new();
+        for key in keys {
+            self.collect_proof_nodes(0, &key.to_bytes_be(), &mut nodes);
+        }
+
+        nodes.into_iter().map(|(node_hash, node)| NodeHashToNode { node_hash, node }).collect()
+    }
+
+    fn collect_proof_nodes(
+        &mut self,
+        depth: usize,
+        key: &TrieKey,
+        nodes: &mut IndexMap<Felt, MerkleNode>,
+    ) {
+        if depth == TRIE_HEIGHT {
+            return;
+        }
+        let Some(subtree) = self.subtree(depth, key) else {
+            return;
+        };
+
+        let node_hash = self.node_hash(depth, key);
+        match subtree {
+            Subtree::Edge { length, leaf_key } => {
+                let child = self.node_hash(depth + length, &leaf_key);
+                let path = key_path(&leaf_key, depth, length);
+                nodes.insert(node_hash, MerkleNode::EdgeNode(EdgeNode { path, length, child }));
+
+                if key_path(key, depth, length) == path {
+                    self.collect_proof_nodes(depth + length, key, nodes);
+                }
+            }
+            Subtree::Binary { left_key, right_key } => {
+                let left = self.node_hash(depth + 1, &left_key);
+                let right = self.node_hash(depth + 1, &right_key);
+                nodes.insert(node_hash, MerkleNode::BinaryNode(BinaryNode { left, right }));
+
+                self.collect_proof_nodes(depth + 1, key, nodes);
+            }
+        }
+    }
+
+    /// Hash of the node at `depth` on the path towards `key`
+    fn node_hash(&mut self, depth: usize, key: &TrieKey) -> Felt {
+        if depth == TRIE_HEIGHT {
+            return self.leaves.get(key).copied().unwrap_or_default();
+        }
+
+        let memo_key = (depth, key_prefix(key, depth));
+        if let Some(hash) = self.node_hashes.get(&memo_key) {
+            return *hash;
+        }
+
+        let hash = match self.subtree(depth, key) {
+            None => return Felt::ZERO,
+            Some(Subtree::Edge { length, leaf_key }) => {
+                let child = self.node_hash(depth + length, &leaf_key);
+                H::hash(&child, &key_path(&leaf_key, depth, length)) + Felt::from(length)
+            }
+            Some(Subtree::Binary { left_key, right_key }) => {
+                let left = self.node_hash(depth + 1, &left_key);
+                let right = self.node_hash(depth + 1, &right_key);
+                H::hash(&left, &right)
+            }
+        };
+
+        self.node_hashes.insert(memo_key, hash);
+        hash
+    }
+
+    /// Determines the type of the node at `depth` on the path towards `key`, based on the first
+    /// and the last leaf in its subtree. Returns `None` if the subtree is empty.
+    fn subtree(&self, depth: usize, key: &TrieKey) -> Option<Subtree> {
+        let mut leaves_in_subtree =
+            self.leaves.range(key_prefix(key, depth)..=key_prefix_end(key, depth));
+        let (first_key, _) = leaves_in_subtree.next()?;
+        let last_key = leaves_in_subtree.next_back().map_or(first_key, |(key, _)| key);
+
+        let length = (depth..TRIE_HEIGHT)
+            .take_while(|i| key_bit(first_key, *i) == key_bit(last_key, *i))
+            .count();
+
+        Some(if length > 0 {
+            Subtree::Edge { length, leaf_key: *first_key }
+        } else {
+            Subtree::Binary { left_key: *first_key, right_key: *last_key }
+        })
+    }
+}
+
+enum Subtree {
+    /// All leaves share the next `length` bits with `leaf_key`
+    Edge { length: usize, leaf_key: TrieKey },
+    /// Leaves diverge at the current bit; the keys are leaves of the left and the right child
+    Binary { left_key: TrieKey, right_key: TrieKey },
+}
+
+/// Returns the bit of `key` at `index`, where index 0 is the most significant of the used bits.
+fn key_bit(key: &TrieKey, index: usize) -> bool {
+    let absolute_index = index + UNUSED_BITS;
+    key[absolute_index / 8] & (0x80 >> (absolute_index % 8)) != 0
+}
+
+/// Returns `key` with all bits after the first `depth` set to `bit`.
+fn with_tail_bits(key: &TrieKey, depth: usize, bit: bool) -> TrieKey {
+    let mut result = *key;
+    let absolute_index = depth + UNUSED_BITS;
+    if absolute_index == 256 {
+        return result;
+    }
+
+    let (byte_index, tail_mask) = (absolute_index / 8, 0xff >> (absolute_index % 8));
+    let tail_byte = if bit { 0xff } else { 0x00 };
+    result[byte_index] = (result[byte_index] & !tail_mask) | (tail_byte & tail_mask);
+    result[byte_index + 1..].fill(tail_byte);
+    result
+}
+
+/// Returns `key` with all bits after the first `depth` zeroed out, i.e. the smallest key sharing
+/// its first `depth` bits.
+fn key_prefix(key: &TrieKey, depth: usize) -> TrieKey {
+    with_tail_bits(key, depth, false)
+}
+
+/// Returns the largest key sharing the first `depth` bits with `key`.
+fn key_prefix_end(key: &TrieKey, depth: usize) -> TrieKey {
+    let mut end = with_tail_bits(key, depth, true);
+    // the unused bits are never set
+    end[0] &= 0xff >> UNUSED_BITS;
+    end
+}
+
+/// Interprets the `length` bits of `key` starting at `depth` as a number.
+fn key_path(key: &TrieKey, depth: usize, length: usize) -> Felt {
+    (depth..depth + length).fold(Felt::ZERO, |path, index| {
+        let bit = if key_bit(key, index) { Felt::ONE } else { Felt::ZERO };
+        path * Felt::TWO + bit
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_rs_core::types::Felt;
+    use starknet_types::rpc::state::{BinaryNode, EdgeNode, MerkleNode, NodeHashToNode};
+    use starknet_types_core::hash::{Pedersen, StarkHash};
+
+    use super::{PatriciaTrie, TRIE_HEIGHT};
+
+    #[test]
+    fn empty_trie_has_zero_root() {
+        let mut trie = PatriciaTrie::<Pedersen>::default();
+        assert_eq!(trie.root(), Felt::ZERO);
+    }
+
+    #[test]
+    fn root_of_single_leaf_is_edge_to_leaf() {
+        let mut trie = PatriciaTrie::<Pedersen>::default();
+        let key = Felt::from(0b101_u32);
+        let value = Felt::from(42_u32);
+        trie.set(key, value);
+
+        let expected = Pedersen::hash(&value, &key) + Felt::from(TRIE_HEIGHT);
+        assert_eq!(trie.root(), expected);
+    }
+
+    #[test]
+    fn root_of_two_leaves_diverging_at_last_bit() {
+        let mut trie = PatriciaTrie::<Pedersen>::default();
+        let (left_value, right_value) = (Felt::from(10_u32), Felt::from(20_u32));
+        trie.set(Felt::from(0b100_u32), left_value);
+        trie.set(Felt::from(0b101_u32), right_value);
+
+        let binary = Pedersen::hash(&left_value, &right_value);
+        let expected = Pedersen::hash(&binary, &Felt::from(0b10_u32)) + Felt::from(TRIE_HEIGHT - 1);
+        assert_eq!(trie.root(), expected);
+    }
+
+    #[test]
+    fn setting_zero_removes_leaf() {
+        let mut trie = PatriciaTrie::<Pedersen>::default();
+        trie.set(Felt::ONE, Felt::from(7_u32));
+        let root_with_one_leaf = trie.root();
+
+        trie.set(Felt::TWO, Felt::from(8_u32));
+        assert_ne!(trie.root(), root_with_one_leaf);
+
+        trie.set(Felt::TWO, Felt::ZERO);
+        assert_eq!(trie.root(), root_with_one_leaf);
+    }
+
+    /// Recomputes the root from the leaf at `key` upwards, using the provided proof nodes
+    fn verify_proof(root: Felt, key: Felt, value: Felt, proof: &[NodeHashToNode]) -> bool {
+        let key_bits = &key.to_bits_be()[256 - TRIE_HEIGHT..];
+        let mut expected_hash = root;
+        let mut depth = 0;
+
+        while depth < TRIE_HEIGHT {
+            let Some(NodeHashToNode { node, .. }) =
+                proof.iter().find(|node| node.node_hash == expected_hash)
+            else {
+                return false;
+            };
+
+            match node {
+                MerkleNode::BinaryNode(BinaryNode { left, right }) => {
+                    if Pedersen::hash(left, right) != expected_hash {
+                        return false;
+                    }
+                    expected_hash = if key_bits[depth] { *right } else { *left };
+                    depth += 1;
+                }
+                MerkleNode::EdgeNode(EdgeNode { path, length, child }) => {
+                    if Pedersen::hash(child, path) + Felt::from(*length) != expected_hash {
+                        return false;
+                    }
+                    let key_path = key_bits[depth..depth + length]
+                        .iter()
+                        .fold(Felt::ZERO, |acc, bit| acc * Felt::TWO + Felt::from(*bit as u8));
+                    if key_path != *path {
+                        // proof of non-membership
+                        return value == Felt::ZERO;
+                    }
+                    expected_hash = *child;
+                    depth += length;
+                }
+            }
+        }
+
+        expected_hash == value
+    }
+
+    #[test]
+    fn proof_nodes_verify_membership_and_non_membership() {
+        let mut trie = PatriciaTrie::<Pedersen>::default();
+        for i in 1..=10_u32 {
+            trie.set(Felt::from(i * 31), Felt::from(i));
+        }
+        let root = trie.root();
+
+        let present_key = Felt::from(5 * 31_u32);
+        let absent_key = Felt::from(5 * 31_u32 + 1);
+        let proof = trie.proof_nodes(&[present_key, absent_key]);
+
+        assert!(verify_proof(root, present_key, Felt::from(5_u32), &proof));
+        assert!(!verify_proof(root, present_key, Felt::from(6_u32), &proof));
+        assert!(verify_proof(root, absent_key, Felt::ZERO, &proof));
+    }
+
+    #[test]
+    fn proof_nodes_of_empty_trie_are_empty() {
+        let mut trie = PatriciaTrie::<Pedersen>::default();
+        assert!(trie.proof_nodes(&[Felt::ONE]).is_empty());
+    }
+
+    #[test]
+    fn memoized_root_equals_freshly_computed_root() {
+        let mut trie = PatriciaTrie::<Pedersen>::default();
+        for i in 0..20_u32 {
+            trie.set(Felt::from(i * 7919), Felt::from(i + 1));
+        }
+        trie.root();
+
+        trie.set(Felt::from(7919 * 3), Felt::from(1000_u32));
+        trie.set(Felt::from(123456789_u32), Felt::from(1001_u32));
+
+        let mut fresh = PatriciaTrie::<Pedersen>::default();
+        for i in 0..20_u32 {
+            fresh.set(Felt::from(i * 7919), Felt::from(i + 1));
+        }
+        fresh.set(Felt::from(7919 * 3), Felt::from(1000_u32));
+        fresh.set(Felt::from(123456789_u32), Felt::from(1001_u32));
+
+        assert_eq!(trie.root(), fresh.root());
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::{HashMap, HashSet};
+
+use starknet_api::core::ContractAddress;
+use starknet_rs_core::types::Felt;
+use starknet_rs_core::utils::cairo_short_string_to_felt;
+use starknet_types::felt::{BlockHash, ClassHash};
+use starknet_types::rpc::state::{
+    ContractLeafData, ContractStorageKeys, ContractsProof, GlobalRoots, StorageProof,
+};
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+
+use super::patricia_trie::PatriciaTrie;
+use super::state_diff::StateDiff;
+use super::state_readers::DictState;
+use crate::error::{DevnetResult, Error};
+
+const CONTRACT_CLASS_LEAF_VERSION: &str = "CONTRACT_CLASS_LEAF_V0";
+const STARKNET_STATE_VERSION: &str = "STARKNET_STATE_V0";
+
+/// Maintains the tries needed for calculating the global state root: a storage trie per contract,
+/// the contracts trie and the classes trie. The tries are updated with the state diff of each
+/// commit, so the cost of an update depends on the size of the diff, not of the whole state. Only
+/// the locally stored state is committed to; in forking mode, the state of the origin is not a part
+/// of the commitment. Likewise, lazily predeployed accounts are only committed to once modified, and
+/// then only with their class hash and the modified values, so the root differs from the one
+/// obtained with regular predeployment.
+#[derive(Clone, Default)]
+pub(crate) struct StateCommitment {
+    storage_tries: HashMap<ContractAddress, PatriciaTrie<Pedersen>>,
+    /// (class hash, storage root, nonce) of each contract, as last inserted in the contracts trie
+    contract_states: HashMap<ContractAddress, (Felt, Felt, Felt)>,
+    contracts_trie: PatriciaTrie<Pedersen>,
+    classes_trie: PatriciaTrie<Poseidon>,
+}
+
+impl StateCommitment {
+    /// Creates the tries of the whole `state`
+    pub(crate) fn from_state(state: &DictState) -> DevnetResult<Self> {
+        let mut commitment = Self::default();
+        commitment.apply_diff(&StateDiff::from_dict_state(state), state)?;
+        Ok(commitment)
+    }
+
+    /// Updates the tries with the changes of `state_diff`. `state` is the state after applying the
+    /// diff; it supplies the class hashes of modified lazily predeployed accounts.
+    pub(crate) fn apply_diff(
+        &mut self,
+        state_diff: &StateDiff,
+        state: &DictState,
+    ) -> DevnetResult<()> {
+        let mut touched_contracts = HashSet::new();
+        for (address, storage_updates) in &state_diff.storage_updates {
+            let address = (*address).try_into()?;
+            let storage_trie = self.storage_tries.entry(address).or_default();
+            for (key, value) in storage_updates {
+                storage_trie.set(key.to_felt(), *value);
+            }
+            touched_contracts.insert(address);
+        }
+
+        let mut class_hashes = HashMap::new();
+        for (address, class_hash) in &state_diff.address_to_class_hash {
+            let address = (*address).try_into()?;
+            class_hashes.insert(address, *class_hash);
+            touched_contracts.insert(address);
+        }
+
+        let mut nonces = HashMap::new();
+        for (address, nonce) in &state_diff.address_to_nonce {
+            let address = (*address).try_into()?;
+            nonces.insert(address, *nonce);
+            touched_contracts.insert(address);
+        }
+
+        for address in touched_contracts {
+            let (old_class_hash, _, old_nonce) = match self.contract_states.get(&address) {
+                Some(contract_state) => *contract_state,
+                None => {
+                    let class_hash = state.defaulter().lazily_predeployed_class_hash(address);
+                    (class_hash.unwrap_or_default().0, Felt::ZERO, Felt::ZERO)
+                }
+            };
+            let class_hash = class_hashes.get(&address).copied().unwrap_or(old_class_hash);
+            let nonce = nonces.get(&address).copied().unwrap_or(old_nonce);
+            let storage_root =
+                self.storage_tries.get_mut(&address).map(|trie| trie.root()).unwrap_or_default();
+
+            let contract_state = (class_hash, storage_root, nonce);
+            if self.contract_states.get(&address) != Some(&contract_state) {
+                self.contracts_trie
+                    .set(*address.0.key(), contract_leaf_hash(class_hash, storage_root, nonce));
+                self.contract_states.insert(address, contract_state);
+            }
+        }
+
+        let class_leaf_version = short_string_to_felt(CONTRACT_CLASS_LEAF_VERSION)?;
+        for (class_hash, compiled_class_hash) in &state_diff.class_hash_to_compiled_class_hash {
+            let leaf = if *compiled_class_hash == Felt::ZERO {
+                Felt::ZERO
+            } else {
+                Poseidon::hash(&class_leaf_version, compiled_class_hash)
+            };
+            self.classes_trie.set(*class_hash, leaf);
+        }
+
+        Ok(())
+    }
+
+    /// Generates proofs of the requested classes, contracts and storage entries against the tries,
+    /// as last updated. The proofs are accompanied by the roots of the contracts and classes tries.
+    pub(crate) fn storage_proof(
+        &mut self,
+        block_hash: BlockHash,
+        class_hashes: &[ClassHash],
+        contract_addresses: &[starknet_types::contract_address::ContractAddress],
+        contracts_storage_keys: &[ContractStorageKeys],
+    ) -> DevnetResult<StorageProof> {
+        let contract_keys: Vec<Felt> =
+            contract_addresses.iter().map(|address| Felt::from(*address)).collect();
+
+        let mut contract_leaves_data = vec![];
+        for address in contract_addresses {
+            let (class_hash, storage_root, nonce) =
+                self.contract_states.get(&(*address).try_into()?).copied().unwrap_or_default();
+            contract_leaves_data.push(ContractLeafData { nonce, class_hash, storage_root });
+        }
+
+        let mut contracts_storage_proofs = vec![];
+        for ContractStorageKeys { contract_address, storage_keys } in contracts_storage_keys {
+            let proof = match self.storage_tries.get_mut(&(*contract_address).try_into()?) {
+                Some(storage_trie) => storage_trie.proof_nodes(storage_keys),
+                None => vec![],
+            };
+            contracts_storage_proofs.push(proof);
+        }
+
+        Ok(StorageProof {
+            classes_proof: self.classes_trie.proof_nodes(class_hashes),
+            contracts_proof: ContractsProof {
+                nodes: self.contracts_trie.proof_nodes(&contract_keys),
+                contract_leaves_data,
+            },
+            contracts_storage_proofs,
+            global_roots: GlobalRoots {
+                contracts_tree_root: self.contracts_trie.root(),
+                classes_tree_root: self.classes_trie.root(),
+                block_hash,
+            },
+        })
+    }
+
+    /// Global state root, as last updated. If no Cairo 1 classes are declared, it equals the root
+    /// of the contracts trie.
+    pub(crate) fn root(&mut self) -> DevnetResult<Felt> {
+        let contracts_root = self.contracts_trie.root();
+        let classes_root = self.classes_trie.root();
+
+        if classes_root == Felt::ZERO {
+            return Ok(contracts_root);
+        }
+
+        Ok(Poseidon::hash_array(&[
+            short_string_to_felt(STARKNET_STATE_VERSION)?,
+            contracts_root,
+            classes_root,
+        ]))
+    }
+}
+
+fn contract_leaf_hash(class_hash: Felt, storage_root: Felt, nonce: Felt) -> Felt {
+    // the last element is the contract state hash version, which is always zero
+    Pedersen::hash(
+        &Pedersen::hash(&Pedersen::hash(&class_hash, &storage_root), &nonce),
+        &Felt::ZERO,
+    )
+}
+
+fn short_string_to_felt(short_string: &str) -> DevnetResult<Felt> {
+    cairo_short_string_to_felt(short_string)
+        .map_err(|err| Error::UnexpectedInternalError { msg: err.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use starknet_api::core::{ClassHash, Nonce};
+    use starknet_rs_core::types::Felt;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types_core::hash::{Pedersen, StarkHash};
+
+    use super::{contract_leaf_hash, StateCommitment};
+    use crate::state::patricia_trie::{PatriciaTrie, TRIE_HEIGHT};
+    use crate::state::state_diff::StateDiff;
+    use crate::state::state_readers::DictState;
+
+    #[test]
+    fn empty_state_has_zero_root() {
+        let mut commitment = StateCommitment::from_state(&DictState::default()).unwrap();
+        assert_eq!(commitment.root().unwrap(), Felt::ZERO);
+    }
+
+    #[test]
+    fn root_of_state_without_cairo_1_classes_equals_contracts_root() {
+        let mut state = DictState::default();
+        let address = 0x123_u32.into();
+        let class_hash = Felt::from(0xabc_u32);
+        let (storage_key, storage_value) = (Felt::from(0x5_u32), Felt::from(0x10_u32));
+
+        state.set_class_hash_at(address, ClassHash(class_hash)).unwrap();
+        state.set_storage_at(address, storage_key.try_into().unwrap(), storage_value).unwrap();
+        state.set_nonce(address, Nonce(Felt::ONE)).unwrap();
+
+        let storage_root = Pedersen::hash(&storage_value, &storage_key) + Felt::from(TRIE_HEIGHT);
+        let mut contracts_trie = PatriciaTrie::<Pedersen>::default();
+        contracts_trie
+            .set(*address.0.key(), contract_leaf_hash(class_hash, storage_root, Felt::ONE));
+
+        let mut commitment = StateCommitment::from_state(&state).unwrap();
+        assert_eq!(commitment.root().unwrap(), contracts_trie.root());
+    }
+
+    #[test]
+    fn root_changes_with_compiled_class_hash_and_is_restored_with_clone() {
+        let address = ContractAddress::new(Felt::from(0x123_u32)).unwrap();
+        let state = DictState::default();
+        let mut commitment = StateCommitment::default();
+        let deployment = StateDiff {
+            address_to_class_hash: HashMap::from([(address, Felt::ONE)]),
+            ..Default::default()
+        };
+        commitment.apply_diff(&deployment, &state).unwrap();
+        let root_before_declaration = commitment.root().unwrap();
+        let commitment_before_declaration = commitment.clone();
+
+        let declaration = StateDiff {
+            class_hash_to_compiled_class_hash: HashMap::from([(Felt::TWO, Felt::THREE)]),
+            ..Default::default()
+        };
+        commitment.apply_diff(&declaration, &state).unwrap();
+        let root_after_declaration = commitment.root().unwrap();
+        assert_ne!(root_before_declaration, root_after_declaration);
+
+        // nonce change also affects the root
+        let nonce_change = StateDiff {
+            address_to_nonce: HashMap::from([(address, Felt::TWO)]),
+            ..Default::default()
+        };
+        commitment.apply_diff(&nonce_change, &state).unwrap();
+        assert_ne!(commitment.root().unwrap(), root_after_declaration);
+
+        assert_eq!(commitment_before_declaration.clone().root().unwrap(), root_before_declaration);
+    }
+
+    #[test]
+    fn applying_diffs_one_by_one_equals_committing_the_resulting_state() {
+        let mut state = DictState::default();
+        let mut commitment = StateCommitment::default();
+        let addresses: Vec<starknet_api::core::ContractAddress> =
+            (1..=3_u32).map(|i| (i * 0x100).into()).collect();
+
+        for (i, address) in addresses.iter().enumerate() {
+            let mut diff_state = DictState::default();
+            let value = Felt::from(i as u32 + 1);
+            for target_state in [&mut state, &mut diff_state] {
+                target_state.set_class_hash_at(*address, ClassHash(value)).unwrap();
+                target_state.set_storage_at(*address, 0x5_u32.into(), value).unwrap();
+                // overwrites the storage of the first contract
+                target_state.set_storage_at(addresses[0], 0x6_u32.into(), value).unwrap();
+            }
+            commitment.apply_diff(&StateDiff::from_dict_state(&diff_state), &state).unwrap();
+        }
+
+        assert_eq!(
+            commitment.root().unwrap(),
+            StateCommitment::from_state(&state).unwrap().root().unwrap()
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
+use blockifier::execution::contract_class::ContractClass as CompiledContractClass;
 use blockifier::state::cached_state::CachedState;
 use blockifier::state::state_api::StateReader;
+use serde::{Deserialize, Serialize};
 use starknet_rs_core::types::Felt;
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_class::ContractClass;
@@ -11,10 +13,11 @@ use starknet_types::rpc::state::{
     ClassHashes, ContractNonce, DeployedContract, StorageDiff, StorageEntry, ThinStateDiff,
 };
 
+use super::state_readers::DictState;
 use crate::error::DevnetResult;
 
 /// This struct is used to store the difference between state modifications
-#[derive(PartialEq, Default, Debug, Clone)]
+#[derive(PartialEq, Default, Debug, Clone, Serialize, Deserialize)]
 pub struct StateDiff {
     pub(crate) storage_updates: HashMap<ContractAddress, HashMap<StorageKey, Felt>>,
     pub(crate) address_to_nonce: HashMap<ContractAddress, Felt>,
@@ -94,6 +97,45 @@ impl StateDiff {
         })
     }
 
+    /// Returns the difference between an empty state and `state`, i.e. the diff which, applied to
+    /// an empty state, results in `state`.
+    pub(crate) fn from_dict_state(state: &DictState) -> Self {
+        let mut storage_updates = HashMap::<ContractAddress, HashMap<StorageKey, Felt>>::new();
+        for ((address, key), value) in &state.storage_view {
+            storage_updates.entry((*address).into()).or_default().insert(key.0.into(), *value);
+        }
+
+        let mut declared_contracts = vec![];
+        let mut cairo_0_declared_contracts = vec![];
+        for (class_hash, class) in &state.class_hash_to_class {
+            match class {
+                CompiledContractClass::V0(_) => cairo_0_declared_contracts.push(class_hash.0),
+                CompiledContractClass::V1(_) => declared_contracts.push(class_hash.0),
+            }
+        }
+
+        StateDiff {
+            storage_updates,
+            address_to_nonce: state
+                .address_to_nonce
+                .iter()
+                .map(|(address, nonce)| ((*address).into(), nonce.0))
+                .collect(),
+            address_to_class_hash: state
+                .address_to_class_hash
+                .iter()
+                .map(|(address, class_hash)| ((*address).into(), class_hash.0))
+                .collect(),
+            class_hash_to_compiled_class_hash: state
+                .class_hash_to_compiled_class_hash
+                .iter()
+                .map(|(class_hash, compiled_class_hash)| (class_hash.0, compiled_class_hash.0))
+                .collect(),
+            declared_contracts,
+            cairo_0_declared_contracts,
+        }
+    }
+
     /// Modify this object by extending all of its properties with the corresponding properties of
     /// the `other` object.
     pub(crate) fn extend(&mut self, other: &StateDiff) {
@@ -26,6 +26,14 @@ impl DictState {
     pub fn new(defaulter: StarknetDefaulter) -> Self {
         Self { defaulter, ..Self::default() }
     }
+
+    pub(crate) fn defaulter(&self) -> &StarknetDefaulter {
+        &self.defaulter
+    }
+
+    pub(crate) fn defaulter_mut(&mut self) -> &mut StarknetDefaulter {
+        &mut self.defaulter
+    }
 }
 
 impl StateReader for DictState {
@@ -74,7 +82,8 @@ impl StateReader for DictState {
 }
 
 // Basing the methods on blockifier's `State` interface, without those that would never be used
-// (add_visited_pcs, to_state_diff)
+// (add_visited_pcs, to_state_diff); `increment_nonce` is replaced with `set_nonce`, since state
+// diffs contain the new nonce values
 impl DictState {
     pub fn set_storage_at(
         &mut self,
@@ -86,11 +95,12 @@ impl DictState {
         Ok(())
     }
 
-    pub fn increment_nonce(&mut self, contract_address: ContractAddress) -> StateResult<()> {
-        let current_nonce = self.get_nonce_at(contract_address)?;
-        let next_nonce = Nonce(current_nonce.0 + 1);
-        self.address_to_nonce.insert(contract_address, next_nonce);
-
+    pub fn set_nonce(
+        &mut self,
+        contract_address: ContractAddress,
+        nonce: Nonce,
+    ) -> StateResult<()> {
+        self.address_to_nonce.insert(contract_address, nonce);
         Ok(())
     }
 
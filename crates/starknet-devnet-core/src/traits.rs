@@ -64,7 +64,7 @@ pub trait AccountGenerator {
     type Acc: Accounted;
     fn generate_accounts(
         &mut self,
-        number_of_accounts: u8,
+        number_of_accounts: u32,
         class_hash: ClassHash,
         contract_class: &ContractClass,
     ) -> DevnetResult<&Vec<Self::Acc>>;
@@ -3,6 +3,7 @@ use blockifier::transaction::objects::TransactionExecutionInfo;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockNumber;
+use starknet_api::transaction::Fee;
 use starknet_rs_core::types::{ExecutionResult, Felt, TransactionFinalityStatus};
 use starknet_rs_core::utils::get_selector_from_name;
 use starknet_types::contract_address::ContractAddress;
@@ -11,7 +12,7 @@ use starknet_types::felt::{felt_from_prefixed_hex, BlockHash, TransactionHash};
 use starknet_types::messaging::MessageToL2;
 use starknet_types::rpc::messaging::{MessageToL1, OrderedMessageToL1};
 use starknet_types::rpc::transaction_receipt::{
-    DeployTransactionReceipt, FeeAmount, FeeInUnits, TransactionReceipt,
+    DeployTransactionReceipt, ExecutionResources, FeeAmount, FeeInUnits, TransactionReceipt,
 };
 use starknet_types::rpc::transactions::{
     DeclareTransaction, DeployAccountTransaction, InvokeTransaction, Transaction, TransactionTrace,
@@ -22,7 +23,7 @@ use crate::constants::UDC_CONTRACT_ADDRESS;
 use crate::error::{DevnetResult, Error};
 use crate::traits::{HashIdentified, HashIdentifiedMut};
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct StarknetTransactions(IndexMap<TransactionHash, StarknetTransaction>);
 
 impl StarknetTransactions {
@@ -37,6 +38,40 @@ impl StarknetTransactions {
     pub fn iter(&self) -> indexmap::map::Iter<'_, Felt, StarknetTransaction> {
         self.0.iter()
     }
+
+    /// Returns the statuses of the transactions in insertion order. Execution info and traces are
+    /// not copied since they can't change once the transaction is added.
+    pub(crate) fn statuses(&self) -> Vec<StarknetTransactionStatus> {
+        self.0
+            .values()
+            .map(|tx| StarknetTransactionStatus {
+                finality_status: tx.finality_status,
+                execution_result: tx.execution_result.clone(),
+                block_hash: tx.block_hash,
+                block_number: tx.block_number,
+            })
+            .collect()
+    }
+
+    /// Removes the transactions added after `statuses` were obtained and restores the statuses of
+    /// the rest.
+    pub(crate) fn restore(&mut self, statuses: Vec<StarknetTransactionStatus>) {
+        self.0.truncate(statuses.len());
+        for (tx, status) in self.0.values_mut().zip(statuses) {
+            tx.finality_status = status.finality_status;
+            tx.execution_result = status.execution_result;
+            tx.block_hash = status.block_hash;
+            tx.block_number = status.block_number;
+        }
+    }
+}
+
+/// The part of `StarknetTransaction` that can change after the transaction is added.
+pub(crate) struct StarknetTransactionStatus {
+    finality_status: TransactionFinalityStatus,
+    execution_result: ExecutionResult,
+    block_hash: Option<BlockHash>,
+    block_number: Option<BlockNumber>,
 }
 
 impl HashIdentifiedMut for StarknetTransactions {
@@ -56,16 +91,19 @@ impl HashIdentified for StarknetTransactions {
 }
 
 #[allow(unused)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StarknetTransaction {
     pub inner: TransactionWithHash,
     pub(crate) finality_status: TransactionFinalityStatus,
     pub(crate) execution_result: ExecutionResult,
     pub(crate) block_hash: Option<BlockHash>,
     pub(crate) block_number: Option<BlockNumber>,
-    #[serde(skip)]
-    pub(crate) execution_info: TransactionExecutionInfo,
-    #[serde(skip)]
+    /// The outputs of the execution are extracted on creation, so that the transaction can be
+    /// stored without the execution info
+    events: Vec<Event>,
+    messages_sent: Vec<MessageToL1>,
+    actual_fee: Fee,
+    execution_resources: ExecutionResources,
     pub(crate) trace: Option<TransactionTrace>,
 }
 
@@ -89,12 +127,19 @@ impl StarknetTransaction {
             inner: transaction.clone(),
             block_hash: None,
             block_number: None,
-            execution_info,
+            events: Self::extract_events(&execution_info),
+            messages_sent: Self::extract_l2_to_l1_messages(&execution_info),
+            actual_fee: execution_info.transaction_receipt.fee,
+            execution_resources: ExecutionResources::from(&execution_info),
             trace: Some(trace),
         }
     }
 
     pub fn get_events(&self) -> Vec<Event> {
+        self.events.clone()
+    }
+
+    fn extract_events(execution_info: &TransactionExecutionInfo) -> Vec<Event> {
         let mut events: Vec<Event> = vec![];
 
         fn get_blockifier_events_recursively(
@@ -118,9 +163,9 @@ impl StarknetTransaction {
         }
 
         let call_infos = vec![
-            self.execution_info.validate_call_info.as_ref(),
-            self.execution_info.execute_call_info.as_ref(),
-            self.execution_info.fee_transfer_call_info.as_ref(),
+            execution_info.validate_call_info.as_ref(),
+            execution_info.execute_call_info.as_ref(),
+            execution_info.fee_transfer_call_info.as_ref(),
         ];
 
         for inner_call_info in call_infos.into_iter().flatten() {
@@ -172,7 +217,7 @@ impl StarknetTransaction {
         // L1 Handler transactions are in WEI
         // V3 transactions are in STRK(FRI)
         // Other transactions versions are in ETH(WEI)
-        let fee_amount = FeeAmount { amount: self.execution_info.transaction_receipt.fee };
+        let fee_amount = FeeAmount { amount: self.actual_fee };
         let actual_fee_in_units = match self.inner.transaction {
             Transaction::L1Handler(_) => FeeInUnits::WEI(fee_amount),
             Transaction::Declare(DeclareTransaction::V3(_))
@@ -189,7 +234,7 @@ impl StarknetTransaction {
             &self.execution_result,
             self.finality_status,
             actual_fee_in_units,
-            &self.execution_info,
+            self.execution_resources.clone(),
         );
 
         match &self.inner.transaction {
@@ -233,6 +278,10 @@ impl StarknetTransaction {
     }
 
     pub fn get_l2_to_l1_messages(&self) -> Vec<MessageToL1> {
+        self.messages_sent.clone()
+    }
+
+    fn extract_l2_to_l1_messages(execution_info: &TransactionExecutionInfo) -> Vec<MessageToL1> {
         let mut messages = vec![];
 
         fn get_blockifier_messages_recursively(call_info: &CallInfo) -> Vec<OrderedMessageToL1> {
@@ -261,7 +310,7 @@ impl StarknetTransaction {
             messages
         }
 
-        let call_infos = self.execution_info.non_optional_call_infos();
+        let call_infos = execution_info.non_optional_call_infos();
 
         for inner_call_info in call_infos {
             let mut not_sorted_messages = get_blockifier_messages_recursively(inner_call_info);
@@ -316,7 +365,10 @@ mod tests {
         assert_eq!(sn_tx.block_number, extracted_tran.block_number);
         assert!(sn_tx.inner == extracted_tran.inner);
         assert_eq!(sn_tx.finality_status, extracted_tran.finality_status);
-        assert_eq!(sn_tx.execution_info, extracted_tran.execution_info);
+        assert_eq!(sn_tx.events, extracted_tran.events);
+        assert_eq!(sn_tx.messages_sent, extracted_tran.messages_sent);
+        assert_eq!(sn_tx.actual_fee, extracted_tran.actual_fee);
+        assert_eq!(sn_tx.execution_resources, extracted_tran.execution_resources);
     }
 
     #[test]
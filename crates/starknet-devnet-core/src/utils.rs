@@ -1,6 +1,9 @@
 use blockifier::bouncer::{BouncerConfig, BouncerWeights, BuiltinCount};
 use blockifier::versioned_constants::VersionedConstants;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet_classes::contract_class::ContractClass as SierraContractClass;
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use starknet_rs_core::types::contract::CompiledClass;
 use starknet_rs_core::types::Felt;
 use starknet_types::patricia_key::{PatriciaKey, StorageKey};
@@ -15,7 +18,7 @@ pub mod random_number_generator {
         thread_rng().gen()
     }
 
-    pub(crate) fn generate_u128_random_numbers(seed: u32, random_numbers_count: u8) -> Vec<u128> {
+    pub(crate) fn generate_u128_random_numbers(seed: u32, random_numbers_count: u32) -> Vec<u128> {
         let mut result: Vec<u128> = Vec::new();
         let mut rng: Mt64 = SeedableRng::seed_from_u64(seed as u64);
 
@@ -80,6 +83,31 @@ pub fn calculate_casm_hash(casm_json: Value) -> DevnetResult<Felt> {
         .map_err(|err| Error::UnexpectedInternalError { msg: err.to_string() })
 }
 
+/// Returns the hex-encoded SHA-256 hash of `value` in JSON format. Used for identifying the inputs
+/// of the predeployed state without storing them.
+pub(crate) fn fingerprint(value: &Value) -> String {
+    format!("{:x}", Sha256::digest(value.to_string().as_bytes()))
+}
+
+/// Compiles the Sierra contract class to CASM and returns it in JSON format.
+pub fn compile_sierra_contract(sierra_contract_class: &SierraContractClass) -> DevnetResult<Value> {
+    let sierra_json = serde_json::to_value(sierra_contract_class)
+        .map_err(|err| Error::SerializationError { origin: err.to_string() })?;
+
+    usc::compile_contract(sierra_json).map_err(|err| {
+        let reason = err.to_string();
+        Error::TypesError(starknet_types::error::Error::SierraCompilationError { reason })
+    })
+}
+
+/// Compiles the Sierra contract class to CASM.
+pub(crate) fn compile_sierra_contract_to_casm(
+    sierra_contract_class: &SierraContractClass,
+) -> DevnetResult<CasmContractClass> {
+    serde_json::from_value(compile_sierra_contract(sierra_contract_class)?)
+        .map_err(|err| Error::DeserializationError { origin: err.to_string() })
+}
+
 #[cfg(test)]
 pub(crate) mod test_utils {
     use cairo_lang_starknet_classes::contract_class::ContractClass as SierraContractClass;
@@ -47,7 +47,7 @@ pub(crate) async fn get_predeployed_accounts_impl(
         .get_predeployed_accounts()
         .into_iter()
         .map(|acc| SerializableAccount {
-            initial_balance: acc.initial_balance.to_string(),
+            initial_balance: acc.eth_initial_balance.to_string(),
             address: acc.account_address,
             public_key: acc.public_key,
             private_key: acc.private_key,
@@ -2,7 +2,9 @@ use axum::extract::State;
 use axum::Json;
 
 use crate::api::http::error::HttpApiError;
-use crate::api::http::models::{AbortedBlocks, AbortingBlocks, CreatedBlock};
+use crate::api::http::models::{
+    AbortedBlocks, AbortingBlocks, AcceptedOnL1, AcceptingOnL1, CreatedBlock,
+};
 use crate::api::http::{HttpApiHandler, HttpApiResult};
 use crate::api::Api;
 
@@ -44,3 +46,23 @@ pub(crate) async fn abort_blocks_impl(
 
     Ok(AbortedBlocks { aborted })
 }
+
+pub async fn accept_on_l1(
+    State(state): State<HttpApiHandler>,
+    Json(data): Json<AcceptingOnL1>,
+) -> HttpApiResult<Json<AcceptedOnL1>> {
+    accept_on_l1_impl(&state.api, data).await.map(Json::from)
+}
+
+pub(crate) async fn accept_on_l1_impl(
+    api: &Api,
+    data: AcceptingOnL1,
+) -> HttpApiResult<AcceptedOnL1> {
+    let mut starknet = api.starknet.lock().await;
+
+    let accepted = starknet
+        .accept_on_l1(From::from(data.starting_block_id))
+        .map_err(|err| HttpApiError::BlockAcceptOnL1Error { msg: err.to_string() })?;
+
+    Ok(AcceptedOnL1 { accepted })
+}
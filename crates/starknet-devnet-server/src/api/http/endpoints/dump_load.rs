@@ -1,5 +1,8 @@
 use axum::extract::State;
 use axum::Json;
+use starknet_core::error::Error;
+use starknet_core::starknet::dump::DumpContent;
+use starknet_core::starknet::starknet_config::LoadUntil;
 
 use super::extract_optional_json_from_request;
 use crate::api::http::error::HttpApiError;
@@ -33,19 +36,21 @@ pub(crate) async fn dump_impl(
             Some(path) => {
                 // dump_path is present
                 starknet
-                    .dump_events_custom_path(Some(path.clone()))
+                    .dump_custom_path(Some(path.clone()))
                     .map_err(|err| HttpApiError::DumpError { msg: err.to_string() })?;
                 Ok(None)
             }
             None => {
                 // dump_path is not present
-                let json_dump = starknet.read_dump_events();
-                Ok(Some(json_dump.clone()))
+                let dump = starknet
+                    .get_dump()
+                    .map_err(|err| HttpApiError::DumpError { msg: err.to_string() })?;
+                Ok(Some(dump))
             }
         }
     } else {
         starknet
-            .dump_events_custom_path(Some(path))
+            .dump_custom_path(Some(path))
             .map_err(|err| HttpApiError::DumpError { msg: err.to_string() })?;
         Ok(None)
     }
@@ -59,16 +64,34 @@ pub async fn load(
 }
 
 pub(crate) async fn load_impl(api: &Api, path_wrapper: LoadPath) -> HttpApiResult<()> {
+    let load_until = match (path_wrapper.block_number, path_wrapper.event_index) {
+        (Some(block_number), None) => Some(LoadUntil::BlockNumber(block_number)),
+        (None, Some(event_index)) => Some(LoadUntil::EventIndex(event_index)),
+        (None, None) => None,
+        (Some(_), Some(_)) => {
+            return Err(HttpApiError::InvalidValueError {
+                msg: "Only one of block_number and event_index can be provided".to_string(),
+            });
+        }
+    };
+
     let mut starknet = api.starknet.lock().await;
 
     // necessary to restart before loading
     starknet.restart().map_err(|e| HttpApiError::RestartError { msg: e.to_string() })?;
 
-    match starknet.load_events(&path_wrapper.path) {
-        Ok(events) => {
-            starknet.re_execute(events).map_err(|e| HttpApiError::ReExecutionError(e.to_string()))
-        }
-        Err(starknet_core::error::Error::FileNotFound) => Err(HttpApiError::FileNotFound),
-        Err(e) => Err(HttpApiError::LoadError(e.to_string())),
-    }
+    let dump = match starknet.load_dump(&path_wrapper.path) {
+        Ok(dump) => dump,
+        Err(Error::FileNotFound) => return Err(HttpApiError::FileNotFound),
+        Err(e) => return Err(HttpApiError::LoadError(e.to_string())),
+    };
+
+    let is_events_dump = matches!(dump.content, DumpContent::Events(_));
+    starknet.apply_dump(dump, load_until).map_err(|e| match e {
+        Error::IncompatibleDump { .. }
+        | Error::LoadUntilOutOfRange { .. }
+        | Error::UnsupportedAction { .. } => HttpApiError::LoadError(e.to_string()),
+        e if is_events_dump => HttpApiError::ReExecutionError(e.to_string()),
+        e => HttpApiError::LoadError(e.to_string()),
+    })
 }
@@ -110,6 +110,7 @@ pub async fn postman_send_message_to_l2_impl(
 ) -> HttpApiResult<TxHash> {
     let mut starknet = api.starknet.lock().await;
 
+    let l1_transaction_hash = message.l1_transaction_hash;
     let transaction = L1HandlerTransaction::try_from_message_to_l2(message).map_err(|_| {
         HttpApiError::InvalidValueError {
             msg: "The `paid_fee_on_l1` is out of range, expecting u128 value".to_string(),
@@ -117,7 +118,7 @@ pub async fn postman_send_message_to_l2_impl(
     })?;
 
     let transaction_hash = starknet
-        .add_l1_handler_transaction(transaction)
+        .add_l1_handler_transaction(transaction, l1_transaction_hash)
         .map_err(|e| HttpApiError::MessagingError { msg: e.to_string() })?;
 
     Ok(TxHash { transaction_hash })
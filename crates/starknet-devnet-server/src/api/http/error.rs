@@ -29,6 +29,8 @@ pub enum HttpApiError {
     BlockIncreaseTimeError { msg: String },
     #[error("Block abortion failed: {msg}")]
     BlockAbortError { msg: String },
+    #[error("Block acceptance on L1 failed: {msg}")]
+    BlockAcceptOnL1Error { msg: String },
     #[error("Could not restart: {msg}")]
     RestartError { msg: String },
     #[error("Messaging error: {msg}")]
@@ -56,6 +58,9 @@ impl IntoResponse for HttpApiError {
             err @ HttpApiError::BlockAbortError { msg: _ } => {
                 (StatusCode::BAD_REQUEST, err.to_string())
             }
+            err @ HttpApiError::BlockAcceptOnL1Error { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
             err @ HttpApiError::ReExecutionError(_) => (StatusCode::BAD_REQUEST, err.to_string()),
             err @ HttpApiError::CreateEmptyBlockError { msg: _ } => {
                 (StatusCode::BAD_REQUEST, err.to_string())
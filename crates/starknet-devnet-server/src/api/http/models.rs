@@ -1,12 +1,16 @@
 use serde::{Deserialize, Serialize};
-use starknet_core::starknet::dump::DumpEvent;
+use starknet_core::starknet::dump::Dump;
 use starknet_rs_core::types::{Felt, Hash256, MsgToL1};
 use starknet_types::contract_address::ContractAddress;
-use starknet_types::felt::{BlockHash, Calldata, EntryPointSelector, Nonce, TransactionHash};
+use starknet_types::felt::{
+    BlockHash, Calldata, ClassHash, EntryPointSelector, Nonce, TransactionHash,
+};
 use starknet_types::num_bigint::BigUint;
+use starknet_types::patricia_key::StorageKey;
 use starknet_types::rpc::block::BlockId;
 use starknet_types::rpc::eth_address::EthAddressWrapper;
 use starknet_types::rpc::messaging::{MessageToL1, MessageToL2};
+use starknet_types::rpc::state::StorageEntry;
 use starknet_types::rpc::transaction_receipt::FeeUnit;
 use starknet_types::serde_helpers::dec_string::deserialize_biguint;
 
@@ -24,6 +28,12 @@ pub struct DumpPath {
 #[cfg_attr(test, derive(Debug))]
 pub struct LoadPath {
     pub path: String,
+    /// The number of the last block to load
+    #[serde(default)]
+    pub block_number: Option<u64>,
+    /// The (zero-based) index of the last event to re-execute
+    #[serde(default)]
+    pub event_index: Option<usize>,
 }
 
 #[derive(Deserialize)]
@@ -44,7 +54,7 @@ pub struct TxHash {
     pub transaction_hash: TransactionHash,
 }
 // Implemented as type alias so JSON returned doesn't have extra key
-pub type DumpResponseBody = Option<Vec<DumpEvent>>;
+pub type DumpResponseBody = Option<Dump>;
 
 #[derive(Serialize)]
 pub struct CreatedBlock {
@@ -63,6 +73,60 @@ pub struct AbortedBlocks {
     pub(crate) aborted: Vec<BlockHash>,
 }
 
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(test, derive(Debug))]
+pub struct AcceptingOnL1 {
+    pub(crate) starting_block_id: BlockId,
+}
+
+#[derive(Serialize)]
+pub struct AcceptedOnL1 {
+    pub(crate) accepted: Vec<BlockHash>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(test, derive(Debug))]
+pub struct SnapshotId {
+    pub(crate) snapshot_id: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(test, derive(Debug))]
+pub struct SetStorageAt {
+    pub contract_address: ContractAddress,
+    pub key: StorageKey,
+    pub value: Felt,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(test, derive(Debug))]
+pub struct SetNonce {
+    pub contract_address: ContractAddress,
+    pub nonce: Nonce,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(test, derive(Debug))]
+pub struct ReplaceClass {
+    pub contract_address: ContractAddress,
+    pub class_hash: ClassHash,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(test, derive(Debug))]
+pub struct DeployAt {
+    pub contract_address: ContractAddress,
+    pub class_hash: ClassHash,
+    #[serde(default)]
+    pub storage: Vec<StorageEntry>,
+}
+
 #[derive(Deserialize)]
 #[serde(deny_unknown_fields)]
 #[cfg_attr(test, derive(Debug))]
@@ -92,10 +156,12 @@ pub struct IncreaseTimeResponse {
 
 #[derive(Serialize)]
 pub struct SerializableAccount {
+    /// The initial balance in WEI
     pub initial_balance: String,
     pub address: ContractAddress,
     pub public_key: Felt,
-    pub private_key: Felt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<Felt>,
     pub balance: Option<AccountBalancesResponse>,
 }
 
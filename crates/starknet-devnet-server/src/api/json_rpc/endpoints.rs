@@ -1,5 +1,5 @@
 use starknet_core::error::{Error, StateError};
-use starknet_rs_core::types::{BlockId as ImportedBlockId, MsgFromL1};
+use starknet_rs_core::types::{BlockId as ImportedBlockId, BlockTag, Hash256, MsgFromL1};
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::{ClassHash, TransactionHash};
 use starknet_types::patricia_key::PatriciaKey;
@@ -13,8 +13,11 @@ use starknet_types::rpc::transactions::{
 use starknet_types::starknet_api::block::BlockStatus;
 
 use super::error::{ApiError, StrictRpcResult};
-use super::models::{BlockHashAndNumberOutput, SyncingOutput, TransactionStatusOutput};
-use super::{DevnetResponse, JsonRpcHandler, JsonRpcResponse, StarknetResponse, RPC_SPEC_VERSION};
+use super::models::{
+    BlockHashAndNumberOutput, GetStorageProofInput, L1HandlerTransactionStatusOutput,
+    SyncingOutput, TransactionStatusOutput,
+};
+use super::{DevnetResponse, JsonRpcHandler, JsonRpcResponse, RpcSpecVersion, StarknetResponse};
 use crate::api::http::endpoints::accounts::{
     get_account_balance_impl, get_predeployed_accounts_impl, BalanceQuery, PredeployedAccountsQuery,
 };
@@ -26,7 +29,7 @@ const DEFAULT_CONTINUATION_TOKEN: &str = "0";
 impl JsonRpcHandler {
     /// starknet_specVersion
     pub fn spec_version(&self) -> StrictRpcResult {
-        Ok(StarknetResponse::String(RPC_SPEC_VERSION.to_string()).into())
+        Ok(StarknetResponse::String(self.rpc_spec_version.as_str().to_string()).into())
     }
 
     /// starknet_getBlockWithTxHashes
@@ -110,6 +113,27 @@ impl JsonRpcHandler {
         }
     }
 
+    /// starknet_getStorageProof
+    pub async fn get_storage_proof(&self, data: GetStorageProofInput) -> StrictRpcResult {
+        if data.block_id.as_ref() == &ImportedBlockId::Tag(BlockTag::Pending) {
+            return Err(ApiError::StorageProofNotSupported);
+        }
+
+        let proof = self.api.starknet.lock().await.get_storage_proof(
+            data.block_id.as_ref(),
+            &data.class_hashes.unwrap_or_default(),
+            &data.contract_addresses.unwrap_or_default(),
+            &data.contracts_storage_keys.unwrap_or_default(),
+        );
+
+        match proof {
+            Ok(proof) => Ok(StarknetResponse::StorageProof(proof).into()),
+            Err(Error::NoBlock) => Err(ApiError::BlockNotFound),
+            Err(Error::NoStateAtBlock { .. }) => Err(ApiError::StorageProofNotSupported),
+            Err(unknown_error) => Err(ApiError::StarknetDevnetError(unknown_error)),
+        }
+    }
+
     /// starknet_getStorageAt
     pub async fn get_storage_at(
         &self,
@@ -152,17 +176,22 @@ impl JsonRpcHandler {
         &self,
         transaction_hash: TransactionHash,
     ) -> StrictRpcResult {
-        match self
-            .api
-            .starknet
-            .lock()
-            .await
-            .get_transaction_execution_and_finality_status(transaction_hash)
-        {
+        let starknet = self.api.starknet.lock().await;
+        match starknet.get_transaction_execution_and_finality_status(transaction_hash) {
             Ok((execution_status, finality_status)) => {
+                // the reason of failure is provided since v0.8
+                let failure_reason = match self.rpc_spec_version {
+                    RpcSpecVersion::V0_7 => None,
+                    RpcSpecVersion::V0_8 => starknet
+                        .get_transaction_execution_result(transaction_hash)?
+                        .revert_reason()
+                        .map(String::from),
+                };
+
                 Ok(StarknetResponse::TransactionStatusByHash(TransactionStatusOutput {
                     execution_status,
                     finality_status,
+                    failure_reason,
                 })
                 .into())
             }
@@ -222,6 +251,48 @@ impl JsonRpcHandler {
         }
     }
 
+    /// starknet_getMessagesStatus
+    pub async fn get_messages_status(&self, l1_transaction_hash: Hash256) -> StrictRpcResult {
+        let starknet = self.api.starknet.lock().await;
+        let transaction_hashes =
+            match starknet.get_l1_handler_transaction_hashes(l1_transaction_hash) {
+                Ok(transaction_hashes) => transaction_hashes,
+                Err(Error::NoTransaction) => return Err(ApiError::TransactionNotFound),
+                Err(err) => return Err(err.into()),
+            };
+
+        let mut statuses = vec![];
+        for transaction_hash in transaction_hashes {
+            let (execution_status, finality_status) =
+                starknet.get_transaction_execution_and_finality_status(transaction_hash)?;
+            let failure_reason = starknet
+                .get_transaction_execution_result(transaction_hash)?
+                .revert_reason()
+                .map(String::from);
+
+            statuses.push(L1HandlerTransactionStatusOutput {
+                transaction_hash,
+                finality_status,
+                execution_status,
+                failure_reason,
+            });
+        }
+
+        Ok(StarknetResponse::MessagesStatusByL1Hash(statuses).into())
+    }
+
+    /// starknet_getCompiledCasm
+    pub async fn get_compiled_casm(&self, class_hash: ClassHash) -> StrictRpcResult {
+        match self.api.starknet.lock().await.get_compiled_casm(class_hash) {
+            Ok(compiled_casm) => Ok(StarknetResponse::CompiledCasm(compiled_casm).into()),
+            Err(Error::StateError(_)) => Err(ApiError::ClassHashNotFound),
+            Err(Error::TypesError(starknet_types::error::Error::SierraCompilationError {
+                reason,
+            })) => Err(ApiError::CompilationError { reason }),
+            Err(unknown_error) => Err(ApiError::StarknetDevnetError(unknown_error)),
+        }
+    }
+
     /// starknet_getClassAt
     pub async fn get_class_at(
         &self,
@@ -60,6 +60,16 @@ pub enum ApiError {
     HttpApiError(#[from] HttpApiError),
     #[error("the compiled class hash did not match the one supplied in the transaction")]
     CompiledClassHashMismatch,
+    #[error("the node doesn't support storage proofs for blocks that are too far in the past")]
+    StorageProofNotSupported,
+    #[error("Invalid subscription id")]
+    InvalidSubscriptionId,
+    #[error("Cannot go back more than 1024 blocks")]
+    TooManyBlocksBack,
+    #[error("Subscription closed after missing {skipped} notifications")]
+    SubscriptionLagged { skipped: u64 },
+    #[error("Failed to compile the contract")]
+    CompilationError { reason: String },
 }
 
 impl ApiError {
@@ -171,6 +181,31 @@ impl ApiError {
                 message: error_message.into(),
                 data: None,
             },
+            ApiError::StorageProofNotSupported => RpcError {
+                code: crate::rpc_core::error::ErrorCode::ServerError(42),
+                message: error_message.into(),
+                data: None,
+            },
+            ApiError::InvalidSubscriptionId => RpcError {
+                code: crate::rpc_core::error::ErrorCode::ServerError(66),
+                message: error_message.into(),
+                data: None,
+            },
+            ApiError::TooManyBlocksBack => RpcError {
+                code: crate::rpc_core::error::ErrorCode::ServerError(68),
+                message: error_message.into(),
+                data: None,
+            },
+            ApiError::SubscriptionLagged { .. } => RpcError {
+                code: crate::rpc_core::error::ErrorCode::ServerError(WILDCARD_RPC_ERROR_CODE),
+                message: error_message.into(),
+                data: None,
+            },
+            ApiError::CompilationError { reason } => RpcError {
+                code: crate::rpc_core::error::ErrorCode::ServerError(100),
+                message: error_message.into(),
+                data: Some(json!({ "compilation_error": reason })),
+            },
             ApiError::StarknetDevnetError(
                 starknet_core::error::Error::TransactionValidationError(validation_error),
             ) => {
@@ -209,7 +244,7 @@ pub type StrictRpcResult = Result<JsonRpcResponse, ApiError>;
 mod tests {
     use super::StrictRpcResult;
     use crate::api::json_rpc::error::ApiError;
-    use crate::api::json_rpc::ToRpcResponseResult;
+    use crate::api::json_rpc::{ToRpcResponseResult, WILDCARD_RPC_ERROR_CODE};
 
     #[test]
     fn contract_not_found_error() {
@@ -276,6 +311,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn storage_proof_not_supported_error() {
+        error_expected_code_and_message(
+            ApiError::StorageProofNotSupported,
+            42,
+            "the node doesn't support storage proofs for blocks that are too far in the past",
+        );
+    }
+
+    #[test]
+    fn invalid_subscription_id_error() {
+        error_expected_code_and_message(
+            ApiError::InvalidSubscriptionId,
+            66,
+            "Invalid subscription id",
+        );
+    }
+
+    #[test]
+    fn too_many_blocks_back_error() {
+        error_expected_code_and_message(
+            ApiError::TooManyBlocksBack,
+            68,
+            "Cannot go back more than 1024 blocks",
+        );
+    }
+
+    #[test]
+    fn subscription_lagged_error() {
+        error_expected_code_and_message(
+            ApiError::SubscriptionLagged { skipped: 3 },
+            WILDCARD_RPC_ERROR_CODE,
+            "Subscription closed after missing 3 notifications",
+        );
+    }
+
+    #[test]
+    fn compilation_error() {
+        let reason = "some reason";
+        error_expected_code_and_message(
+            ApiError::CompilationError { reason: reason.into() },
+            100,
+            "Failed to compile the contract",
+        );
+
+        let error = ApiError::CompilationError { reason: reason.into() }.api_error_to_rpc_error();
+        assert_eq!(error.data.unwrap().get("compilation_error").unwrap().as_str().unwrap(), reason);
+    }
+
     #[test]
     fn contract_error() {
         fn test_error() -> starknet_core::error::Error {
@@ -4,16 +4,22 @@ pub mod models;
 pub(crate) mod origin_forwarder;
 #[cfg(test)]
 mod spec_reader;
+mod spec_version;
 mod write_endpoints;
 
-pub const RPC_SPEC_VERSION: &str = "0.7.1";
+/// The version of the specification served via the unversioned routes
+pub const RPC_SPEC_VERSION: &str = RpcSpecVersion::V0_7.as_str();
+
+pub use spec_version::RpcSpecVersion;
 
 use models::{
     BlockAndClassHashInput, BlockAndContractAddressInput, BlockAndIndexInput, CallInput,
-    EstimateFeeInput, EventsInput, GetStorageInput, TransactionHashInput, TransactionHashOutput,
+    ClassHashInput, EstimateFeeInput, EventsInput, GetStorageInput, GetStorageProofInput,
+    L1TransactionHashInput, TransactionHashInput, TransactionHashOutput,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use starknet_rs_core::types::contract::CompiledClass;
 use starknet_rs_core::types::{ContractClass as CodegenContractClass, Felt};
 use starknet_types::messaging::{MessageToL1, MessageToL2};
 use starknet_types::rpc::block::{Block, PendingBlock};
@@ -21,7 +27,7 @@ use starknet_types::rpc::estimate_message_fee::{
     EstimateMessageFeeRequestWrapper, FeeEstimateWrapper,
 };
 use starknet_types::rpc::gas_modification::{GasModification, GasModificationRequest};
-use starknet_types::rpc::state::{PendingStateUpdate, StateUpdate};
+use starknet_types::rpc::state::{PendingStateUpdate, StateUpdate, StorageProof};
 use starknet_types::rpc::transaction_receipt::TransactionReceipt;
 use starknet_types::rpc::transactions::{
     BlockTransactionTrace, EventsChunk, SimulatedTransaction, TransactionTrace, TransactionWithHash,
@@ -34,16 +40,17 @@ use self::models::{
     AccountAddressInput, BlockHashAndNumberOutput, BlockIdInput,
     BroadcastedDeclareTransactionInput, BroadcastedDeployAccountTransactionInput,
     BroadcastedInvokeTransactionInput, DeclareTransactionOutput, DeployAccountTransactionOutput,
-    SyncingOutput, TransactionStatusOutput,
+    L1HandlerTransactionStatusOutput, SyncingOutput, TransactionStatusOutput,
 };
 use self::origin_forwarder::OriginForwarder;
 use super::http::endpoints::accounts::{BalanceQuery, PredeployedAccountsQuery};
 use super::http::endpoints::DevnetConfig;
 use super::http::models::{
-    AbortedBlocks, AbortingBlocks, AccountBalanceResponse, CreatedBlock, DumpPath,
-    DumpResponseBody, FlushParameters, FlushedMessages, IncreaseTime, IncreaseTimeResponse,
-    LoadPath, MessageHash, MessagingLoadAddress, MintTokensRequest, MintTokensResponse,
-    PostmanLoadL1MessagingContract, SerializableAccount, SetTime, SetTimeResponse,
+    AbortedBlocks, AbortingBlocks, AcceptedOnL1, AcceptingOnL1, AccountBalanceResponse,
+    CreatedBlock, DeployAt, DumpPath, DumpResponseBody, FlushParameters, FlushedMessages,
+    IncreaseTime, IncreaseTimeResponse, LoadPath, MessageHash, MessagingLoadAddress,
+    MintTokensRequest, MintTokensResponse, PostmanLoadL1MessagingContract, ReplaceClass,
+    SerializableAccount, SetNonce, SetStorageAt, SetTime, SetTimeResponse, SnapshotId,
 };
 use super::Api;
 use crate::api::json_rpc::models::{
@@ -95,6 +102,7 @@ pub struct JsonRpcHandler {
     pub api: Api,
     pub origin_caller: Option<OriginForwarder>,
     pub server_config: ServerConfig,
+    pub rpc_spec_version: RpcSpecVersion,
 }
 
 #[async_trait::async_trait]
@@ -109,6 +117,14 @@ impl RpcHandler for JsonRpcHandler {
         info!(target: "rpc", "received method in on_request {}", request);
         self.execute(request, original_call).await
     }
+
+    fn supports_method(&self, method: &str) -> bool {
+        self.rpc_spec_version.supports_method(method)
+    }
+
+    fn convert_params(&self, method: &str, params: serde_json::Value) -> serde_json::Value {
+        self.rpc_spec_version.convert_params(method, params)
+    }
 }
 
 impl JsonRpcHandler {
@@ -138,6 +154,7 @@ impl JsonRpcHandler {
             JsonRpcRequest::StorageAt(GetStorageInput { contract_address, key, block_id }) => {
                 self.get_storage_at(contract_address, key, block_id).await
             }
+            JsonRpcRequest::StorageProof(data) => self.get_storage_proof(data).await,
             JsonRpcRequest::TransactionStatusByHash(TransactionHashInput { transaction_hash }) => {
                 self.get_transaction_status_by_hash(transaction_hash).await
             }
@@ -153,6 +170,12 @@ impl JsonRpcHandler {
             JsonRpcRequest::ClassByHash(BlockAndClassHashInput { block_id, class_hash }) => {
                 self.get_class(block_id, class_hash).await
             }
+            JsonRpcRequest::CompiledCasm(ClassHashInput { class_hash }) => {
+                self.get_compiled_casm(class_hash).await
+            }
+            JsonRpcRequest::MessagesStatusByL1Hash(L1TransactionHashInput { transaction_hash }) => {
+                self.get_messages_status(transaction_hash).await
+            }
             JsonRpcRequest::ClassHashAtContractAddress(BlockAndContractAddressInput {
                 block_id,
                 contract_address,
@@ -239,9 +262,16 @@ impl JsonRpcHandler {
             }
             JsonRpcRequest::CreateBlock => self.create_block().await,
             JsonRpcRequest::AbortBlocks(data) => self.abort_blocks(data).await,
+            JsonRpcRequest::AcceptOnL1(data) => self.accept_on_l1(data).await,
             JsonRpcRequest::SetGasPrice(data) => self.set_gas_price(data).await,
             JsonRpcRequest::Restart => self.restart().await,
+            JsonRpcRequest::Snapshot => self.snapshot().await,
+            JsonRpcRequest::Revert(SnapshotId { snapshot_id }) => self.revert(snapshot_id).await,
             JsonRpcRequest::SetTime(data) => self.set_time(data).await,
+            JsonRpcRequest::SetStorageAt(data) => self.set_storage_at(data).await,
+            JsonRpcRequest::SetNonce(data) => self.set_nonce(data).await,
+            JsonRpcRequest::ReplaceClass(data) => self.replace_class(data).await,
+            JsonRpcRequest::DeployAt(data) => self.deploy_at(data).await,
             JsonRpcRequest::IncreaseTime(data) => self.increase_time(data).await,
             JsonRpcRequest::PredeployedAccounts(data) => self.get_predeployed_accounts(data).await,
             JsonRpcRequest::AccountBalance(data) => self.get_account_balance(data).await,
@@ -271,7 +301,12 @@ impl JsonRpcHandler {
             }
         }
 
-        starknet_resp.to_rpc_result()
+        match starknet_resp.to_rpc_result() {
+            ResponseResult::Success(result) => ResponseResult::Success(
+                self.rpc_spec_version.convert_result(&original_call.method, result),
+            ),
+            error => error,
+        }
     }
 }
 
@@ -291,6 +326,8 @@ pub enum JsonRpcRequest {
     StateUpdate(BlockIdInput),
     #[serde(rename = "starknet_getStorageAt")]
     StorageAt(GetStorageInput),
+    #[serde(rename = "starknet_getStorageProof")]
+    StorageProof(GetStorageProofInput),
     #[serde(rename = "starknet_getTransactionByHash")]
     TransactionByHash(TransactionHashInput),
     #[serde(rename = "starknet_getTransactionByBlockIdAndIndex")]
@@ -301,6 +338,10 @@ pub enum JsonRpcRequest {
     TransactionStatusByHash(TransactionHashInput),
     #[serde(rename = "starknet_getClass")]
     ClassByHash(BlockAndClassHashInput),
+    #[serde(rename = "starknet_getCompiledCasm")]
+    CompiledCasm(ClassHashInput),
+    #[serde(rename = "starknet_getMessagesStatus")]
+    MessagesStatusByL1Hash(L1TransactionHashInput),
     #[serde(rename = "starknet_getClassHashAt")]
     ClassHashAtContractAddress(BlockAndContractAddressInput),
     #[serde(rename = "starknet_getClassAt")]
@@ -361,12 +402,26 @@ pub enum JsonRpcRequest {
     CreateBlock,
     #[serde(rename = "devnet_abortBlocks")]
     AbortBlocks(AbortingBlocks),
+    #[serde(rename = "devnet_acceptOnL1")]
+    AcceptOnL1(AcceptingOnL1),
     #[serde(rename = "devnet_setGasPrice")]
     SetGasPrice(GasModificationRequest),
     #[serde(rename = "devnet_restart", with = "empty_params")]
     Restart,
+    #[serde(rename = "devnet_snapshot", with = "empty_params")]
+    Snapshot,
+    #[serde(rename = "devnet_revert")]
+    Revert(SnapshotId),
     #[serde(rename = "devnet_setTime")]
     SetTime(SetTime),
+    #[serde(rename = "devnet_setStorageAt")]
+    SetStorageAt(SetStorageAt),
+    #[serde(rename = "devnet_setNonce")]
+    SetNonce(SetNonce),
+    #[serde(rename = "devnet_replaceClass")]
+    ReplaceClass(ReplaceClass),
+    #[serde(rename = "devnet_deployAt")]
+    DeployAt(DeployAt),
     #[serde(rename = "devnet_increaseTime")]
     IncreaseTime(IncreaseTime),
     #[serde(rename = "devnet_getPredeployedAccounts", with = "optional_params")]
@@ -390,6 +445,7 @@ impl std::fmt::Display for JsonRpcRequest {
             JsonRpcRequest::BlockWithReceipts(_) => write!(f, "starknet_getBlockWithReceipts"),
             JsonRpcRequest::StateUpdate(_) => write!(f, "starknet_getStateUpdate"),
             JsonRpcRequest::StorageAt(_) => write!(f, "starknet_getStorageAt"),
+            JsonRpcRequest::StorageProof(_) => write!(f, "starknet_getStorageProof"),
             JsonRpcRequest::TransactionByHash(_) => write!(f, "starknet_getTransactionByHash"),
             JsonRpcRequest::TransactionStatusByHash(_) => {
                 write!(f, "starknet_getTransactionStatus")
@@ -401,6 +457,8 @@ impl std::fmt::Display for JsonRpcRequest {
                 write!(f, "starknet_getTransactionReceipt")
             }
             JsonRpcRequest::ClassByHash(_) => write!(f, "starknet_getClass"),
+            JsonRpcRequest::CompiledCasm(_) => write!(f, "starknet_getCompiledCasm"),
+            JsonRpcRequest::MessagesStatusByL1Hash(_) => write!(f, "starknet_getMessagesStatus"),
             JsonRpcRequest::ClassHashAtContractAddress(_) => write!(f, "starknet_getClassHashAt"),
             JsonRpcRequest::ClassAtContractAddress(_) => write!(f, "starknet_getClassAt"),
             JsonRpcRequest::BlockTransactionCount(_) => {
@@ -445,9 +503,16 @@ impl std::fmt::Display for JsonRpcRequest {
             }
             JsonRpcRequest::CreateBlock => write!(f, "devnet_createBlock"),
             JsonRpcRequest::AbortBlocks(_) => write!(f, "devnet_abortBlocks"),
+            JsonRpcRequest::AcceptOnL1(_) => write!(f, "devnet_acceptOnL1"),
             JsonRpcRequest::SetGasPrice(_) => write!(f, "devnet_setGasPrice"),
             JsonRpcRequest::Restart => write!(f, "devnet_restart"),
+            JsonRpcRequest::Snapshot => write!(f, "devnet_snapshot"),
+            JsonRpcRequest::Revert(_) => write!(f, "devnet_revert"),
             JsonRpcRequest::SetTime(_) => write!(f, "devnet_setTime"),
+            JsonRpcRequest::SetStorageAt(_) => write!(f, "devnet_setStorageAt"),
+            JsonRpcRequest::SetNonce(_) => write!(f, "devnet_setNonce"),
+            JsonRpcRequest::ReplaceClass(_) => write!(f, "devnet_replaceClass"),
+            JsonRpcRequest::DeployAt(_) => write!(f, "devnet_deployAt"),
             JsonRpcRequest::IncreaseTime(_) => write!(f, "devnet_increaseTime"),
             JsonRpcRequest::PredeployedAccounts(_) => write!(f, "devnet_getPredeployedAccounts"),
             JsonRpcRequest::AccountBalance(_) => write!(f, "devnet_getAccountBalance"),
@@ -505,6 +570,9 @@ pub enum StarknetResponse {
     SimulateTransactions(Vec<SimulatedTransaction>),
     TraceTransaction(TransactionTrace),
     BlockTransactionTraces(Vec<BlockTransactionTrace>),
+    StorageProof(StorageProof),
+    CompiledCasm(CompiledClass),
+    MessagesStatusByL1Hash(Vec<L1HandlerTransactionStatusOutput>),
 }
 
 #[derive(Serialize)]
@@ -515,7 +583,9 @@ pub enum DevnetResponse {
     MessageHash(MessageHash),
     CreatedBlock(CreatedBlock),
     AbortedBlocks(AbortedBlocks),
+    AcceptedOnL1(AcceptedOnL1),
     GasModification(GasModification),
+    Snapshot(SnapshotId),
     SetTime(SetTimeResponse),
     IncreaseTime(IncreaseTimeResponse),
     TransactionHash(TransactionHashOutput),
@@ -589,6 +659,54 @@ mod requests_tests {
         );
     }
 
+    #[test]
+    fn deserialize_get_storage_proof_request() {
+        let json_str = r#"{"method":"starknet_getStorageProof","params":{"block_id":"latest","class_hashes":["0x1"],"contract_addresses":["0x2"],"contracts_storage_keys":[{"contract_address":"0x2","storage_keys":["0x3"]}]}}"#;
+        assert_deserialization_succeeds(json_str);
+
+        // all params except block_id are optional
+        assert_deserialization_succeeds(
+            r#"{"method":"starknet_getStorageProof","params":{"block_id":"latest"}}"#,
+        );
+
+        assert_deserialization_fails(
+            &json_str.replace(r#""storage_keys""#, r#""keys""#),
+            "unknown field `keys`",
+        );
+    }
+
+    #[test]
+    fn deserialize_get_compiled_casm_request() {
+        assert_deserialization_succeeds(
+            r#"{"method":"starknet_getCompiledCasm","params":{"class_hash":"0x1"}}"#,
+        );
+        assert_deserialization_succeeds(
+            r#"{"method":"starknet_getCompiledCasm","params":["0x1"]}"#,
+        );
+
+        assert_deserialization_fails(
+            r#"{"method":"starknet_getCompiledCasm","params":{"block_id":"latest","class_hash":"0x1"}}"#,
+            "unknown field `block_id`",
+        );
+    }
+
+    #[test]
+    fn deserialize_get_messages_status_request() {
+        assert_deserialization_succeeds(
+            r#"{"method":"starknet_getMessagesStatus","params":{"transaction_hash":"0x1"}}"#,
+        );
+
+        // an L1 transaction hash is not a felt, so it can exceed the felt range
+        assert_deserialization_succeeds(
+            r#"{"method":"starknet_getMessagesStatus","params":{"transaction_hash":"0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"}}"#,
+        );
+
+        assert_deserialization_fails(
+            r#"{"method":"starknet_getMessagesStatus","params":{"transaction_hash":"0x1","block_id":"latest"}}"#,
+            "unknown field `block_id`",
+        );
+    }
+
     #[test]
     fn deserialize_get_transaction_by_hash_request() {
         let json_str = r#"{"method":"starknet_getTransactionByHash","params":{"transaction_hash":"0x134134"}}"#;
@@ -1081,6 +1199,76 @@ mod requests_tests {
         }
     }
 
+    #[test]
+    fn deserialize_snapshot_and_revert_requests() {
+        for body in [
+            json!({ "method": "devnet_snapshot" }),
+            json!({ "method": "devnet_snapshot", "params": {} }),
+            json!({ "method": "devnet_revert", "params": { "snapshot_id": 1 } }),
+        ] {
+            assert_deserialization_succeeds(body.to_string().as_str())
+        }
+
+        assert_deserialization_fails(
+            json!({ "method": "devnet_revert", "params": {} }).to_string().as_str(),
+            "missing field `snapshot_id`",
+        );
+    }
+
+    #[test]
+    fn deserialize_state_modification_requests() {
+        // for each method: valid params and invalid params with the expected error
+        let cases = [
+            (
+                "devnet_setStorageAt",
+                vec![json!({ "contract_address": "0x1", "key": "0x2", "value": "0x3" })],
+                json!({ "contract_address": "0x1", "key": "0x2" }),
+                "missing field `value`",
+            ),
+            (
+                "devnet_setNonce",
+                vec![json!({ "contract_address": "0x1", "nonce": "0x2" })],
+                json!({ "contract_address": "0x1" }),
+                "missing field `nonce`",
+            ),
+            (
+                "devnet_replaceClass",
+                vec![json!({ "contract_address": "0x1", "class_hash": "0x2" })],
+                json!({ "contract_address": "0x1" }),
+                "missing field `class_hash`",
+            ),
+            (
+                "devnet_deployAt",
+                vec![
+                    json!({ "contract_address": "0x1", "class_hash": "0x2" }),
+                    json!({
+                        "contract_address": "0x1",
+                        "class_hash": "0x2",
+                        "storage": [{ "key": "0x3", "value": "0x4" }]
+                    }),
+                ],
+                json!({
+                    "contract_address": "0x1",
+                    "class_hash": "0x2",
+                    "storage": { "0x3": "0x4" }
+                }),
+                "invalid type: map, expected a sequence",
+            ),
+        ];
+
+        for (method, valid_params, invalid_params, expected_msg) in cases {
+            for params in valid_params {
+                assert_deserialization_succeeds(
+                    json!({ "method": method, "params": params }).to_string().as_str(),
+                );
+            }
+            assert_deserialization_fails(
+                json!({ "method": method, "params": invalid_params }).to_string().as_str(),
+                expected_msg,
+            );
+        }
+    }
+
     #[test]
     fn deserialize_devnet_methods_with_optional_body() {
         for mut body in [
@@ -1155,8 +1343,8 @@ mod response_tests {
     use crate::api::json_rpc::ToRpcResponseResult;
 
     #[test]
-    fn serializing_starknet_response_empty_variant_has_to_produce_empty_json_object_when_converted_to_rpc_result()
-     {
+    fn serializing_starknet_response_empty_variant_has_to_produce_empty_json_object_when_converted_to_rpc_result(
+    ) {
         assert_eq!(
             r#"{"result":{}}"#,
             serde_json::to_string(
@@ -0,0 +1,391 @@
+use serde_json::{json, Value};
+
+/// Starknet methods introduced in v0.8, not served via the routes of older versions
+const METHODS_SINCE_V0_8: [&str; 3] =
+    ["starknet_getStorageProof", "starknet_getCompiledCasm", "starknet_getMessagesStatus"];
+
+/// Invocations of a transaction trace, as named in the specification
+const TRACE_INVOCATIONS: [&str; 5] = [
+    "validate_invocation",
+    "execute_invocation",
+    "fee_transfer_invocation",
+    "constructor_invocation",
+    "function_invocation",
+];
+
+/// Methods whose params contain broadcasted transactions, with the name and the position of that
+/// param; it holds either a single transaction or an array of them
+const METHODS_WITH_TRANSACTIONS: [(&str, &str, usize); 5] = [
+    ("starknet_addInvokeTransaction", "invoke_transaction", 0),
+    ("starknet_addDeclareTransaction", "declare_transaction", 0),
+    ("starknet_addDeployAccountTransaction", "deploy_account_transaction", 0),
+    ("starknet_estimateFee", "request", 0),
+    ("starknet_simulateTransactions", "transactions", 1),
+];
+
+/// Versions of the JSON-RPC specification that are served side by side, each via its own route.
+/// All of them are handled by the same Starknet instance, only the requests and the responses are
+/// converted according to the version.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RpcSpecVersion {
+    #[default]
+    V0_7,
+    V0_8,
+}
+
+impl RpcSpecVersion {
+    pub const ALL: [RpcSpecVersion; 2] = [RpcSpecVersion::V0_7, RpcSpecVersion::V0_8];
+
+    /// The version as returned by `starknet_specVersion`
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            RpcSpecVersion::V0_7 => starknet_core::constants::RPC_SPEC_VERSION,
+            RpcSpecVersion::V0_8 => "0.8.0",
+        }
+    }
+
+    /// The path of the route via which the version is served
+    pub const fn route_path(&self) -> &'static str {
+        match self {
+            RpcSpecVersion::V0_7 => "/rpc/v0_7",
+            RpcSpecVersion::V0_8 => "/rpc/v0_8",
+        }
+    }
+
+    /// The path of the WebSocket route via which the version is served
+    pub const fn ws_route_path(&self) -> &'static str {
+        match self {
+            RpcSpecVersion::V0_7 => "/ws/v0_7",
+            RpcSpecVersion::V0_8 => "/ws/v0_8",
+        }
+    }
+
+    /// Returns false for Starknet methods not defined in this version of the specification. Devnet
+    /// methods are supported by all versions.
+    pub(crate) fn supports_method(&self, method: &str) -> bool {
+        match self {
+            RpcSpecVersion::V0_7 => !METHODS_SINCE_V0_8.contains(&method),
+            RpcSpecVersion::V0_8 => true,
+        }
+    }
+
+    /// Converts the params of `method`, provided in the format of this version, to the format of
+    /// v0.7, which requests are deserialized from.
+    pub(crate) fn convert_params(&self, method: &str, params: Value) -> Value {
+        match self {
+            RpcSpecVersion::V0_7 => params,
+            RpcSpecVersion::V0_8 => from_v0_8_params(method, params),
+        }
+    }
+
+    /// Converts the result of `method`, produced in the format of v0.7, to the format of this
+    /// version. The results of subscription notifications are converted by their method as well.
+    pub(crate) fn convert_result(&self, method: &str, result: Value) -> Value {
+        match self {
+            RpcSpecVersion::V0_7 => result,
+            RpcSpecVersion::V0_8 => to_v0_8_result(method, result),
+        }
+    }
+}
+
+/// The resource bounds of v0.8 transactions also bound L1 data gas, which is not bounded separately
+/// by the execution of Devnet; the fee of L1 data gas is limited by the L1 gas bounds instead.
+fn from_v0_8_params(method: &str, mut params: Value) -> Value {
+    let Some((_, name, position)) = METHODS_WITH_TRANSACTIONS
+        .iter()
+        .find(|(transaction_method, ..)| *transaction_method == method)
+    else {
+        return params;
+    };
+
+    let transactions = match &mut params {
+        Value::Object(named_params) => named_params.get_mut(*name),
+        Value::Array(positional_params) => positional_params.get_mut(*position),
+        _ => None,
+    };
+    match transactions {
+        Some(Value::Array(transactions)) => {
+            transactions.iter_mut().for_each(remove_l1_data_gas_bounds)
+        }
+        Some(transaction) => remove_l1_data_gas_bounds(transaction),
+        None => (),
+    }
+
+    params
+}
+
+/// Devnet doesn't store the L1 data gas bounds of transactions, so they are reported as zero
+fn add_l1_data_gas_bounds(transaction: &mut Value) {
+    if let Some(resource_bounds) =
+        transaction.get_mut("resource_bounds").and_then(Value::as_object_mut)
+    {
+        resource_bounds.insert(
+            "l1_data_gas".into(),
+            json!({ "max_amount": "0x0", "max_price_per_unit": "0x0" }),
+        );
+    }
+}
+
+fn remove_l1_data_gas_bounds(transaction: &mut Value) {
+    if let Some(resource_bounds) =
+        transaction.get_mut("resource_bounds").and_then(Value::as_object_mut)
+    {
+        resource_bounds.remove("l1_data_gas");
+    }
+}
+
+/// Since Devnet doesn't charge L2 gas, the L2 gas prices and amounts of v0.8 are always zero.
+fn to_v0_8_result(method: &str, mut result: Value) -> Value {
+    match method {
+        "starknet_getBlockWithTxHashes" | "starknet_subscriptionNewHeads" => {
+            add_l2_gas_price(&mut result)
+        }
+        "starknet_getBlockWithTxs" => {
+            add_l2_gas_price(&mut result);
+            array_items(&mut result["transactions"]).for_each(add_l1_data_gas_bounds);
+        }
+        "starknet_getBlockWithReceipts" => {
+            add_l2_gas_price(&mut result);
+            for transaction in array_items(&mut result["transactions"]) {
+                add_l1_data_gas_bounds(&mut transaction["transaction"]);
+                convert_execution_resources(&mut transaction["receipt"]);
+            }
+        }
+        "starknet_getTransactionByHash"
+        | "starknet_getTransactionByBlockIdAndIndex"
+        | "starknet_subscriptionPendingTransactions" => add_l1_data_gas_bounds(&mut result),
+        "starknet_getTransactionReceipt" => convert_execution_resources(&mut result),
+        "starknet_estimateFee" => array_items(&mut result).for_each(convert_fee_estimate),
+        "starknet_estimateMessageFee" => convert_fee_estimate(&mut result),
+        "starknet_simulateTransactions" => {
+            for simulated_transaction in array_items(&mut result) {
+                convert_fee_estimate(&mut simulated_transaction["fee_estimation"]);
+                convert_trace(&mut simulated_transaction["transaction_trace"]);
+            }
+        }
+        "starknet_traceTransaction" => convert_trace(&mut result),
+        "starknet_traceBlockTransactions" => {
+            for block_transaction_trace in array_items(&mut result) {
+                convert_trace(&mut block_transaction_trace["trace_root"]);
+            }
+        }
+        _ => (),
+    }
+
+    result
+}
+
+fn array_items(value: &mut Value) -> impl Iterator<Item = &mut Value> {
+    value.as_array_mut().into_iter().flatten()
+}
+
+fn add_l2_gas_price(block_header: &mut Value) {
+    if let Some(block_header) = block_header.as_object_mut() {
+        block_header
+            .insert("l2_gas_price".into(), json!({ "price_in_fri": "0x0", "price_in_wei": "0x0" }));
+    }
+}
+
+/// Replaces the computation resources and the data availability of v0.7 with the gas amounts of
+/// v0.8. Applicable to receipts and traces.
+fn convert_execution_resources(parent: &mut Value) {
+    if let Some(execution_resources) = parent.get_mut("execution_resources") {
+        let data_availability = &execution_resources["data_availability"];
+        let gas = json!({
+            "l1_gas": data_availability["l1_gas"],
+            "l1_data_gas": data_availability["l1_data_gas"],
+            "l2_gas": 0,
+        });
+        *execution_resources = gas;
+    }
+}
+
+fn convert_fee_estimate(fee_estimate: &mut Value) {
+    let Some(fee_estimate) = fee_estimate.as_object_mut() else {
+        return;
+    };
+
+    for (old_name, new_name) in [
+        ("gas_consumed", "l1_gas_consumed"),
+        ("gas_price", "l1_gas_price"),
+        ("data_gas_consumed", "l1_data_gas_consumed"),
+        ("data_gas_price", "l1_data_gas_price"),
+    ] {
+        if let Some(value) = fee_estimate.remove(old_name) {
+            fee_estimate.insert(new_name.into(), value);
+        }
+    }
+    fee_estimate.insert("l2_gas_consumed".into(), json!("0x0"));
+    fee_estimate.insert("l2_gas_price".into(), json!("0x0"));
+}
+
+fn convert_trace(trace: &mut Value) {
+    convert_execution_resources(trace);
+    for invocation_name in TRACE_INVOCATIONS {
+        if let Some(invocation) = trace.get_mut(invocation_name) {
+            convert_function_invocation(invocation);
+        }
+    }
+}
+
+/// Reverted executions have no execution resources and are left as they are. Devnet doesn't
+/// track the gas of individual calls, so it is reported as zero.
+fn convert_function_invocation(invocation: &mut Value) {
+    let Some(invocation) = invocation.as_object_mut() else {
+        return;
+    };
+
+    if invocation.contains_key("execution_resources") {
+        invocation.insert("execution_resources".into(), json!({ "l1_gas": 0, "l2_gas": 0 }));
+        invocation.insert("is_reverted".into(), json!(false));
+    }
+    if let Some(calls) = invocation.get_mut("calls") {
+        array_items(calls).for_each(convert_function_invocation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::RpcSpecVersion;
+
+    #[test]
+    fn methods_since_v0_8_are_not_supported_by_v0_7() {
+        for method in ["starknet_getStorageProof", "starknet_getCompiledCasm"] {
+            assert!(!RpcSpecVersion::V0_7.supports_method(method));
+            assert!(RpcSpecVersion::V0_8.supports_method(method));
+        }
+
+        for method in ["starknet_getBlockWithTxHashes", "devnet_mint"] {
+            assert!(RpcSpecVersion::V0_7.supports_method(method));
+            assert!(RpcSpecVersion::V0_8.supports_method(method));
+        }
+    }
+
+    #[test]
+    fn v0_7_results_are_not_converted() {
+        let receipt = json!({ "execution_resources": { "steps": 1 } });
+        assert_eq!(
+            RpcSpecVersion::V0_7.convert_result("starknet_getTransactionReceipt", receipt.clone()),
+            receipt
+        );
+    }
+
+    #[test]
+    fn l1_data_gas_bounds_converted_between_v0_7_and_v0_8() {
+        let v0_7_bounds = json!({
+            "l1_gas": { "max_amount": "0x1", "max_price_per_unit": "0x2" },
+            "l2_gas": { "max_amount": "0x0", "max_price_per_unit": "0x0" }
+        });
+        let mut v0_8_bounds = v0_7_bounds.clone();
+        v0_8_bounds["l1_data_gas"] = json!({ "max_amount": "0x3", "max_price_per_unit": "0x4" });
+        let transaction = |resource_bounds: &serde_json::Value| json!({ "type": "INVOKE", "version": "0x3", "resource_bounds": resource_bounds });
+
+        assert_eq!(
+            RpcSpecVersion::V0_8.convert_params(
+                "starknet_addInvokeTransaction",
+                json!({ "invoke_transaction": transaction(&v0_8_bounds) })
+            ),
+            json!({ "invoke_transaction": transaction(&v0_7_bounds) })
+        );
+        assert_eq!(
+            RpcSpecVersion::V0_8.convert_params(
+                "starknet_simulateTransactions",
+                json!(["latest", [transaction(&v0_8_bounds)], []])
+            ),
+            json!(["latest", [transaction(&v0_7_bounds)], []])
+        );
+
+        let mut v0_8_bounds_without_data_gas = v0_7_bounds.clone();
+        v0_8_bounds_without_data_gas["l1_data_gas"] =
+            json!({ "max_amount": "0x0", "max_price_per_unit": "0x0" });
+        assert_eq!(
+            RpcSpecVersion::V0_8
+                .convert_result("starknet_getTransactionByHash", transaction(&v0_7_bounds)),
+            transaction(&v0_8_bounds_without_data_gas)
+        );
+
+        let params = json!({ "request": [transaction(&v0_8_bounds)], "block_id": "latest" });
+        assert_eq!(
+            RpcSpecVersion::V0_7.convert_params("starknet_estimateFee", params.clone()),
+            params
+        );
+    }
+
+    #[test]
+    fn receipt_and_fee_estimate_converted_to_v0_8() {
+        let receipt = json!({
+            "transaction_hash": "0x1",
+            "execution_resources": {
+                "steps": 100,
+                "data_availability": { "l1_gas": 0, "l1_data_gas": 128 }
+            }
+        });
+        assert_eq!(
+            RpcSpecVersion::V0_8.convert_result("starknet_getTransactionReceipt", receipt),
+            json!({
+                "transaction_hash": "0x1",
+                "execution_resources": { "l1_gas": 0, "l1_data_gas": 128, "l2_gas": 0 }
+            })
+        );
+
+        let fee_estimate = json!({
+            "gas_consumed": "0x1",
+            "gas_price": "0x2",
+            "data_gas_consumed": "0x3",
+            "data_gas_price": "0x4",
+            "overall_fee": "0xe",
+            "unit": "WEI"
+        });
+        assert_eq!(
+            RpcSpecVersion::V0_8.convert_result("starknet_estimateFee", json!([fee_estimate])),
+            json!([{
+                "l1_gas_consumed": "0x1",
+                "l1_gas_price": "0x2",
+                "l2_gas_consumed": "0x0",
+                "l2_gas_price": "0x0",
+                "l1_data_gas_consumed": "0x3",
+                "l1_data_gas_price": "0x4",
+                "overall_fee": "0xe",
+                "unit": "WEI"
+            }])
+        );
+    }
+
+    #[test]
+    fn trace_converted_to_v0_8() {
+        let computation_resources = json!({ "steps": 10 });
+        let trace = json!({
+            "type": "INVOKE",
+            "execute_invocation": { "revert_reason": "reverted" },
+            "validate_invocation": {
+                "calls": [{ "calls": [], "execution_resources": computation_resources }],
+                "execution_resources": computation_resources
+            },
+            "execution_resources": {
+                "steps": 20,
+                "data_availability": { "l1_gas": 1, "l1_data_gas": 2 }
+            }
+        });
+
+        let inner_resources = json!({ "l1_gas": 0, "l2_gas": 0 });
+        assert_eq!(
+            RpcSpecVersion::V0_8.convert_result("starknet_traceTransaction", trace),
+            json!({
+                "type": "INVOKE",
+                "execute_invocation": { "revert_reason": "reverted" },
+                "validate_invocation": {
+                    "calls": [{
+                        "calls": [],
+                        "execution_resources": inner_resources,
+                        "is_reverted": false
+                    }],
+                    "execution_resources": inner_resources,
+                    "is_reverted": false
+                },
+                "execution_resources": { "l1_gas": 1, "l1_data_gas": 2, "l2_gas": 0 }
+            })
+        );
+    }
+}
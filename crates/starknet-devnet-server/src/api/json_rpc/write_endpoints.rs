@@ -11,7 +11,9 @@ use super::models::{
     DeclareTransactionOutput, DeployAccountTransactionOutput, TransactionHashOutput,
 };
 use super::{DevnetResponse, StarknetResponse};
-use crate::api::http::endpoints::blocks::{abort_blocks_impl, create_block_impl};
+use crate::api::http::endpoints::blocks::{
+    abort_blocks_impl, accept_on_l1_impl, create_block_impl,
+};
 use crate::api::http::endpoints::dump_load::{dump_impl, load_impl};
 use crate::api::http::endpoints::mint_token::mint_impl;
 use crate::api::http::endpoints::postman::{
@@ -21,8 +23,9 @@ use crate::api::http::endpoints::postman::{
 use crate::api::http::endpoints::restart_impl;
 use crate::api::http::endpoints::time::{increase_time_impl, set_time_impl};
 use crate::api::http::models::{
-    AbortingBlocks, DumpPath, FlushParameters, IncreaseTime, LoadPath, MintTokensRequest,
-    PostmanLoadL1MessagingContract, SetTime,
+    AbortingBlocks, AcceptingOnL1, DeployAt, DumpPath, FlushParameters, IncreaseTime, LoadPath,
+    MintTokensRequest, PostmanLoadL1MessagingContract, ReplaceClass, SetNonce, SetStorageAt,
+    SetTime, SnapshotId,
 };
 use crate::api::json_rpc::JsonRpcHandler;
 
@@ -160,6 +163,13 @@ impl JsonRpcHandler {
         Ok(DevnetResponse::AbortedBlocks(aborted_blocks).into())
     }
 
+    /// devnet_acceptOnL1
+    pub async fn accept_on_l1(&self, data: AcceptingOnL1) -> StrictRpcResult {
+        let accepted_blocks = accept_on_l1_impl(&self.api, data).await.map_err(ApiError::from)?;
+
+        Ok(DevnetResponse::AcceptedOnL1(accepted_blocks).into())
+    }
+
     /// devnet_setGasPrice
     pub async fn set_gas_price(&self, data: GasModificationRequest) -> StrictRpcResult {
         let modified_gas =
@@ -175,12 +185,83 @@ impl JsonRpcHandler {
         Ok(super::JsonRpcResponse::Empty)
     }
 
+    /// devnet_snapshot
+    pub async fn snapshot(&self) -> StrictRpcResult {
+        let snapshot_id = self.api.starknet.lock().await.snapshot()?;
+
+        Ok(DevnetResponse::Snapshot(SnapshotId { snapshot_id }).into())
+    }
+
+    /// devnet_revert
+    pub async fn revert(&self, snapshot_id: u64) -> StrictRpcResult {
+        self.api.starknet.lock().await.revert(snapshot_id)?;
+
+        Ok(super::JsonRpcResponse::Empty)
+    }
+
     /// devnet_setTime
     pub async fn set_time(&self, data: SetTime) -> StrictRpcResult {
         let set_time_response = set_time_impl(&self.api, data).await.map_err(ApiError::from)?;
         Ok(DevnetResponse::SetTime(set_time_response).into())
     }
 
+    /// devnet_setStorageAt
+    pub async fn set_storage_at(&self, data: SetStorageAt) -> StrictRpcResult {
+        let SetStorageAt { contract_address, key, value } = data;
+        self.api.starknet.lock().await.set_storage_at(contract_address, key, value).map_err(
+            |err| match err {
+                starknet_core::error::Error::ContractNotFound => ApiError::ContractNotFound,
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            },
+        )?;
+
+        Ok(super::JsonRpcResponse::Empty)
+    }
+
+    /// devnet_setNonce
+    pub async fn set_nonce(&self, data: SetNonce) -> StrictRpcResult {
+        let SetNonce { contract_address, nonce } = data;
+        self.api.starknet.lock().await.set_nonce(contract_address, nonce).map_err(
+            |err| match err {
+                starknet_core::error::Error::ContractNotFound => ApiError::ContractNotFound,
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            },
+        )?;
+
+        Ok(super::JsonRpcResponse::Empty)
+    }
+
+    /// devnet_replaceClass
+    pub async fn replace_class(&self, data: ReplaceClass) -> StrictRpcResult {
+        let ReplaceClass { contract_address, class_hash } = data;
+        self.api.starknet.lock().await.replace_class(contract_address, class_hash).map_err(
+            |err| match err {
+                starknet_core::error::Error::ContractNotFound => ApiError::ContractNotFound,
+                starknet_core::error::Error::StateError(
+                    starknet_core::error::StateError::NoneClassHash(_),
+                ) => ApiError::ClassHashNotFound,
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            },
+        )?;
+
+        Ok(super::JsonRpcResponse::Empty)
+    }
+
+    /// devnet_deployAt
+    pub async fn deploy_at(&self, data: DeployAt) -> StrictRpcResult {
+        let DeployAt { contract_address, class_hash, storage } = data;
+        self.api.starknet.lock().await.deploy_at(contract_address, class_hash, storage).map_err(
+            |err| match err {
+                starknet_core::error::Error::StateError(
+                    starknet_core::error::StateError::NoneClassHash(_),
+                ) => ApiError::ClassHashNotFound,
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            },
+        )?;
+
+        Ok(super::JsonRpcResponse::Empty)
+    }
+
     /// devnet_increaseTime
     pub async fn increase_time(&self, data: IncreaseTime) -> StrictRpcResult {
         let increase_time_response =
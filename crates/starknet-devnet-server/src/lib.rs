@@ -5,6 +5,8 @@ pub mod rpc_core;
 /// handlers for axum server
 pub mod rpc_handler;
 pub mod server;
+/// handlers for WebSocket subscriptions
+pub mod subscribe;
 #[cfg(any(test, feature = "test_utils"))]
 pub mod test_utils;
 
@@ -24,6 +24,17 @@ pub trait RpcHandler: Clone + Send + Sync + 'static {
         original_call: RpcMethodCall,
     ) -> ResponseResult;
 
+    /// Returns false if `method` is not served by this handler, even though `Request` has a
+    /// variant for it. Such calls result in Method Not Found, regardless of their params.
+    fn supports_method(&self, _method: &str) -> bool {
+        true
+    }
+
+    /// Converts the params of `method` to the format that `Request` is deserialized from
+    fn convert_params(&self, _method: &str, params: serde_json::Value) -> serde_json::Value {
+        params
+    }
+
     /// Invoked for every incoming `RpcMethodCall`
     ///
     /// This will attempt to deserialize a `{ "method" : "<name>", "params": "<params>" }` message
@@ -36,7 +47,12 @@ pub trait RpcHandler: Clone + Send + Sync + 'static {
         trace!(target: "rpc",  id = ?call.id , method = ?call.method, "received method call");
         let RpcMethodCall { method, params, id, .. } = call.clone();
 
-        let params: serde_json::Value = params.into();
+        if !self.supports_method(&method) {
+            warn!(target: "rpc", ?method, "method not supported by handler");
+            return RpcResponse::new(id, RpcError::method_not_found());
+        }
+
+        let params = self.convert_params(&method, params.into());
         let deserializable_call = serde_json::json!({
             "method": &method,
             "params": params
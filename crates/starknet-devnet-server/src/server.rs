@@ -17,10 +17,10 @@ use tower_http::trace::TraceLayer;
 
 use crate::api::http::{endpoints as http, HttpApiHandler};
 use crate::api::json_rpc::origin_forwarder::OriginForwarder;
-use crate::api::json_rpc::JsonRpcHandler;
+use crate::api::json_rpc::{JsonRpcHandler, RpcSpecVersion};
 use crate::api::Api;
 use crate::rpc_handler::RpcHandler;
-use crate::{rpc_handler, ServerConfig};
+use crate::{rpc_handler, subscribe, ServerConfig};
 pub type StarknetDevnetServer = axum::serve::Serve<IntoMakeService<Router>, Router>;
 
 fn json_rpc_routes<TJsonRpcHandler: RpcHandler>(json_rpc_handler: TJsonRpcHandler) -> Router {
@@ -30,6 +30,32 @@ fn json_rpc_routes<TJsonRpcHandler: RpcHandler>(json_rpc_handler: TJsonRpcHandle
         .with_state(json_rpc_handler)
 }
 
+/// Routes of all supported spec versions, each with its own handler
+fn versioned_json_rpc_routes(json_rpc_handler: &JsonRpcHandler) -> Router {
+    RpcSpecVersion::ALL.into_iter().fold(Router::new(), |routes, rpc_spec_version| {
+        let versioned_handler = JsonRpcHandler { rpc_spec_version, ..json_rpc_handler.clone() };
+        routes.route(
+            rpc_spec_version.route_path(),
+            post(rpc_handler::handle::<JsonRpcHandler>).with_state(versioned_handler),
+        )
+    })
+}
+
+/// The unversioned route is served by the default version
+fn ws_routes(json_rpc_handler: JsonRpcHandler) -> Router {
+    let unversioned_routes = Router::new()
+        .route("/ws", get(subscribe::handle_socket_upgrade))
+        .with_state(json_rpc_handler.clone());
+
+    RpcSpecVersion::ALL.into_iter().fold(unversioned_routes, |routes, rpc_spec_version| {
+        let versioned_handler = JsonRpcHandler { rpc_spec_version, ..json_rpc_handler.clone() };
+        routes.route(
+            rpc_spec_version.ws_route_path(),
+            get(subscribe::handle_socket_upgrade).with_state(versioned_handler),
+        )
+    })
+}
+
 fn http_api_routes(http_api_handler: HttpApiHandler) -> Router {
     Router::new()
         .route("/is_alive", get(http::is_alive))
@@ -44,6 +70,7 @@ fn http_api_routes(http_api_handler: HttpApiHandler) -> Router {
         )
         .route("/create_block", post(http::blocks::create_block))
         .route("/abort_blocks", post(http::blocks::abort_blocks))
+        .route("/accept_on_l1", post(http::blocks::accept_on_l1))
         .route("/restart", post(http::restart))
         .route("/set_time", post(http::time::set_time))
         .route("/increase_time", post(http::time::increase_time))
@@ -54,7 +81,8 @@ fn http_api_routes(http_api_handler: HttpApiHandler) -> Router {
         .with_state(http_api_handler)
 }
 
-/// Configures an [axum::Server] that handles related JSON-RPC calls and WEB API calls via HTTP
+/// Configures an [axum::Server] that handles related JSON-RPC calls and WEB API calls via HTTP, and
+/// JSON-RPC calls and subscriptions via WebSocket
 pub fn serve_http_api_json_rpc(
     tcp_listener: TcpListener,
     api: Api,
@@ -70,12 +98,22 @@ pub fn serve_http_api_json_rpc(
         None
     };
 
-    let json_rpc_handler =
-        JsonRpcHandler { api, origin_caller, server_config: server_config.clone() };
-    let json_rpc_routes = json_rpc_routes(json_rpc_handler);
+    let json_rpc_handler = JsonRpcHandler {
+        api,
+        origin_caller,
+        server_config: server_config.clone(),
+        rpc_spec_version: RpcSpecVersion::default(),
+    };
+    let json_rpc_routes = json_rpc_routes(json_rpc_handler.clone());
+    let versioned_json_rpc_routes = versioned_json_rpc_routes(&json_rpc_handler);
+    let ws_routes = ws_routes(json_rpc_handler);
     let http_api_routes = http_api_routes(http_handler);
 
-    let mut routes = http_api_routes.merge(json_rpc_routes).layer(TraceLayer::new_for_http());
+    let mut routes = http_api_routes
+        .merge(json_rpc_routes)
+        .merge(versioned_json_rpc_routes)
+        .merge(ws_routes)
+        .layer(TraceLayer::new_for_http());
 
     if server_config.log_response {
         routes = routes.layer(axum::middleware::from_fn(response_logging_middleware));
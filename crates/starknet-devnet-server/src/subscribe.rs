@@ -0,0 +1,659 @@
+use std::collections::HashMap;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::IntoResponse;
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use starknet_core::error::Error;
+use starknet_core::starknet::events::check_if_filter_applies_for_event;
+use starknet_core::starknet::notifications::StarknetNotification;
+use starknet_rs_core::types::{BlockId as ImportedBlockId, Felt};
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::emitted_event::Event;
+use starknet_types::felt::TransactionHash;
+use starknet_types::rpc::block::{BlockHeader, BlockId};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tracing::{error, trace, warn};
+
+use crate::api::json_rpc::error::ApiError;
+use crate::api::json_rpc::models::{BlockIdInput, TransactionHashInput, TransactionStatusOutput};
+use crate::api::json_rpc::{JsonRpcHandler, RpcSpecVersion};
+use crate::api::serde_helpers::optional_params;
+use crate::rpc_core::error::RpcError;
+use crate::rpc_core::request::{Request, RpcCall, RpcMethodCall};
+use crate::rpc_core::response::{Response, ResponseResult, RpcResponse};
+use crate::rpc_handler::RpcHandler;
+
+/// The maximum number of blocks that can be backfilled when subscribing
+const MAX_BLOCKS_BACK: u64 = 1024;
+
+pub type SubscriptionId = u64;
+
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(Debug))]
+#[serde(tag = "method", content = "params")]
+pub enum SubscriptionRequest {
+    #[serde(rename = "starknet_subscribeNewHeads", with = "optional_params")]
+    NewHeads(Option<BlockIdInput>),
+    #[serde(rename = "starknet_subscribeEvents", with = "optional_params")]
+    Events(Option<EventsSubscriptionInput>),
+    #[serde(rename = "starknet_subscribeTransactionStatus")]
+    TransactionStatus(TransactionHashInput),
+    #[serde(rename = "starknet_subscribePendingTransactions", with = "optional_params")]
+    PendingTransactions(Option<PendingTransactionsSubscriptionInput>),
+    #[serde(rename = "starknet_unsubscribe")]
+    Unsubscribe(SubscriptionIdInput),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct EventsSubscriptionInput {
+    pub from_address: Option<ContractAddress>,
+    pub keys: Option<Vec<Vec<Felt>>>,
+    pub block_id: Option<BlockId>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PendingTransactionsSubscriptionInput {
+    /// if true, full transactions are sent instead of just their hashes
+    pub transaction_details: Option<bool>,
+    pub sender_address: Option<Vec<ContractAddress>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SubscriptionIdInput {
+    pub subscription_id: SubscriptionId,
+}
+
+/// The kind of data a subscription is interested in
+#[derive(Clone, Debug)]
+enum Subscription {
+    NewHeads,
+    Events { from_address: Option<ContractAddress>, keys: Option<Vec<Vec<Felt>>> },
+    TransactionStatus { transaction_hash: TransactionHash },
+    PendingTransactions { transaction_details: bool, sender_address: Option<Vec<ContractAddress>> },
+}
+
+/// The result of a transaction status message
+#[derive(Serialize)]
+struct TransactionStatusResult {
+    transaction_hash: TransactionHash,
+    status: TransactionStatusOutput,
+}
+
+impl Subscription {
+    /// The method of the messages sent to the subscriber
+    fn notification_method(&self) -> &'static str {
+        match self {
+            Subscription::NewHeads => "starknet_subscriptionNewHeads",
+            Subscription::Events { .. } => "starknet_subscriptionEvents",
+            Subscription::TransactionStatus { .. } => "starknet_subscriptionTransactionStatus",
+            Subscription::PendingTransactions { .. } => "starknet_subscriptionPendingTransactions",
+        }
+    }
+
+    /// Returns the results of the messages that the notification results in; empty if the
+    /// notification is not of interest to the subscription
+    fn notification_results(&self, notification: StarknetNotification) -> Vec<serde_json::Value> {
+        match (self, notification) {
+            (Subscription::NewHeads, StarknetNotification::NewBlock(header)) => {
+                to_json_values(vec![header])
+            }
+            (
+                Subscription::Events { from_address, keys },
+                StarknetNotification::NewEvents(events),
+            ) => to_json_values(
+                events
+                    .into_iter()
+                    .filter(|emitted_event| {
+                        let event = Event {
+                            from_address: emitted_event.from_address,
+                            keys: emitted_event.keys.clone(),
+                            data: emitted_event.data.clone(),
+                        };
+                        check_if_filter_applies_for_event(from_address, keys, &event)
+                    })
+                    .collect(),
+            ),
+            (
+                Subscription::TransactionStatus { transaction_hash: subscribed_hash },
+                StarknetNotification::TransactionStatus {
+                    transaction_hash,
+                    finality_status,
+                    execution_status,
+                    failure_reason,
+                },
+            ) if *subscribed_hash == transaction_hash => {
+                to_json_values(vec![TransactionStatusResult {
+                    transaction_hash,
+                    status: TransactionStatusOutput {
+                        finality_status,
+                        execution_status,
+                        failure_reason,
+                    },
+                }])
+            }
+            (
+                Subscription::PendingTransactions { transaction_details, sender_address },
+                StarknetNotification::PendingTransaction(transaction),
+            ) => {
+                if let Some(sender_address) = sender_address {
+                    match transaction.transaction.get_sender_address() {
+                        Some(address) if sender_address.contains(address) => {}
+                        _ => return vec![],
+                    }
+                }
+
+                if *transaction_details {
+                    to_json_values(vec![transaction])
+                } else {
+                    to_json_values(vec![transaction.get_transaction_hash()])
+                }
+            }
+            _ => vec![],
+        }
+    }
+}
+
+fn to_json_values<T: Serialize>(results: Vec<T>) -> Vec<serde_json::Value> {
+    results
+        .into_iter()
+        .filter_map(|result| {
+            serde_json::to_value(result)
+                .inspect_err(|err| error!(target: "ws", ?err, "failed serializing result"))
+                .ok()
+        })
+        .collect()
+}
+
+/// A subscription that has been accepted, but whose task has not been started yet. The task is
+/// started after the response to the subscription request is sent, so that the client receives the
+/// subscription id before any of the subscription's messages.
+struct PendingSubscription {
+    id: SubscriptionId,
+    subscription: Subscription,
+    receiver: broadcast::Receiver<StarknetNotification>,
+    /// notifications about the state before the subscription was made
+    backfill: Vec<StarknetNotification>,
+    /// the version to whose format the notifications are converted
+    rpc_spec_version: RpcSpecVersion,
+}
+
+/// Subscriptions of a single socket
+#[derive(Default)]
+struct SocketContext {
+    last_subscription_id: SubscriptionId,
+    subscription_tasks: HashMap<SubscriptionId, JoinHandle<()>>,
+}
+
+impl SocketContext {
+    fn next_subscription_id(&mut self) -> SubscriptionId {
+        self.last_subscription_id += 1;
+        self.last_subscription_id
+    }
+
+    fn unsubscribe_all(&mut self) {
+        for (_, task) in self.subscription_tasks.drain() {
+            task.abort();
+        }
+    }
+}
+
+/// Upgrades the connection to a WebSocket which accepts JSON-RPC requests, both the ones served via
+/// HTTP and the subscription requests
+pub async fn handle_socket_upgrade(
+    ws_upgrade: WebSocketUpgrade,
+    State(handler): State<JsonRpcHandler>,
+) -> impl IntoResponse {
+    ws_upgrade.on_upgrade(move |socket| handle_socket(socket, handler))
+}
+
+async fn handle_socket(socket: WebSocket, handler: JsonRpcHandler) {
+    let (mut socket_writer, mut socket_reader) = socket.split();
+
+    // responses and subscription messages are all written by a single task
+    let (message_sender, mut message_receiver) = mpsc::unbounded_channel::<String>();
+    let writer_task = tokio::spawn(async move {
+        while let Some(message) = message_receiver.recv().await {
+            if let Err(err) = socket_writer.send(Message::Text(message)).await {
+                trace!(target: "ws", ?err, "failed writing to socket");
+                break;
+            }
+        }
+    });
+
+    let mut context = SocketContext::default();
+    while let Some(message) = socket_reader.next().await {
+        let text = match message {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(err) => {
+                trace!(target: "ws", ?err, "failed reading from socket");
+                break;
+            }
+        };
+
+        let (response, pending_subscriptions) =
+            handle_socket_request(&text, &handler, &mut context).await;
+        if let Some(response) = response {
+            match serde_json::to_string(&response) {
+                Ok(serialized_response) => {
+                    if message_sender.send(serialized_response).is_err() {
+                        break;
+                    }
+                }
+                Err(err) => error!(target: "ws", ?err, "failed serializing response"),
+            }
+        }
+
+        for pending_subscription in pending_subscriptions {
+            let id = pending_subscription.id;
+            let task = tokio::spawn(run_subscription(pending_subscription, message_sender.clone()));
+            context.subscription_tasks.insert(id, task);
+        }
+    }
+
+    context.unsubscribe_all();
+    writer_task.abort();
+}
+
+/// Handles a single or a batch request received via socket. Returns the response, unless only
+/// notifications were received, and the subscriptions that were made.
+async fn handle_socket_request(
+    text: &str,
+    handler: &JsonRpcHandler,
+    context: &mut SocketContext,
+) -> (Option<Response>, Vec<PendingSubscription>) {
+    let (calls, is_batch) = match serde_json::from_str::<Request>(text) {
+        Ok(Request::Single(call)) => (vec![call], false),
+        Ok(Request::Batch(calls)) => (calls, true),
+        Err(err) => {
+            warn!(target: "ws", ?err, "invalid request");
+            return (Some(Response::error(RpcError::parse_error())), vec![]);
+        }
+    };
+
+    let mut responses = vec![];
+    let mut pending_subscriptions = vec![];
+    for call in calls {
+        match call {
+            RpcCall::MethodCall(call) => {
+                let (response, pending_subscription) =
+                    handle_socket_call(call, handler, context).await;
+                responses.push(response);
+                pending_subscriptions.extend(pending_subscription);
+            }
+            RpcCall::Notification(notification) => {
+                trace!(target: "ws", method = ?notification.method, "received rpc notification");
+            }
+            RpcCall::Invalid { id } => responses.push(RpcResponse::invalid_request(id)),
+        }
+    }
+
+    let response = if is_batch {
+        (!responses.is_empty()).then_some(Response::Batch(responses))
+    } else {
+        responses.pop().map(Response::Single)
+    };
+
+    (response, pending_subscriptions)
+}
+
+/// Handles subscription requests and forwards all other requests to the JSON-RPC handler
+async fn handle_socket_call(
+    call: RpcMethodCall,
+    handler: &JsonRpcHandler,
+    context: &mut SocketContext,
+) -> (RpcResponse, Option<PendingSubscription>) {
+    let params: serde_json::Value = call.params.clone().into();
+    let deserializable_call = serde_json::json!({
+        "method": &call.method,
+        "params": params
+    });
+
+    let request = match serde_json::from_value::<SubscriptionRequest>(deserializable_call) {
+        Ok(request) => request,
+        Err(err) => {
+            let err = err.to_string();
+            // the same way of distinguishing an unknown method as in `RpcHandler::on_call`
+            if err.contains(&format!("unknown variant `{}`", call.method)) {
+                return (handler.clone().on_call(call).await, None);
+            }
+
+            error!(target: "ws", method = ?call.method, ?err, "failed to deserialize method");
+            return (RpcResponse::new(call.id, RpcError::invalid_params(err)), None);
+        }
+    };
+
+    let subscription_result = match request {
+        SubscriptionRequest::NewHeads(block_id_input) => {
+            let block_id = block_id_input.map(|BlockIdInput { block_id }| block_id);
+            subscribe(handler, context, Subscription::NewHeads, block_id).await
+        }
+        SubscriptionRequest::Events(input) => {
+            let EventsSubscriptionInput { from_address, keys, block_id } =
+                input.unwrap_or_default();
+            subscribe(handler, context, Subscription::Events { from_address, keys }, block_id).await
+        }
+        SubscriptionRequest::TransactionStatus(TransactionHashInput { transaction_hash }) => {
+            let subscription = Subscription::TransactionStatus { transaction_hash };
+            subscribe(handler, context, subscription, None).await
+        }
+        SubscriptionRequest::PendingTransactions(input) => {
+            let PendingTransactionsSubscriptionInput { transaction_details, sender_address } =
+                input.unwrap_or_default();
+            let subscription = Subscription::PendingTransactions {
+                transaction_details: transaction_details.unwrap_or_default(),
+                sender_address,
+            };
+            subscribe(handler, context, subscription, None).await
+        }
+        SubscriptionRequest::Unsubscribe(SubscriptionIdInput { subscription_id }) => {
+            let result = match context.subscription_tasks.remove(&subscription_id) {
+                Some(task) => {
+                    task.abort();
+                    ResponseResult::success(true)
+                }
+                None => ApiError::InvalidSubscriptionId.api_error_to_rpc_error().into(),
+            };
+            return (RpcResponse::new(call.id, result), None);
+        }
+    };
+
+    match subscription_result {
+        Ok(pending_subscription) => (
+            RpcResponse::new(call.id, ResponseResult::success(pending_subscription.id)),
+            Some(pending_subscription),
+        ),
+        Err(err) => (RpcResponse::new(call.id, err.api_error_to_rpc_error()), None),
+    }
+}
+
+/// Starts receiving notifications and collects the notifications about the blocks starting from
+/// the optionally provided block id, or about the current status of the subscribed transaction.
+/// Both happen while Starknet is locked, so no notification is missed or received twice.
+async fn subscribe(
+    handler: &JsonRpcHandler,
+    context: &mut SocketContext,
+    subscription: Subscription,
+    block_id: Option<BlockId>,
+) -> Result<PendingSubscription, ApiError> {
+    let starknet = handler.api.starknet.lock().await;
+
+    let mut backfill = vec![];
+    if let Some(block_id) = block_id {
+        let map_block_error = |err| match err {
+            Error::NoBlock => ApiError::BlockNotFound,
+            other => ApiError::StarknetDevnetError(other),
+        };
+
+        let starting_block_number =
+            starknet.get_block(block_id.as_ref()).map_err(map_block_error)?.block_number().0;
+        let latest_block_number = starknet.get_latest_block()?.block_number().0;
+        if latest_block_number.saturating_sub(starting_block_number) > MAX_BLOCKS_BACK {
+            return Err(ApiError::TooManyBlocksBack);
+        }
+
+        match &subscription {
+            Subscription::NewHeads => {
+                for block_number in starting_block_number..=latest_block_number {
+                    let block = starknet
+                        .get_block(&ImportedBlockId::Number(block_number))
+                        .map_err(map_block_error)?;
+                    let header = Box::new(BlockHeader::from(block));
+                    backfill.push(StarknetNotification::NewBlock(header));
+                }
+            }
+            Subscription::Events { from_address, keys } => {
+                let (events, _) = starknet
+                    .get_events(
+                        Some(ImportedBlockId::Number(starting_block_number)),
+                        Some(ImportedBlockId::Number(latest_block_number)),
+                        *from_address,
+                        keys.clone(),
+                        0,
+                        None,
+                    )
+                    .map_err(map_block_error)?;
+                backfill.push(StarknetNotification::NewEvents(events));
+            }
+            Subscription::TransactionStatus { .. } | Subscription::PendingTransactions { .. } => {}
+        }
+    }
+
+    // the current status is sent right away, because it might never change again
+    if let Subscription::TransactionStatus { transaction_hash } = &subscription {
+        if let Ok((execution_status, finality_status)) =
+            starknet.get_transaction_execution_and_finality_status(*transaction_hash)
+        {
+            let failure_reason = starknet
+                .get_transaction_execution_result(*transaction_hash)
+                .ok()
+                .and_then(|execution_result| execution_result.revert_reason().map(String::from));
+            backfill.push(StarknetNotification::TransactionStatus {
+                transaction_hash: *transaction_hash,
+                finality_status,
+                execution_status,
+                failure_reason,
+            });
+        }
+    }
+
+    Ok(PendingSubscription {
+        id: context.next_subscription_id(),
+        subscription,
+        receiver: starknet.subscribe_to_notifications(),
+        backfill,
+        rpc_spec_version: handler.rpc_spec_version,
+    })
+}
+
+/// Sends the backfilled messages of the subscription, followed by the messages of all future
+/// notifications of interest
+async fn run_subscription(
+    PendingSubscription { id, subscription, mut receiver, backfill, rpc_spec_version }: PendingSubscription,
+    message_sender: mpsc::UnboundedSender<String>,
+) {
+    let send = |notification: StarknetNotification| -> bool {
+        let method = subscription.notification_method();
+        subscription.notification_results(notification).into_iter().all(|result| {
+            let result = rpc_spec_version.convert_result(method, result);
+            let message = serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": method,
+                "params": { "subscription_id": id, "result": result },
+            });
+            message_sender.send(message.to_string()).is_ok()
+        })
+    };
+
+    for notification in backfill {
+        if !send(notification) {
+            return;
+        }
+    }
+
+    loop {
+        match receiver.recv().await {
+            Ok(notification) => {
+                if !send(notification) {
+                    return;
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                // the missed notifications cannot be recovered, so the subscriber is told that the
+                // subscription is closed instead of silently receiving only the later ones
+                warn!(target: "ws", subscription_id = id, skipped, "closing lagging subscription");
+                let error = ApiError::SubscriptionLagged { skipped }.api_error_to_rpc_error();
+                let message = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": subscription.notification_method(),
+                    "params": { "subscription_id": id, "error": error },
+                });
+                let _ = message_sender.send(message.to_string());
+                return;
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_core::starknet::notifications::StarknetNotification;
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, Felt, TransactionExecutionStatus, TransactionFinalityStatus,
+    };
+    use tokio::sync::{broadcast, mpsc};
+
+    use super::{
+        run_subscription, EventsSubscriptionInput, PendingSubscription, Subscription,
+        SubscriptionIdInput, SubscriptionRequest,
+    };
+    use crate::api::json_rpc::models::{BlockIdInput, TransactionHashInput};
+    use crate::api::json_rpc::RpcSpecVersion;
+
+    #[test]
+    fn deserialize_subscribe_new_heads_request() {
+        for params in ["null", "{}", "[]"] {
+            let request = format!(r#"{{"method":"starknet_subscribeNewHeads","params":{params}}}"#);
+            assert!(matches!(
+                serde_json::from_str(&request).unwrap(),
+                SubscriptionRequest::NewHeads(None)
+            ));
+        }
+
+        let request = r#"{"method":"starknet_subscribeNewHeads","params":{"block_id":"latest"}}"#;
+        assert!(matches!(
+            serde_json::from_str(request).unwrap(),
+            SubscriptionRequest::NewHeads(Some(BlockIdInput {
+                block_id: starknet_types::rpc::block::BlockId(BlockId::Tag(BlockTag::Latest))
+            }))
+        ));
+    }
+
+    #[test]
+    fn deserialize_subscribe_events_request() {
+        let request = r#"{"method":"starknet_subscribeEvents","params":{}}"#;
+        assert!(matches!(
+            serde_json::from_str(request).unwrap(),
+            SubscriptionRequest::Events(None)
+        ));
+
+        let request = r#"{"method":"starknet_subscribeEvents","params":{
+            "from_address":"0x1","keys":[["0x2"],[]],"block_id":{"block_number":0}
+        }}"#;
+        match serde_json::from_str(request).unwrap() {
+            SubscriptionRequest::Events(Some(EventsSubscriptionInput {
+                from_address: Some(from_address),
+                keys: Some(keys),
+                block_id: Some(starknet_types::rpc::block::BlockId(BlockId::Number(0))),
+            })) => {
+                assert_eq!(Felt::from(from_address), Felt::ONE);
+                assert_eq!(keys, vec![vec![Felt::TWO], vec![]]);
+            }
+            unexpected => panic!("Got unexpected request: {unexpected:?}"),
+        }
+
+        let request = r#"{"method":"starknet_subscribeEvents","params":{"address":"0x1"}}"#;
+        assert!(serde_json::from_str::<SubscriptionRequest>(request).is_err());
+    }
+
+    #[test]
+    fn deserialize_subscribe_transaction_status_request() {
+        let request = r#"{"method":"starknet_subscribeTransactionStatus","params":{
+            "transaction_hash":"0x1"
+        }}"#;
+        match serde_json::from_str(request).unwrap() {
+            SubscriptionRequest::TransactionStatus(TransactionHashInput { transaction_hash }) => {
+                assert_eq!(transaction_hash, Felt::ONE)
+            }
+            unexpected => panic!("Got unexpected request: {unexpected:?}"),
+        }
+
+        let request = r#"{"method":"starknet_subscribeTransactionStatus","params":{}}"#;
+        assert!(serde_json::from_str::<SubscriptionRequest>(request).is_err());
+    }
+
+    #[test]
+    fn deserialize_subscribe_pending_transactions_request() {
+        let request = r#"{"method":"starknet_subscribePendingTransactions","params":[]}"#;
+        assert!(matches!(
+            serde_json::from_str(request).unwrap(),
+            SubscriptionRequest::PendingTransactions(None)
+        ));
+
+        let request = r#"{"method":"starknet_subscribePendingTransactions","params":{
+            "transaction_details":true,"sender_address":["0x1"]
+        }}"#;
+        match serde_json::from_str(request).unwrap() {
+            SubscriptionRequest::PendingTransactions(Some(input)) => {
+                assert_eq!(input.transaction_details, Some(true));
+                let senders: Vec<Felt> =
+                    input.sender_address.unwrap().into_iter().map(Felt::from).collect();
+                assert_eq!(senders, vec![Felt::ONE]);
+            }
+            unexpected => panic!("Got unexpected request: {unexpected:?}"),
+        }
+    }
+
+    #[test]
+    fn deserialize_unsubscribe_request() {
+        let request = r#"{"method":"starknet_unsubscribe","params":{"subscription_id":3}}"#;
+        assert!(matches!(
+            serde_json::from_str(request).unwrap(),
+            SubscriptionRequest::Unsubscribe(SubscriptionIdInput { subscription_id: 3 })
+        ));
+
+        let request = r#"{"method":"starknet_unsubscribe","params":{}}"#;
+        assert!(serde_json::from_str::<SubscriptionRequest>(request).is_err());
+    }
+
+    #[tokio::test]
+    async fn lagging_transaction_status_subscription_is_closed_with_error() {
+        let (notification_sender, receiver) = broadcast::channel(1);
+        let status = StarknetNotification::TransactionStatus {
+            transaction_hash: Felt::ONE,
+            finality_status: TransactionFinalityStatus::AcceptedOnL2,
+            execution_status: TransactionExecutionStatus::Succeeded,
+            failure_reason: None,
+        };
+        // the second notification pushes the first one out of the channel before it is received
+        notification_sender.send(status.clone()).unwrap();
+        notification_sender.send(status).unwrap();
+
+        let subscription = PendingSubscription {
+            id: 1,
+            subscription: Subscription::TransactionStatus { transaction_hash: Felt::ONE },
+            receiver,
+            backfill: vec![],
+            rpc_spec_version: RpcSpecVersion::default(),
+        };
+        let (message_sender, mut message_receiver) = mpsc::unbounded_channel();
+        run_subscription(subscription, message_sender).await;
+
+        let message: serde_json::Value =
+            serde_json::from_str(&message_receiver.recv().await.unwrap()).unwrap();
+        assert_eq!(
+            message,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "starknet_subscriptionTransactionStatus",
+                "params": {
+                    "subscription_id": 1,
+                    "error": {
+                        "code": -1,
+                        "message": "Subscription closed after missing 1 notifications"
+                    },
+                },
+            })
+        );
+        // the subscription has ended without sending the notification received after the gap
+        assert!(message_receiver.recv().await.is_none());
+    }
+}
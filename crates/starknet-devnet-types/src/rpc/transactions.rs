@@ -5,7 +5,6 @@ use std::sync::Arc;
 use blockifier::execution::contract_class::ClassInfo;
 use blockifier::state::state_api::StateReader;
 use blockifier::transaction::account_transaction::AccountTransaction;
-use blockifier::transaction::objects::TransactionExecutionInfo;
 use broadcasted_declare_transaction_v1::BroadcastedDeclareTransactionV1;
 use broadcasted_declare_transaction_v2::BroadcastedDeclareTransactionV2;
 use declare_transaction_v0v1::DeclareTransactionV0V1;
@@ -122,6 +121,22 @@ pub enum Transaction {
     L1Handler(L1HandlerTransaction),
 }
 
+impl Transaction {
+    /// Returns the address of the account which sent the transaction, or `None` if the
+    /// transaction was not sent by an account (deploy and L1 handler transactions)
+    pub fn get_sender_address(&self) -> Option<&ContractAddress> {
+        match self {
+            Transaction::Declare(DeclareTransaction::V1(tx)) => Some(&tx.sender_address),
+            Transaction::Declare(DeclareTransaction::V2(tx)) => Some(&tx.sender_address),
+            Transaction::Declare(DeclareTransaction::V3(tx)) => Some(tx.get_sender_address()),
+            Transaction::DeployAccount(tx) => Some(tx.get_contract_address()),
+            Transaction::Invoke(InvokeTransaction::V1(tx)) => Some(&tx.sender_address),
+            Transaction::Invoke(InvokeTransaction::V3(tx)) => Some(tx.get_sender_address()),
+            Transaction::Deploy(_) | Transaction::L1Handler(_) => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TransactionWithHash {
     transaction_hash: TransactionHash,
@@ -158,10 +173,9 @@ impl TransactionWithHash {
         execution_result: &ExecutionResult,
         finality_status: TransactionFinalityStatus,
         actual_fee: FeeInUnits,
-        execution_info: &TransactionExecutionInfo,
+        execution_resources: ExecutionResources,
     ) -> CommonTransactionReceipt {
         let r#type = self.get_type();
-        let execution_resources = ExecutionResources::from(execution_info);
         let maybe_pending_properties =
             MaybePendingProperties { block_number, block_hash: block_hash.cloned() };
 
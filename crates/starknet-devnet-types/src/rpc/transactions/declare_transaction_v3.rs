@@ -46,4 +46,8 @@ impl DeclareTransactionV3 {
     pub fn get_class_hash(&self) -> &ClassHash {
         &self.class_hash
     }
+
+    pub fn get_sender_address(&self) -> &ContractAddress {
+        &self.sender_address
+    }
 }
@@ -40,6 +40,10 @@ impl InvokeTransactionV3 {
             account_deployment_data: broadcasted_txn.account_deployment_data.clone(),
         }
     }
+
+    pub fn get_sender_address(&self) -> &ContractAddress {
+        &self.sender_address
+    }
 }
 
 impl From<InvokeTransactionV3> for BroadcastedInvokeTransactionV3 {
@@ -128,6 +128,7 @@ impl TryFrom<&L1HandlerTransaction> for MessageToL2 {
             payload,
             paid_fee_on_l1: Felt::from(value.paid_fee_on_l1),
             nonce: value.nonce,
+            l1_transaction_hash: None,
         })
     }
 }
@@ -168,6 +169,7 @@ mod tests {
             payload,
             nonce: nonce.into(),
             paid_fee_on_l1: fee.into(),
+            l1_transaction_hash: None,
         };
 
         let chain_id = ChainId::goerli_legacy_id();
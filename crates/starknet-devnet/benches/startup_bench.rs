@@ -0,0 +1,29 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use starknet_core::starknet::starknet_config::StarknetConfig;
+use starknet_core::starknet::Starknet;
+
+/// Both modes compute the keys, addresses and storage of every account on startup, so both take
+/// time linear in the number of accounts; the lazy one only skips writing them to the state
+static TOTAL_ACCOUNTS: [u32; 2] = [1_000, 2_000];
+
+fn startup(total_accounts: u32, lazy_predeployment: bool) {
+    let config = StarknetConfig { total_accounts, lazy_predeployment, ..Default::default() };
+    Starknet::new(&config).expect("Could not start Starknet");
+}
+
+fn bench_startup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Startup");
+    group.significance_level(0.1).sample_size(10);
+    for total_accounts in TOTAL_ACCOUNTS {
+        for (name, lazy_predeployment) in [("eager", false), ("lazy", true)] {
+            group.bench_function(format!("{name} {total_accounts}"), |b| {
+                b.iter(|| startup(black_box(total_accounts), black_box(lazy_predeployment)))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_startup);
+criterion_main!(benches);
@@ -2,14 +2,19 @@ use std::num::NonZeroU128;
 
 use clap::Parser;
 use server::ServerConfig;
+use starknet_core::accounts_file::AccountsFile;
 use starknet_core::constants::{
     DEVNET_DEFAULT_DATA_GAS_PRICE, DEVNET_DEFAULT_GAS_PRICE, DEVNET_DEFAULT_PORT,
     DEVNET_DEFAULT_REQUEST_BODY_SIZE_LIMIT, DEVNET_DEFAULT_TIMEOUT, DEVNET_DEFAULT_TOTAL_ACCOUNTS,
 };
 use starknet_core::contract_class_choice::{AccountClassWrapper, AccountContractClassChoice};
+use starknet_core::mnemonic::{AccountMnemonic, MnemonicScheme};
 use starknet_core::random_number_generator::generate_u32_random_number;
+use starknet_core::starknet::dump::COMPRESSED_DUMP_EXTENSION;
+use starknet_core::starknet::genesis::Genesis;
 use starknet_core::starknet::starknet_config::{
-    BlockGenerationOn, DumpOn, ForkConfig, StarknetConfig, StateArchiveCapacity,
+    BlockGenerationOn, DumpFormat, DumpOn, ForkConfig, LoadUntil, StarknetConfig,
+    StateArchiveCapacity,
 };
 use starknet_types::chain_id::ChainId;
 use tracing_subscriber::EnvFilter;
@@ -34,7 +39,13 @@ pub(crate) struct Args {
     #[arg(value_name = "NUMBER")]
     #[arg(default_value_t = DEVNET_DEFAULT_TOTAL_ACCOUNTS)]
     #[arg(help = "Specify the number of accounts to be predeployed;")]
-    accounts_count: u8,
+    accounts_count: u32,
+
+    #[arg(long = "lazy-predeployment")]
+    #[arg(env = "LAZY_PREDEPLOYMENT")]
+    #[arg(help = "Specify whether to set up the state of predeployed accounts on first access \
+                  instead of on startup; useful for predeploying thousands of accounts;")]
+    lazy_predeployment: bool,
 
     /// Class used for account predeployment
     #[arg(long = "account-class")]
@@ -68,6 +79,38 @@ pub(crate) struct Args {
                   provided, it is randomly generated")]
     seed: Option<u32>,
 
+    #[arg(long = "accounts-file")]
+    #[arg(env = "ACCOUNTS_FILE")]
+    #[arg(value_name = "PATH")]
+    #[arg(conflicts_with = "accounts_count")]
+    #[arg(help = "Specify the path to a JSON or YAML file listing the accounts to be \
+                  predeployed instead of the ones generated from the seed;")]
+    accounts_file: Option<AccountsFile>,
+
+    #[arg(long = "mnemonic")]
+    #[arg(env = "MNEMONIC")]
+    #[arg(value_name = "PHRASE")]
+    #[arg(conflicts_with = "accounts_file")]
+    #[arg(help = "Specify the BIP-39 mnemonic phrase from which the keys of predeployed \
+                  accounts are derived, instead of generating them from the seed;")]
+    mnemonic: Option<String>,
+
+    #[arg(long = "derivation-path")]
+    #[arg(env = "DERIVATION_PATH")]
+    #[arg(value_name = "PATH")]
+    #[arg(requires = "mnemonic")]
+    #[arg(help = "Specify the BIP-32 path to which the account index is appended when deriving \
+                  keys from the mnemonic; defaults to m/44'/9004'/0'/0;")]
+    derivation_path: Option<String>,
+
+    #[arg(long = "mnemonic-scheme")]
+    #[arg(env = "MNEMONIC_SCHEME")]
+    #[arg(value_name = "SCHEME")]
+    #[arg(default_value = "braavos")]
+    #[arg(requires = "mnemonic")]
+    #[arg(help = "Specify which wallet's way of deriving keys from the mnemonic is used;")]
+    mnemonic_scheme: MnemonicScheme,
+
     // Host address
     #[arg(long = "host")]
     #[arg(env = "HOST")]
@@ -160,6 +203,39 @@ pub(crate) struct Args {
     #[arg(required_if_eq_any([("dump_on", "exit"), ("dump_on", "block")]))]
     dump_path: Option<String>,
 
+    #[arg(long = "dump-format")]
+    #[arg(env = "DUMP_FORMAT")]
+    #[arg(value_name = "FORMAT")]
+    #[arg(default_value = "events")]
+    #[arg(help = "Specify the format of the dump. Possible values are:
+- \"events\" - the received transactions and requests, re-executed on load
+- \"state\" - the state, blocks and transactions, loaded without re-execution
+
+If the dump path ends with \".gz\", the dump is compressed with gzip.")]
+    dump_format: DumpFormat,
+
+    #[arg(long = "load-until-block")]
+    #[arg(env = "LOAD_UNTIL_BLOCK")]
+    #[arg(value_name = "BLOCK_NUMBER")]
+    #[arg(requires = "dump_path")]
+    #[arg(conflicts_with = "load_until_event")]
+    #[arg(help = "Specify the number of the last block to load from the dump at the dump path;")]
+    load_until_block: Option<u64>,
+
+    #[arg(long = "load-until-event")]
+    #[arg(env = "LOAD_UNTIL_EVENT")]
+    #[arg(value_name = "EVENT_INDEX")]
+    #[arg(requires = "dump_path")]
+    #[arg(help = "Specify the (zero-based) index of the last event to re-execute from the dump \
+                  at the dump path;")]
+    load_until_event: Option<usize>,
+
+    #[arg(long = "ignore-dump-version")]
+    #[arg(env = "IGNORE_DUMP_VERSION")]
+    #[arg(help = "Specify whether to load dumps of events created by a different version of \
+                  Devnet; re-execution may then have different results;")]
+    ignore_dump_version: bool,
+
     #[arg(long = "block-generation-on")]
     #[arg(env = "BLOCK_GENERATION_ON")]
     #[arg(default_value = "transaction")]
@@ -202,6 +278,13 @@ Sending POST /create_block is also an option in modes other than \"demand\".")]
     #[arg(env = "DISABLE_ACCOUNT_IMPERSONATION")]
     #[arg(help = "Disables the possibility to impersonate accounts;")]
     disable_account_impersonation: bool,
+
+    #[arg(long = "genesis")]
+    #[arg(env = "GENESIS")]
+    #[arg(value_name = "PATH")]
+    #[arg(help = "Specify the path to a JSON or YAML file describing the classes to declare, \
+                  the contracts to deploy and the balances to set before the genesis block;")]
+    genesis: Option<Genesis>,
 }
 
 impl Args {
@@ -212,12 +295,36 @@ impl Args {
             None => self.account_class_choice.get_class_wrapper()?,
         };
 
+        // events are appended to the dump after each block, which compressed dumps don't allow
+        if let (Some(DumpOn::Block), DumpFormat::Events, Some(dump_path)) =
+            (self.dump_on, self.dump_format, &self.dump_path)
+        {
+            if dump_path.ends_with(COMPRESSED_DUMP_EXTENSION) {
+                anyhow::bail!(
+                    "Compressed dumps of events are not supported with --dump-on block; use \
+                     --dump-format state or dump on exit or on request instead"
+                );
+            }
+        }
+
         let starknet_config = StarknetConfig {
             seed: match self.seed {
                 Some(seed) => seed,
                 None => generate_u32_random_number(),
             },
+            mnemonic: self
+                .mnemonic
+                .as_deref()
+                .map(|phrase| {
+                    AccountMnemonic::new(
+                        phrase,
+                        self.derivation_path.as_deref(),
+                        self.mnemonic_scheme,
+                    )
+                })
+                .transpose()?,
             total_accounts: self.accounts_count,
+            lazy_predeployment: self.lazy_predeployment,
             account_contract_class: account_class_wrapper.contract_class,
             account_contract_class_hash: account_class_wrapper.class_hash,
             predeployed_accounts_initial_balance: self.initial_balance.0.clone(),
@@ -229,6 +336,12 @@ impl Args {
             chain_id: self.chain_id,
             dump_on: self.dump_on,
             dump_path: self.dump_path.clone(),
+            dump_format: self.dump_format,
+            load_until: self
+                .load_until_block
+                .map(LoadUntil::BlockNumber)
+                .or(self.load_until_event.map(LoadUntil::EventIndex)),
+            ignore_dump_version: self.ignore_dump_version,
             block_generation_on: self.block_generation_on,
             lite_mode: self.lite_mode,
             state_archive: self.state_archive,
@@ -237,6 +350,8 @@ impl Args {
                 block_number: self.fork_block,
             },
             disable_account_impersonation: self.disable_account_impersonation,
+            genesis: self.genesis.clone(),
+            accounts_file: self.accounts_file.clone(),
         };
 
         let RequestResponseLogging { log_request, log_response } =
@@ -357,6 +472,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn not_allowing_accounts_file_and_number_of_accounts() {
+        match Args::try_parse_from([
+            "--",
+            "--accounts-file",
+            "test_data/accounts_file.yaml",
+            "--accounts",
+            "3",
+        ]) {
+            Err(err) => {
+                assert_eq!(
+                    get_first_line(&err.to_string()),
+                    "error: the argument '--accounts-file <PATH>' cannot be used with '--accounts \
+                     <NUMBER>'"
+                );
+            }
+            Ok(parsed) => panic!("Should have failed; got: {parsed:?}"),
+        }
+    }
+
+    #[test]
+    fn not_allowing_derivation_path_without_mnemonic() {
+        match Args::try_parse_from(["--", "--derivation-path", "m/44'/9004'/0'/0"]) {
+            Err(err) => assert_eq!(
+                get_first_line(&err.to_string()),
+                "error: the following required arguments were not provided:"
+            ),
+            Ok(parsed) => panic!("Should have failed; got: {parsed:?}"),
+        }
+    }
+
+    #[test]
+    fn not_allowing_mnemonic_scheme_without_mnemonic() {
+        match Args::try_parse_from(["--", "--mnemonic-scheme", "argent"]) {
+            Err(err) => assert_eq!(
+                get_first_line(&err.to_string()),
+                "error: the following required arguments were not provided:"
+            ),
+            Ok(parsed) => panic!("Should have failed; got: {parsed:?}"),
+        }
+    }
+
+    #[test]
+    fn compressed_dump_of_events_on_block_rejected() {
+        let args = Args::parse_from(["--", "--dump-on", "block", "--dump-path", "dump.gz"]);
+        match args.to_config() {
+            Err(err) => assert!(err.to_string().starts_with("Compressed dumps of events")),
+            Ok(_) => panic!("Should have failed"),
+        }
+
+        let args = Args::parse_from([
+            "--",
+            "--dump-on",
+            "block",
+            "--dump-path",
+            "dump.gz",
+            "--dump-format",
+            "state",
+        ]);
+        args.to_config().unwrap();
+    }
+
+    #[test]
+    fn invalid_mnemonic_rejected() {
+        let args = Args::parse_from(["--", "--mnemonic", "not a valid phrase"]);
+        match args.to_config() {
+            Err(err) => assert!(err.to_string().starts_with("Invalid mnemonic: ")),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
     #[test]
     fn allowing_if_only_account_class() {
         match Args::try_parse_from(["--", "--account-class", "cairo1"]) {
@@ -533,6 +719,8 @@ mod tests {
             ("--data-gas-price-fri", "DATA_GAS_PRICE_FRI", "4"),
             ("--dump-on", "DUMP_ON", "exit"),
             ("--dump-path", "DUMP_PATH", "dummy-path"),
+            ("--dump-format", "DUMP_FORMAT", "state"),
+            ("--load-until-block", "LOAD_UNTIL_BLOCK", "3"),
             ("--state-archive-capacity", "STATE_ARCHIVE_CAPACITY", "full"),
             ("--fork-network", "FORK_NETWORK", "http://dummy.com"),
             ("--fork-block", "FORK_BLOCK", "42"),
@@ -641,6 +829,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn load_until_block_and_event_are_mutually_exclusive() {
+        match Args::try_parse_from([
+            "--",
+            "--dump-path",
+            "dummy-path",
+            "--load-until-block",
+            "1",
+            "--load-until-event",
+            "2",
+        ]) {
+            Ok(args) => panic!("Should have failed; got: {args:?}"),
+            Err(e) => assert_eq!(
+                get_first_line(&e.to_string()),
+                "error: the argument '--load-until-block <BLOCK_NUMBER>' cannot be used with \
+                 '--load-until-event <EVENT_INDEX>'"
+            ),
+        }
+    }
+
     #[test]
     fn invalid_dump_path_not_allowed() {
         match Args::try_parse_from(["--", "--dump-path", "dump_wrong_cli_mode", "--dump-on", "e"]) {
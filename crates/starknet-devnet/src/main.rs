@@ -13,13 +13,14 @@ use starknet_core::constants::{
     CAIRO_1_ERC20_CONTRACT_CLASS_HASH, ETH_ERC20_CONTRACT_ADDRESS, STRK_ERC20_CONTRACT_ADDRESS,
     UDC_CONTRACT_ADDRESS, UDC_CONTRACT_CLASS_HASH,
 };
-use starknet_core::starknet::starknet_config::{BlockGenerationOn, DumpOn, ForkConfig};
+use starknet_core::starknet::starknet_config::{
+    BlockGenerationOn, DumpOn, ForkConfig, StarknetConfig,
+};
 use starknet_core::starknet::Starknet;
 use starknet_rs_core::types::{BlockId, BlockTag, MaybePendingBlockWithTxHashes};
 use starknet_rs_providers::jsonrpc::HttpTransport;
 use starknet_rs_providers::{JsonRpcClient, Provider};
 use starknet_types::chain_id::ChainId;
-use starknet_types::rpc::state::Balance;
 use starknet_types::traits::ToHexString;
 use tokio::net::TcpListener;
 #[cfg(unix)]
@@ -37,6 +38,8 @@ mod ip_addr_wrapper;
 
 const REQUEST_LOG_ENV_VAR: &str = "request";
 const RESPONSE_LOG_ENV_VAR: &str = "response";
+/// With lazy predeployment there may be thousands of accounts, so only the first ones are logged
+const MAX_LOGGED_LAZILY_PREDEPLOYED_ACCOUNTS: usize = 10;
 
 /// Configures tracing with default level INFO,
 /// If the environment variable `RUST_LOG` is set, it will be used instead.
@@ -65,31 +68,66 @@ fn configure_tracing() {
     tracing_subscriber::fmt().with_env_filter(level_filter_layer).init();
 }
 
-fn log_predeployed_accounts(
-    predeployed_accounts: &Vec<Account>,
-    seed: u32,
-    initial_balance: Balance,
-) {
-    for account in predeployed_accounts {
-        let formatted_str = format!(
+fn log_predeployed_accounts(predeployed_accounts: &[Account], starknet_config: &StarknetConfig) {
+    let from_accounts_file = starknet_config.accounts_file.is_some();
+    let logged_accounts_count = if starknet_config.lazy_predeployment {
+        MAX_LOGGED_LAZILY_PREDEPLOYED_ACCOUNTS
+    } else {
+        predeployed_accounts.len()
+    };
+
+    for account in predeployed_accounts.iter().take(logged_accounts_count) {
+        let mut formatted_str = format!(
             r"
 | Account address |  {}
 | Private key     |  {}
 | Public key      |  {}",
             account.account_address.to_prefixed_hex_str(),
-            account.private_key.to_fixed_hex_string(),
+            account
+                .private_key
+                .map_or("unknown".to_string(), |private_key| private_key.to_fixed_hex_string()),
             account.public_key.to_fixed_hex_string()
         );
 
+        // accounts from a file don't necessarily share the class and the balances
+        if from_accounts_file {
+            formatted_str.push_str(&format!(
+                r"
+| Class hash      |  {}
+| Initial balance |  {} WEI and {} FRI",
+                account.class_hash.to_fixed_hex_string(),
+                account.eth_initial_balance,
+                account.strk_initial_balance
+            ));
+        }
+
         println!("{}", formatted_str);
     }
 
-    if !predeployed_accounts.is_empty() {
+    if predeployed_accounts.len() > logged_accounts_count {
+        println!(
+            "\n... and {} more accounts, retrievable via /predeployed_accounts",
+            predeployed_accounts.len() - logged_accounts_count
+        );
+    }
+
+    if !predeployed_accounts.is_empty() && !from_accounts_file {
         println!();
         let class_hash = predeployed_accounts.first().unwrap().class_hash.to_fixed_hex_string();
         println!("Predeployed accounts using class with hash: {class_hash}");
-        println!("Initial balance of each account: {} WEI and FRI", initial_balance);
-        println!("Seed to replicate this account sequence: {seed}");
+        println!(
+            "Initial balance of each account: {} WEI and FRI",
+            starknet_config.predeployed_accounts_initial_balance
+        );
+        match &starknet_config.mnemonic {
+            Some(mnemonic) => println!(
+                "Keys derived from the mnemonic with the {:?} scheme at derivation path: {}/<account \
+                 index>",
+                mnemonic.scheme(),
+                mnemonic.derivation_path()
+            ),
+            None => println!("Seed to replicate this account sequence: {}", starknet_config.seed),
+        }
     }
 }
 
@@ -189,11 +227,7 @@ async fn main() -> Result<(), anyhow::Error> {
     log_chain_id(&starknet_config.chain_id);
 
     let predeployed_accounts = api.starknet.lock().await.get_predeployed_accounts();
-    log_predeployed_accounts(
-        &predeployed_accounts,
-        starknet_config.seed,
-        starknet_config.predeployed_accounts_initial_balance.clone(),
-    );
+    log_predeployed_accounts(&predeployed_accounts, &starknet_config);
 
     let server = serve_http_api_json_rpc(listener, api.clone(), &starknet_config, &server_config);
 
@@ -266,7 +300,7 @@ pub async fn shutdown_signal(api: Api) {
     // dump on exit scenario
     let starknet = api.starknet.lock().await;
     if starknet.config.dump_on == Some(DumpOn::Exit) {
-        starknet.dump_events().expect("Failed to dump starknet transactions");
+        starknet.dump().expect("Failed to dump Starknet state");
     }
 }
 
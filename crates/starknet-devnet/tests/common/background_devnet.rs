@@ -94,6 +94,11 @@ impl BackgroundDevnet {
         // Iterate through default args, and remove from specified args when found
         // That way in the end we can just append the non-removed args
         for (arg_name, default_value) in DEFAULT_CLI_MAP.iter() {
+            // the number of accounts is determined by the accounts file, if specified
+            if *arg_name == "--accounts" && specified_args.contains(&"--accounts-file") {
+                continue;
+            }
+
             let value =
                 match specified_args_vec.iter().position(|arg_candidate| arg_candidate == arg_name)
                 {
@@ -164,6 +169,17 @@ impl BackgroundDevnet {
         &self,
         method: &str,
         params: serde_json::Value,
+    ) -> Result<serde_json::Value, RpcError> {
+        self.send_custom_rpc_to_path(RPC_PATH, method, params).await
+    }
+
+    /// Like `send_custom_rpc`, but sends the request to `path`, e.g. to the route of a specific
+    /// spec version
+    pub async fn send_custom_rpc_to_path(
+        &self,
+        path: &str,
+        method: &str,
+        params: serde_json::Value,
     ) -> Result<serde_json::Value, RpcError> {
         let body_json = if params.is_null() {
             json!({
@@ -182,7 +198,7 @@ impl BackgroundDevnet {
 
         let json_rpc_result: serde_json::Value = self
             .reqwest_client()
-            .post_json_async(RPC_PATH, body_json)
+            .post_json_async(path, body_json)
             .await
             .map_err(|err| RpcError::internal_error_with(err.error_message()))?;
 
@@ -3,7 +3,11 @@ use std::fs;
 use std::path::Path;
 use std::process::{Child, Command};
 use std::sync::Arc;
+use std::time::Duration;
 
+use futures::{SinkExt, StreamExt};
+use serde_json::json;
+use server::rpc_core::error::RpcError;
 use server::test_utils::exported_test_utils::assert_contains;
 use starknet_core::constants::CAIRO_1_ACCOUNT_CONTRACT_SIERRA_HASH;
 use starknet_core::random_number_generator::generate_u32_random_number;
@@ -22,9 +26,12 @@ use starknet_rs_providers::jsonrpc::HttpTransport;
 use starknet_rs_providers::{JsonRpcClient, Provider};
 use starknet_rs_signers::LocalWallet;
 use starknet_types::felt::felt_from_prefixed_hex;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 
 use super::background_devnet::BackgroundDevnet;
-use super::constants::{ARGENT_ACCOUNT_CLASS_HASH, CAIRO_1_CONTRACT_PATH};
+use super::constants::{ARGENT_ACCOUNT_CLASS_HASH, CAIRO_1_CONTRACT_PATH, WS_PATH};
 
 pub enum ImpersonationAction {
     ImpersonateAccount(Felt),
@@ -339,6 +346,95 @@ where
     }
 }
 
+pub type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Opens a WebSocket connection to the `devnet`
+pub async fn connect_to_ws(devnet: &BackgroundDevnet) -> Result<WsStream, anyhow::Error> {
+    connect_to_ws_path(devnet, WS_PATH).await
+}
+
+/// Opens a WebSocket connection to the `path` of the `devnet`
+pub async fn connect_to_ws_path(
+    devnet: &BackgroundDevnet,
+    path: &str,
+) -> Result<WsStream, anyhow::Error> {
+    let ws_url = format!("{}{path}", devnet.url.replacen("http", "ws", 1));
+    let (ws, _) = connect_async(ws_url).await?;
+    Ok(ws)
+}
+
+/// Sends a JSON-RPC request via `ws` and returns the result of the response. Assumes that no
+/// subscription messages arrive before the response.
+pub async fn send_text_rpc_via_ws(
+    ws: &mut WsStream,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<serde_json::Value, RpcError> {
+    let request = json!({ "jsonrpc": "2.0", "id": 0, "method": method, "params": params });
+    ws.send(Message::Text(request.to_string()))
+        .await
+        .map_err(|err| RpcError::internal_error_with(err.to_string()))?;
+
+    let response = receive_rpc_via_ws(ws)
+        .await
+        .map_err(|err| RpcError::internal_error_with(err.to_string()))?;
+
+    if let Some(result) = response.get("result") {
+        Ok(result.clone())
+    } else if let Some(error) = response.get("error") {
+        Err(serde_json::from_value(error.clone()).unwrap())
+    } else {
+        Err(RpcError::internal_error_with("Server responded with malformed response"))
+    }
+}
+
+/// Subscribes via `ws` and returns the subscription id
+pub async fn subscribe(
+    ws: &mut WsStream,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<u64, RpcError> {
+    let subscription_id = send_text_rpc_via_ws(ws, method, params).await?;
+    Ok(serde_json::from_value(subscription_id).unwrap())
+}
+
+pub async fn unsubscribe(
+    ws: &mut WsStream,
+    subscription_id: u64,
+) -> Result<serde_json::Value, RpcError> {
+    send_text_rpc_via_ws(ws, "starknet_unsubscribe", json!({ "subscription_id": subscription_id }))
+        .await
+}
+
+/// Receives the next text message via `ws`; fails if it doesn't arrive within a second
+pub async fn receive_rpc_via_ws(ws: &mut WsStream) -> Result<serde_json::Value, anyhow::Error> {
+    let message = tokio::time::timeout(Duration::from_secs(1), ws.next())
+        .await?
+        .ok_or(anyhow::anyhow!("Socket closed"))??;
+    Ok(serde_json::from_str(&message.into_text()?)?)
+}
+
+/// Receives the next subscription message via `ws`, asserts its method and subscription id and
+/// returns its result
+pub async fn receive_notification(
+    ws: &mut WsStream,
+    method: &str,
+    expected_subscription_id: u64,
+) -> Result<serde_json::Value, anyhow::Error> {
+    let mut notification = receive_rpc_via_ws(ws).await?;
+    assert_eq!(notification["jsonrpc"], "2.0");
+    assert_eq!(notification["method"], method);
+    assert_eq!(notification["params"]["subscription_id"], json!(expected_subscription_id));
+    Ok(notification["params"]["result"].take())
+}
+
+/// Asserts that no message arrives via `ws` within a second
+pub async fn assert_no_notifications(ws: &mut WsStream) {
+    if let Ok(message) = receive_rpc_via_ws(ws).await {
+        panic!("Unexpected message: {message}");
+    }
+}
+
 #[cfg(test)]
 mod test_unique_auto_deletable_file {
     use std::path::Path;
@@ -68,6 +68,7 @@ mod general_integration_tests {
         let mut expected_config = json!({
             "seed": 1,
             "total_accounts": 2,
+            "lazy_predeployment": false,
             "account_contract_class_hash": "0x61dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f",
             "predeployed_accounts_initial_balance": "3",
             "start_time": 4,
@@ -78,6 +79,9 @@ mod general_integration_tests {
             "chain_id": "SN_MAIN",
             "dump_on": "exit",
             "dump_path": dump_file.path,
+            "dump_format": "events",
+            "load_until": null,
+            "ignore_dump_version": false,
             "state_archive": "full",
             "fork_config": {
                 "url": null,
@@ -3,10 +3,17 @@ pub mod common;
 
 mod general_rpc_tests {
     use serde_json::json;
-    use server::api::json_rpc::RPC_SPEC_VERSION;
+    use server::api::json_rpc::{RpcSpecVersion, RPC_SPEC_VERSION};
+    use server::rpc_core::error::ErrorCode;
+    use starknet_core::constants::STRK_ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_accounts::{Account, Call, ExecutionEncoding, SingleOwnerAccount};
+    use starknet_rs_core::types::{BlockId, BlockTag, Felt};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_providers::Provider;
+    use starknet_types::felt::felt_from_prefixed_hex;
 
     use crate::common::background_devnet::BackgroundDevnet;
-    use crate::common::constants::RPC_PATH;
+    use crate::common::constants::{CHAIN_ID, RPC_PATH};
     use crate::common::reqwest_client::PostReqwestSender;
 
     #[tokio::test]
@@ -56,4 +63,230 @@ mod general_rpc_tests {
 
         assert_eq!(rpc_error.code, server::rpc_core::error::ErrorCode::InvalidParams);
     }
+
+    #[tokio::test]
+    async fn versioned_routes_return_their_spec_versions() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        for (rpc_spec_version, expected_version) in
+            [(RpcSpecVersion::V0_7, "0.7.1"), (RpcSpecVersion::V0_8, "0.8.0")]
+        {
+            let received_version = devnet
+                .send_custom_rpc_to_path(
+                    rpc_spec_version.route_path(),
+                    "starknet_specVersion",
+                    json!([]),
+                )
+                .await
+                .unwrap();
+            assert_eq!(received_version, json!(expected_version));
+        }
+    }
+
+    #[tokio::test]
+    async fn versioned_routes_are_served_by_the_same_devnet() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        devnet.create_block().await.unwrap();
+
+        let expected_block_number = devnet.json_rpc_client.block_number().await.unwrap();
+        for rpc_spec_version in RpcSpecVersion::ALL {
+            let block_number = devnet
+                .send_custom_rpc_to_path(
+                    rpc_spec_version.route_path(),
+                    "starknet_blockNumber",
+                    json!([]),
+                )
+                .await
+                .unwrap();
+            assert_eq!(block_number, json!(expected_block_number));
+        }
+    }
+
+    #[tokio::test]
+    async fn transaction_status_contains_failure_reason_only_since_v0_8() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--state-archive-capacity", "full"])
+                .await
+                .unwrap();
+
+        let mint_hash = devnet.mint(0x1, 1).await;
+        devnet
+            .send_custom_rpc(
+                "devnet_abortBlocks",
+                json!({ "starting_block_id": BlockId::Tag(BlockTag::Latest) }),
+            )
+            .await
+            .unwrap();
+
+        let params = json!({ "transaction_hash": mint_hash });
+        let status_v0_7 = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_7.route_path(),
+                "starknet_getTransactionStatus",
+                params.clone(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            status_v0_7,
+            json!({ "finality_status": "ACCEPTED_ON_L2", "execution_status": "REVERTED" })
+        );
+
+        let status_v0_8 = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_8.route_path(),
+                "starknet_getTransactionStatus",
+                params,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            status_v0_8,
+            json!({
+                "finality_status": "ACCEPTED_ON_L2",
+                "execution_status": "REVERTED",
+                "failure_reason": "Block aborted manually"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn methods_since_v0_8_not_found_via_older_routes() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        for path in [RpcSpecVersion::V0_7.route_path(), RPC_PATH] {
+            for method in [
+                "starknet_getStorageProof",
+                "starknet_getCompiledCasm",
+                "starknet_getMessagesStatus",
+            ] {
+                let rpc_error =
+                    devnet.send_custom_rpc_to_path(path, method, json!({})).await.unwrap_err();
+                assert_eq!(rpc_error.code, ErrorCode::MethodNotFound);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn block_and_receipt_returned_in_format_of_requested_version() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mint_hash = devnet.mint(0x1, 1).await;
+
+        let block_id = json!({ "block_id": "latest" });
+        let block_v0_7 = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_7.route_path(),
+                "starknet_getBlockWithTxHashes",
+                block_id.clone(),
+            )
+            .await
+            .unwrap();
+        let block_v0_8 = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_8.route_path(),
+                "starknet_getBlockWithTxHashes",
+                block_id,
+            )
+            .await
+            .unwrap();
+        assert!(block_v0_7.get("l2_gas_price").is_none());
+        assert_eq!(
+            block_v0_8["l2_gas_price"],
+            json!({ "price_in_fri": "0x0", "price_in_wei": "0x0" })
+        );
+        assert_eq!(block_v0_7["l1_gas_price"], block_v0_8["l1_gas_price"]);
+
+        let receipt_params = json!({ "transaction_hash": mint_hash });
+
+        let receipt_v0_7 = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_7.route_path(),
+                "starknet_getTransactionReceipt",
+                receipt_params.clone(),
+            )
+            .await
+            .unwrap();
+        let receipt_v0_8 = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_8.route_path(),
+                "starknet_getTransactionReceipt",
+                receipt_params,
+            )
+            .await
+            .unwrap();
+
+        let resources_v0_7 = &receipt_v0_7["execution_resources"];
+        assert!(resources_v0_7["steps"].is_u64());
+        assert_eq!(
+            receipt_v0_8["execution_resources"],
+            json!({
+                "l1_gas": resources_v0_7["data_availability"]["l1_gas"],
+                "l1_data_gas": resources_v0_7["data_availability"]["l1_data_gas"],
+                "l2_gas": 0,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn v3_transaction_accepted_and_returned_in_format_of_v0_8() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        let account = SingleOwnerAccount::new(
+            &devnet.json_rpc_client,
+            signer,
+            account_address,
+            CHAIN_ID,
+            ExecutionEncoding::New,
+        );
+
+        let invoke_request = account
+            .execute_v3(vec![Call {
+                to: felt_from_prefixed_hex(STRK_ERC20_CONTRACT_ADDRESS).unwrap(),
+                selector: get_selector_from_name("transfer").unwrap(),
+                calldata: vec![Felt::ONE, Felt::from(1_000_000_000), Felt::ZERO],
+            }])
+            .gas(1_000_000)
+            .gas_price(1e12 as u128)
+            .nonce(Felt::ZERO)
+            .prepared()
+            .unwrap()
+            .get_invoke_request(false)
+            .await
+            .unwrap();
+
+        // v0.8 transactions also bound L1 data gas
+        let mut invoke_transaction = serde_json::to_value(invoke_request).unwrap();
+        invoke_transaction["resource_bounds"]["l1_data_gas"] =
+            json!({ "max_amount": "0x1000", "max_price_per_unit": "0x1000" });
+
+        let v0_8_path = RpcSpecVersion::V0_8.route_path();
+        let invoke_result = devnet
+            .send_custom_rpc_to_path(
+                v0_8_path,
+                "starknet_addInvokeTransaction",
+                json!({ "invoke_transaction": invoke_transaction }),
+            )
+            .await
+            .unwrap();
+
+        let hash_params = json!({ "transaction_hash": invoke_result["transaction_hash"] });
+        let receipt = devnet
+            .send_custom_rpc_to_path(
+                v0_8_path,
+                "starknet_getTransactionReceipt",
+                hash_params.clone(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(receipt["execution_status"], "SUCCEEDED");
+
+        let transaction = devnet
+            .send_custom_rpc_to_path(v0_8_path, "starknet_getTransactionByHash", hash_params)
+            .await
+            .unwrap();
+        assert_eq!(
+            transaction["resource_bounds"]["l1_data_gas"],
+            json!({ "max_amount": "0x0", "max_price_per_unit": "0x0" })
+        );
+    }
 }
@@ -0,0 +1,110 @@
+pub mod common;
+
+mod accepting_blocks_on_l1_tests {
+    use serde_json::json;
+    use starknet_rs_core::types::{
+        BlockId, BlockStatus, BlockTag, Felt, MaybePendingBlockWithTxHashes,
+        TransactionFinalityStatus,
+    };
+    use starknet_rs_providers::Provider;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    async fn accept_on_l1(devnet: &BackgroundDevnet, starting_block_id: &BlockId) -> Vec<Felt> {
+        let mut accepted_blocks = devnet
+            .send_custom_rpc("devnet_acceptOnL1", json!({ "starting_block_id": starting_block_id }))
+            .await
+            .unwrap();
+
+        let accepted_blocks = accepted_blocks["accepted"].take().as_array().unwrap().clone();
+
+        accepted_blocks
+            .into_iter()
+            .map(|block_hash| serde_json::from_value(block_hash).unwrap())
+            .collect()
+    }
+
+    async fn assert_block_status(devnet: &BackgroundDevnet, block_hash: Felt, status: BlockStatus) {
+        match devnet.json_rpc_client.get_block_with_tx_hashes(BlockId::Hash(block_hash)).await {
+            Ok(MaybePendingBlockWithTxHashes::Block(block)) => assert_eq!(block.status, status),
+            other => panic!("Unexpected block: {other:?}"),
+        }
+    }
+
+    async fn assert_tx_accepted_on_l1(devnet: &BackgroundDevnet, tx_hash: Felt) {
+        let receipt = devnet.json_rpc_client.get_transaction_receipt(tx_hash).await.unwrap();
+        assert_eq!(receipt.receipt.finality_status(), &TransactionFinalityStatus::AcceptedOnL1);
+
+        let status = devnet.json_rpc_client.get_transaction_status(tx_hash).await.unwrap();
+        assert!(status.is_accepted_on_l1());
+    }
+
+    #[tokio::test]
+    async fn should_accept_block_and_its_predecessors_on_l1() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let genesis_block_hash = devnet.get_latest_block_with_tx_hashes().await.unwrap().block_hash;
+
+        let mint_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let mint_block_hash = devnet.get_latest_block_with_tx_hashes().await.unwrap().block_hash;
+        let empty_block_hash = devnet.create_block().await.unwrap();
+
+        let accepted_blocks = accept_on_l1(&devnet, &BlockId::Hash(mint_block_hash)).await;
+        assert_eq!(accepted_blocks, vec![mint_block_hash, genesis_block_hash]);
+
+        assert_block_status(&devnet, genesis_block_hash, BlockStatus::AcceptedOnL1).await;
+        assert_block_status(&devnet, mint_block_hash, BlockStatus::AcceptedOnL1).await;
+        assert_block_status(&devnet, empty_block_hash, BlockStatus::AcceptedOnL2).await;
+        assert_tx_accepted_on_l1(&devnet, mint_hash).await;
+
+        // only the blocks not yet accepted on L1 are accepted
+        let accepted_blocks = accept_on_l1(&devnet, &BlockId::Tag(BlockTag::Latest)).await;
+        assert_eq!(accepted_blocks, vec![empty_block_hash]);
+        assert_block_status(&devnet, empty_block_hash, BlockStatus::AcceptedOnL1).await;
+    }
+
+    #[tokio::test]
+    async fn should_not_accept_pending_block_on_l1() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--block-generation-on", "demand"])
+                .await
+                .unwrap();
+
+        let mint_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let rpc_error = devnet
+            .send_custom_rpc(
+                "devnet_acceptOnL1",
+                json!({ "starting_block_id": BlockId::Tag(BlockTag::Pending) }),
+            )
+            .await
+            .unwrap_err();
+        assert!(rpc_error.message.contains("Block acceptance on L1 failed"));
+
+        let status = devnet.json_rpc_client.get_transaction_status(mint_hash).await.unwrap();
+        assert!(status.is_accepted_on_l2());
+    }
+
+    #[tokio::test]
+    async fn should_not_abort_block_accepted_on_l1() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--state-archive-capacity", "full"])
+                .await
+                .unwrap();
+
+        let block_hash = devnet.create_block().await.unwrap();
+        accept_on_l1(&devnet, &BlockId::Hash(block_hash)).await;
+
+        let rpc_error = devnet
+            .send_custom_rpc(
+                "devnet_abortBlocks",
+                json!({ "starting_block_id": BlockId::Hash(block_hash) }),
+            )
+            .await
+            .unwrap_err();
+        assert!(rpc_error.message.contains("Block is already accepted on L1"));
+        assert_block_status(&devnet, block_hash, BlockStatus::AcceptedOnL1).await;
+    }
+}
@@ -6,7 +6,8 @@ mod test_account_selection {
 
     use serde_json::json;
     use starknet_core::constants::{
-        CAIRO_1_ACCOUNT_CONTRACT_SIERRA_HASH, CAIRO_1_ACCOUNT_CONTRACT_SIERRA_PATH,
+        CAIRO_0_ACCOUNT_CONTRACT_HASH, CAIRO_1_ACCOUNT_CONTRACT_SIERRA_HASH,
+        CAIRO_1_ACCOUNT_CONTRACT_SIERRA_PATH,
     };
     use starknet_core::utils::exported_test_utils::dummy_cairo_0_contract_class;
     use starknet_rs_accounts::{Account, Call, ExecutionEncoding, SingleOwnerAccount};
@@ -17,12 +18,12 @@ mod test_account_selection {
         get_selector_from_name, get_udc_deployed_address, UdcUniqueness,
     };
     use starknet_rs_providers::Provider;
-    use starknet_rs_signers::LocalWallet;
+    use starknet_rs_signers::{LocalWallet, SigningKey};
     use starknet_types::felt::felt_from_prefixed_hex;
     use starknet_types::rpc::transaction_receipt::FeeUnit;
 
     use crate::common::background_devnet::BackgroundDevnet;
-    use crate::common::constants::MAINNET_URL;
+    use crate::common::constants::{MAINNET_URL, PREDEPLOYED_ACCOUNT_INITIAL_BALANCE};
     use crate::common::reqwest_client::GetReqwestSender;
     use crate::common::utils::{
         assert_tx_successful, deploy_argent_account, deploy_oz_account,
@@ -383,4 +384,132 @@ mod test_account_selection {
             assert_eq!(http_response, json_rpc_response);
         }
     }
+
+    #[tokio::test]
+    async fn predeployed_accounts_from_accounts_file() {
+        let cli_args = ["--accounts-file", "test_data/accounts_file.yaml"];
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&cli_args).await.unwrap();
+
+        let accounts = get_predeployed_accounts(&devnet, json!({"with_balance": true})).await;
+        let accounts = accounts.as_array().unwrap();
+        assert_eq!(accounts.len(), 2);
+
+        assert_eq!(accounts[0]["private_key"], json!("0xc248668388dbe9acdfa3bc734cc2d57a"));
+        assert_eq!(
+            accounts[0]["balance"],
+            json!({
+                "eth": { "amount": "1000000000000000000000", "unit": "WEI" },
+                "strk": { "amount": "2000000000000000000000", "unit": "FRI" },
+            })
+        );
+
+        // the account specified by its address and public key has no known private key
+        let account_address =
+            felt_from_prefixed_hex(accounts[1]["address"].as_str().unwrap()).unwrap();
+        assert_eq!(account_address, Felt::from(0x1234));
+        assert!(accounts[1].get("private_key").is_none());
+        assert_eq!(
+            devnet
+                .json_rpc_client
+                .get_class_hash_at(BlockId::Tag(BlockTag::Latest), account_address)
+                .await
+                .unwrap(),
+            felt_from_prefixed_hex(CAIRO_0_ACCOUNT_CONTRACT_HASH).unwrap()
+        );
+
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        can_declare_deploy_invoke_cairo1_using_account(&devnet, &signer, account_address).await;
+    }
+
+    #[tokio::test]
+    async fn predeployed_accounts_derived_from_mnemonic() {
+        let mnemonic = "test test test test test test test test test test test junk";
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--mnemonic", mnemonic]).await.unwrap();
+        let accounts = get_predeployed_accounts(&devnet, json!({})).await;
+
+        // the derivation doesn't depend on the seed
+        let devnet_with_other_seed =
+            BackgroundDevnet::spawn_with_additional_args(&["--mnemonic", mnemonic, "--seed", "43"])
+                .await
+                .unwrap();
+        assert_eq!(get_predeployed_accounts(&devnet_with_other_seed, json!({})).await, accounts);
+
+        let devnet_with_other_path = BackgroundDevnet::spawn_with_additional_args(&[
+            "--mnemonic",
+            mnemonic,
+            "--derivation-path",
+            "m/44'/9004'/1'/0",
+        ])
+        .await
+        .unwrap();
+        assert_ne!(get_predeployed_accounts(&devnet_with_other_path, json!({})).await, accounts);
+
+        let devnet_with_argent_scheme = BackgroundDevnet::spawn_with_additional_args(&[
+            "--mnemonic",
+            mnemonic,
+            "--mnemonic-scheme",
+            "argent",
+        ])
+        .await
+        .unwrap();
+        let argent_accounts = get_predeployed_accounts(&devnet_with_argent_scheme, json!({})).await;
+        assert_eq!(
+            felt_from_prefixed_hex(argent_accounts[0]["private_key"].as_str().unwrap()).unwrap(),
+            felt_from_prefixed_hex(
+                "0xeb2609ea42924e99d0d5aa50ef056210a985a69bc53b50e48cfa3ae954ffc0"
+            )
+            .unwrap()
+        );
+
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        can_declare_deploy_invoke_cairo1_using_account(&devnet, &signer, account_address).await;
+    }
+
+    #[tokio::test]
+    async fn thousands_of_lazily_predeployed_accounts() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--accounts",
+            "5000",
+            "--lazy-predeployment",
+        ])
+        .await
+        .unwrap();
+
+        let accounts = get_predeployed_accounts(&devnet, json!({})).await;
+        let accounts = accounts.as_array().unwrap();
+        assert_eq!(accounts.len(), 5000);
+
+        // the same accounts are predeployed as without lazy predeployment
+        let eager_devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--accounts", "5"]).await.unwrap();
+        let eager_accounts = get_predeployed_accounts(&eager_devnet, json!({})).await;
+        assert_eq!(&accounts[..5], eager_accounts.as_array().unwrap());
+
+        let last_account = accounts.last().unwrap();
+        let account_address =
+            felt_from_prefixed_hex(last_account["address"].as_str().unwrap()).unwrap();
+        let private_key =
+            felt_from_prefixed_hex(last_account["private_key"].as_str().unwrap()).unwrap();
+
+        let class_hash = devnet
+            .json_rpc_client
+            .get_class_hash_at(BlockId::Tag(BlockTag::Latest), account_address)
+            .await
+            .unwrap();
+        assert_eq!(
+            class_hash,
+            felt_from_prefixed_hex(CAIRO_1_ACCOUNT_CONTRACT_SIERRA_HASH).unwrap()
+        );
+
+        let initial_balance =
+            devnet.get_balance_latest(&account_address, FeeUnit::WEI).await.unwrap();
+        assert_eq!(initial_balance, Felt::from(PREDEPLOYED_ACCOUNT_INITIAL_BALANCE));
+
+        let signer = LocalWallet::from(SigningKey::from_secret_scalar(private_key));
+        can_declare_deploy_invoke_cairo1_using_account(&devnet, &signer, account_address).await;
+
+        let balance = devnet.get_balance_latest(&account_address, FeeUnit::WEI).await.unwrap();
+        assert!(balance < initial_balance);
+    }
 }
@@ -20,9 +20,11 @@ mod dump_and_load_tests {
 
     use starknet_rs_accounts::{Account, ExecutionEncoding, SingleOwnerAccount};
     use starknet_rs_contract::ContractFactory;
-    use starknet_rs_core::types::Felt;
+    use starknet_rs_core::types::{BlockId, BlockTag, Felt};
 
-    use crate::common::utils::get_events_contract_in_sierra_and_compiled_class_hash;
+    use crate::common::utils::{
+        declare_deploy_v1, get_events_contract_in_sierra_and_compiled_class_hash,
+    };
 
     async fn dump_load_dump_load(mode: &str) {
         let dump_file =
@@ -547,4 +549,340 @@ mod dump_and_load_tests {
         assert_eq!(latest_block.block_number, 1);
         assert_eq!(latest_block.timestamp, past_time);
     }
+
+    async fn dump_state_and_load(dump_path: &str) {
+        let devnet_dump = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-path",
+            dump_path,
+            "--dump-on",
+            "exit",
+            "--dump-format",
+            "state",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let (signer, account_address) = devnet_dump.get_first_predeployed_account().await;
+        let predeployed_account = Arc::new(SingleOwnerAccount::new(
+            devnet_dump.clone_provider(),
+            signer,
+            account_address,
+            constants::CHAIN_ID,
+            ExecutionEncoding::New,
+        ));
+        let (contract_class, casm_hash) = get_events_contract_in_sierra_and_compiled_class_hash();
+        let (class_hash, contract_address) =
+            declare_deploy_v1(predeployed_account, contract_class, casm_hash, &[]).await.unwrap();
+
+        let mint_tx_hash = devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        devnet_dump.create_block().await.unwrap();
+
+        let latest_block = devnet_dump.get_latest_block_with_tx_hashes().await.unwrap();
+        let mint_receipt =
+            devnet_dump.json_rpc_client.get_transaction_receipt(mint_tx_hash).await.unwrap();
+
+        send_ctrl_c_signal_and_wait(&devnet_dump.process).await;
+
+        let devnet_load = BackgroundDevnet::spawn_with_additional_args(&["--dump-path", dump_path])
+            .await
+            .expect("Could not start Devnet");
+
+        let loaded_latest_block = devnet_load.get_latest_block_with_tx_hashes().await.unwrap();
+        assert_eq!(loaded_latest_block, latest_block);
+
+        let loaded_mint_receipt =
+            devnet_load.json_rpc_client.get_transaction_receipt(mint_tx_hash).await.unwrap();
+        assert_eq!(loaded_mint_receipt, mint_receipt);
+
+        let loaded_balance =
+            devnet_load.get_balance_latest(&Felt::from(DUMMY_ADDRESS), FeeUnit::WEI).await.unwrap();
+        assert_eq!(loaded_balance, Felt::from(DUMMY_AMOUNT));
+
+        let loaded_class_hash = devnet_load
+            .json_rpc_client
+            .get_class_hash_at(BlockId::Tag(BlockTag::Latest), contract_address)
+            .await
+            .unwrap();
+        assert_eq!(loaded_class_hash, class_hash);
+
+        // the loaded Devnet can still execute transactions
+        devnet_load.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let balance_after_mint =
+            devnet_load.get_balance_latest(&Felt::from(DUMMY_ADDRESS), FeeUnit::WEI).await.unwrap();
+        assert_eq!(balance_after_mint, Felt::from(DUMMY_AMOUNT * 2));
+    }
+
+    #[tokio::test]
+    async fn dump_state_on_exit_and_load() {
+        let dump_file = UniqueAutoDeletableFile::new("dump_state_on_exit");
+        dump_state_and_load(&dump_file.path).await;
+    }
+
+    #[tokio::test]
+    async fn dump_compressed_state_on_exit_and_load() {
+        let mut dump_file = UniqueAutoDeletableFile::new("dump_compressed_state_on_exit");
+        dump_file.path.push_str(".gz");
+        dump_state_and_load(&dump_file.path).await;
+
+        let dump_content = std::fs::read(&dump_file.path).unwrap();
+        assert_eq!(dump_content[..2], [0x1f, 0x8b]);
+    }
+
+    #[tokio::test]
+    async fn dump_state_on_block_and_load_via_endpoint() {
+        let dump_file = UniqueAutoDeletableFile::new("dump_state_on_block");
+        let devnet_dump = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-path",
+            &dump_file.path,
+            "--dump-on",
+            "block",
+            "--dump-format",
+            "state",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let latest_block = devnet_dump.get_latest_block_with_tx_hashes().await.unwrap();
+
+        let devnet_load = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        devnet_load.mint(DUMMY_ADDRESS, DUMMY_AMOUNT * 10).await;
+        devnet_load
+            .send_custom_rpc("devnet_load", json!({ "path": dump_file.path }))
+            .await
+            .unwrap();
+
+        let loaded_latest_block = devnet_load.get_latest_block_with_tx_hashes().await.unwrap();
+        assert_eq!(loaded_latest_block, latest_block);
+
+        let loaded_balance =
+            devnet_load.get_balance_latest(&Felt::from(DUMMY_ADDRESS), FeeUnit::WEI).await.unwrap();
+        assert_eq!(loaded_balance, Felt::from(DUMMY_AMOUNT * 2));
+    }
+
+    #[tokio::test]
+    async fn load_state_dump_fails_when_dumping_events() {
+        let dump_file = UniqueAutoDeletableFile::new("dump_state_load_into_events");
+        let devnet_dump = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-on",
+            "request",
+            "--dump-format",
+            "state",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        devnet_dump
+            .send_custom_rpc("devnet_dump", json!({ "path": dump_file.path }))
+            .await
+            .unwrap();
+
+        let devnet_load = BackgroundDevnet::spawn_with_additional_args(&["--dump-on", "request"])
+            .await
+            .expect("Could not start Devnet");
+        let load_error = devnet_load
+            .send_custom_rpc("devnet_load", json!({ "path": dump_file.path }))
+            .await
+            .unwrap_err();
+        assert!(load_error.message.contains("--dump-format state"), "{load_error:?}");
+    }
+
+    #[tokio::test]
+    async fn load_dump_with_different_seed_fails() {
+        let dump_file = UniqueAutoDeletableFile::new("dump_with_different_seed");
+        let devnet_dump = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-path",
+            &dump_file.path,
+            "--dump-on",
+            "request",
+            "--seed",
+            "1",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        devnet_dump.send_custom_rpc("devnet_dump", json!({})).await.unwrap();
+
+        let devnet_load = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let load_error = devnet_load
+            .send_custom_rpc("devnet_load", json!({ "path": dump_file.path }))
+            .await
+            .unwrap_err();
+        assert!(
+            load_error
+                .message
+                .contains("seed is 1 in the dump, but 42 in this Devnet (set via --seed)"),
+            "{load_error:?}"
+        );
+
+        // nothing was loaded
+        let balance =
+            devnet_load.get_balance_latest(&Felt::from(DUMMY_ADDRESS), FeeUnit::WEI).await.unwrap();
+        assert_eq!(balance, Felt::ZERO);
+
+        // loading on startup fails as well
+        let devnet_load_on_startup =
+            BackgroundDevnet::spawn_with_additional_args(&["--dump-path", &dump_file.path]).await;
+        assert!(devnet_load_on_startup.is_err());
+    }
+
+    #[tokio::test]
+    async fn loaded_blocks_and_receipts_are_identical() {
+        let dump_file = UniqueAutoDeletableFile::new("dump_identical_blocks");
+        let devnet_dump = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-path",
+            &dump_file.path,
+            "--dump-on",
+            "exit",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let mint_tx_hash = devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        devnet_dump
+            .send_custom_rpc(
+                "devnet_setGasPrice",
+                json!({ "gas_price_wei": 1_000_000, "generate_block": true }),
+            )
+            .await
+            .unwrap();
+        devnet_dump.send_custom_rpc("devnet_increaseTime", json!({ "time": 100 })).await.unwrap();
+        let mint_after_gas_change_tx_hash = devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let latest_block_number = devnet_dump.json_rpc_client.block_number().await.unwrap();
+        let mut blocks = vec![];
+        for block_number in 0..=latest_block_number {
+            let block_id = BlockId::Number(block_number);
+            blocks.push(
+                devnet_dump.json_rpc_client.get_block_with_tx_hashes(block_id).await.unwrap(),
+            );
+        }
+        let mut receipts = vec![];
+        for tx_hash in [mint_tx_hash, mint_after_gas_change_tx_hash] {
+            receipts
+                .push(devnet_dump.json_rpc_client.get_transaction_receipt(tx_hash).await.unwrap());
+        }
+
+        send_ctrl_c_signal_and_wait(&devnet_dump.process).await;
+        // so that timestamps not restored from the dump would differ
+        tokio::time::sleep(time::Duration::from_secs(1)).await;
+
+        let devnet_load =
+            BackgroundDevnet::spawn_with_additional_args(&["--dump-path", &dump_file.path])
+                .await
+                .expect("Could not start Devnet");
+
+        assert_eq!(devnet_load.json_rpc_client.block_number().await.unwrap(), latest_block_number);
+        for (block_number, block) in (0..=latest_block_number).zip(blocks) {
+            let block_id = BlockId::Number(block_number);
+            let loaded_block =
+                devnet_load.json_rpc_client.get_block_with_tx_hashes(block_id).await.unwrap();
+            assert_eq!(loaded_block, block);
+        }
+        for (tx_hash, receipt) in
+            [mint_tx_hash, mint_after_gas_change_tx_hash].into_iter().zip(receipts)
+        {
+            let loaded_receipt =
+                devnet_load.json_rpc_client.get_transaction_receipt(tx_hash).await.unwrap();
+            assert_eq!(loaded_receipt, receipt);
+        }
+    }
+
+    #[tokio::test]
+    async fn load_dump_until_event() {
+        let dump_file = UniqueAutoDeletableFile::new("dump_load_until_event");
+        let devnet_dump = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-path",
+            &dump_file.path,
+            "--dump-on",
+            "request",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        for _ in 0..3 {
+            devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        }
+        devnet_dump.send_custom_rpc("devnet_dump", json!({})).await.unwrap();
+
+        // only the mints are indexed, not the timestamps of the blocks they generated
+        let devnet_load = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-path",
+            &dump_file.path,
+            "--load-until-event",
+            "1",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        assert_eq!(devnet_load.json_rpc_client.block_number().await.unwrap(), 2);
+        let balance =
+            devnet_load.get_balance_latest(&Felt::from(DUMMY_ADDRESS), FeeUnit::WEI).await.unwrap();
+        assert_eq!(balance, Felt::from(DUMMY_AMOUNT * 2));
+
+        let load_error = devnet_load
+            .send_custom_rpc("devnet_load", json!({ "path": dump_file.path, "event_index": 3 }))
+            .await
+            .unwrap_err();
+        assert!(
+            load_error.message.contains("event index is 3, but the dump contains 3 events"),
+            "{load_error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn load_dump_until_block() {
+        let dump_file = UniqueAutoDeletableFile::new("dump_load_until_block");
+        let devnet_dump = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-path",
+            &dump_file.path,
+            "--dump-on",
+            "request",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        for _ in 0..3 {
+            devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        }
+        devnet_dump.send_custom_rpc("devnet_dump", json!({})).await.unwrap();
+
+        let devnet_load = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-path",
+            &dump_file.path,
+            "--load-until-block",
+            "2",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        assert_eq!(devnet_load.json_rpc_client.block_number().await.unwrap(), 2);
+        let balance =
+            devnet_load.get_balance_latest(&Felt::from(DUMMY_ADDRESS), FeeUnit::WEI).await.unwrap();
+        assert_eq!(balance, Felt::from(DUMMY_AMOUNT * 2));
+
+        devnet_load
+            .send_custom_rpc("devnet_load", json!({ "path": dump_file.path, "block_number": 1 }))
+            .await
+            .unwrap();
+        assert_eq!(devnet_load.json_rpc_client.block_number().await.unwrap(), 1);
+        let balance =
+            devnet_load.get_balance_latest(&Felt::from(DUMMY_ADDRESS), FeeUnit::WEI).await.unwrap();
+        assert_eq!(balance, Felt::from(DUMMY_AMOUNT));
+
+        let load_error = devnet_load
+            .send_custom_rpc("devnet_load", json!({ "path": dump_file.path, "block_number": 4 }))
+            .await
+            .unwrap_err();
+        assert!(
+            load_error.message.contains("block number is 4, but the dump contains blocks up to 3"),
+            "{load_error:?}"
+        );
+
+        let load_error = devnet_load
+            .send_custom_rpc(
+                "devnet_load",
+                json!({ "path": dump_file.path, "block_number": 1, "event_index": 1 }),
+            )
+            .await
+            .unwrap_err();
+        assert!(load_error.message.contains("Only one of"), "{load_error:?}");
+    }
 }
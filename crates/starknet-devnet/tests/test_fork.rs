@@ -4,6 +4,8 @@ mod fork_tests {
     use std::str::FromStr;
     use std::sync::Arc;
 
+    use serde_json::json;
+    use server::api::json_rpc::RpcSpecVersion;
     use server::test_utils::exported_test_utils::assert_contains;
     use starknet_rs_accounts::{
         Account, AccountFactory, AccountFactoryError, Call, ExecutionEncoding,
@@ -11,6 +13,7 @@ mod fork_tests {
     };
     use starknet_rs_contract::ContractFactory;
     use starknet_rs_core::types::contract::legacy::LegacyContractClass;
+    use starknet_rs_core::types::contract::CompiledClass;
     use starknet_rs_core::types::{
         BlockId, BlockTag, ContractClass, Felt, FunctionCall, MaybePendingBlockWithTxHashes,
         StarknetError,
@@ -241,6 +244,51 @@ mod fork_tests {
         assert_cairo1_classes_equal(&retrieved_class_by_address, &expected_sierra).unwrap();
     }
 
+    #[tokio::test]
+    async fn test_getting_compiled_casm_of_cairo1_class_from_origin() {
+        let origin_devnet = BackgroundDevnet::spawn_forkable_devnet().await.unwrap();
+
+        let (signer, account_address) = origin_devnet.get_first_predeployed_account().await;
+        let predeployed_account = Arc::new(SingleOwnerAccount::new(
+            origin_devnet.clone_provider(),
+            signer,
+            account_address,
+            constants::CHAIN_ID,
+            ExecutionEncoding::New,
+        ));
+
+        let (contract_class, casm_hash) = get_simple_contract_in_sierra_and_compiled_class_hash();
+        let declaration_result = predeployed_account
+            .declare_v2(Arc::new(contract_class), casm_hash)
+            .max_fee(Felt::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+
+        // the request is forwarded to the origin as is, so the origin has to serve v0.8 as well
+        let origin_url = format!("{}{}", origin_devnet.url, RpcSpecVersion::V0_8.route_path());
+        let fork_devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--fork-network",
+            origin_url.as_str(),
+            "--accounts",
+            "0",
+        ])
+        .await
+        .unwrap();
+
+        let compiled_casm = fork_devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_8.route_path(),
+                "starknet_getCompiledCasm",
+                json!({ "class_hash": declaration_result.class_hash }),
+            )
+            .await
+            .unwrap();
+
+        let compiled_casm: CompiledClass = serde_json::from_value(compiled_casm).unwrap();
+        assert_eq!(compiled_casm.class_hash().unwrap(), casm_hash);
+    }
+
     #[tokio::test]
     async fn test_origin_declare_deploy_fork_invoke() {
         let origin_devnet = BackgroundDevnet::spawn_forkable_devnet().await.unwrap();
@@ -0,0 +1,86 @@
+// must use `pub`: https://github.com/rust-lang/rust/issues/46379#issuecomment-548787629
+pub mod common;
+
+mod genesis_tests {
+    use starknet_core::constants::{
+        CAIRO_1_ACCOUNT_CONTRACT_SIERRA_HASH, ETH_ERC20_CONTRACT_ADDRESS,
+    };
+    use starknet_rs_accounts::{Account, Call, ExecutionEncoding, SingleOwnerAccount};
+    use starknet_rs_core::types::{BlockId, BlockTag, Felt};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_providers::Provider;
+    use starknet_rs_signers::{LocalWallet, SigningKey};
+    use starknet_types::felt::felt_from_prefixed_hex;
+    use starknet_types::rpc::transaction_receipt::FeeUnit;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants::{self, CAIRO_1_PANICKING_CONTRACT_SIERRA_PATH};
+    use crate::common::utils::get_flattened_sierra_contract_and_casm_hash;
+
+    const GENESIS_PATH: &str = "test_data/genesis.json";
+    const GENESIS_ACCOUNT_ADDRESS: u128 = 0x1234;
+    const GENESIS_ACCOUNT_BALANCE: u128 = 1_000_000_000_000_000_000_000;
+
+    #[tokio::test]
+    async fn genesis_classes_contracts_and_balances_are_present() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--genesis", GENESIS_PATH])
+            .await
+            .expect("Could not start Devnet");
+        let latest = BlockId::Tag(BlockTag::Latest);
+
+        let (panicking_contract, _) =
+            get_flattened_sierra_contract_and_casm_hash(CAIRO_1_PANICKING_CONTRACT_SIERRA_PATH);
+        devnet.json_rpc_client.get_class(latest, panicking_contract.class_hash()).await.unwrap();
+
+        let account_address = Felt::from(GENESIS_ACCOUNT_ADDRESS);
+        assert_eq!(
+            devnet.json_rpc_client.get_class_hash_at(latest, account_address).await.unwrap(),
+            felt_from_prefixed_hex(CAIRO_1_ACCOUNT_CONTRACT_SIERRA_HASH).unwrap()
+        );
+        assert_eq!(
+            devnet.get_balance_latest(&account_address, FeeUnit::WEI).await.unwrap(),
+            Felt::from(GENESIS_ACCOUNT_BALANCE)
+        );
+
+        // the genesis setup is repeated on restart
+        devnet.restart().await;
+        assert_eq!(
+            devnet.get_balance_latest(&account_address, FeeUnit::WEI).await.unwrap(),
+            Felt::from(GENESIS_ACCOUNT_BALANCE)
+        );
+    }
+
+    #[tokio::test]
+    async fn genesis_account_can_send_transactions() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--genesis", GENESIS_PATH])
+            .await
+            .expect("Could not start Devnet");
+
+        // the public key of the genesis account, passed to its constructor, belongs to this key
+        let signer = LocalWallet::from(SigningKey::from_secret_scalar(
+            felt_from_prefixed_hex(constants::PREDEPLOYED_ACCOUNT_PRIVATE_KEY).unwrap(),
+        ));
+        let account = SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            signer,
+            Felt::from(GENESIS_ACCOUNT_ADDRESS),
+            constants::CHAIN_ID,
+            ExecutionEncoding::New,
+        );
+
+        let recipient = Felt::ONE;
+        let amount = Felt::from(1_000);
+        account
+            .execute_v1(vec![Call {
+                to: felt_from_prefixed_hex(ETH_ERC20_CONTRACT_ADDRESS).unwrap(),
+                selector: get_selector_from_name("transfer").unwrap(),
+                calldata: vec![recipient, amount, Felt::ZERO],
+            }])
+            .max_fee(Felt::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(devnet.get_balance_latest(&recipient, FeeUnit::WEI).await.unwrap(), amount);
+    }
+}
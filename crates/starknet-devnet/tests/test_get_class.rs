@@ -3,9 +3,13 @@ pub mod common;
 mod get_class_tests {
     use std::sync::Arc;
 
+    use serde_json::json;
+    use server::api::json_rpc::RpcSpecVersion;
+    use server::rpc_core::error::ErrorCode;
     use starknet_rs_accounts::{Account, ExecutionEncoding, SingleOwnerAccount};
     use starknet_rs_core::chain_id;
     use starknet_rs_core::types::contract::legacy::LegacyContractClass;
+    use starknet_rs_core::types::contract::CompiledClass;
     use starknet_rs_core::types::{BlockId, BlockTag, ContractClass, Felt, StarknetError};
     use starknet_rs_providers::{Provider, ProviderError};
     use starknet_types::felt::felt_from_prefixed_hex;
@@ -339,4 +343,56 @@ mod get_class_tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_getting_compiled_casm_of_declared_cairo1_contract() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        let predeployed_account = Arc::new(SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            signer.clone(),
+            account_address,
+            chain_id::SEPOLIA,
+            ExecutionEncoding::New,
+        ));
+
+        let (contract_class, casm_class_hash) =
+            get_events_contract_in_sierra_and_compiled_class_hash();
+
+        // declare the contract
+        let declaration_result = predeployed_account
+            .declare_v2(Arc::new(contract_class), casm_class_hash)
+            .max_fee(Felt::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+
+        let compiled_casm = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_8.route_path(),
+                "starknet_getCompiledCasm",
+                json!({ "class_hash": declaration_result.class_hash }),
+            )
+            .await
+            .unwrap();
+
+        let compiled_casm: CompiledClass = serde_json::from_value(compiled_casm).unwrap();
+        assert_eq!(compiled_casm.class_hash().unwrap(), casm_class_hash);
+    }
+
+    #[tokio::test]
+    async fn test_getting_compiled_casm_of_undeclared_class() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        let rpc_error = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_8.route_path(),
+                "starknet_getCompiledCasm",
+                json!({ "class_hash": "0xdead" }),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(rpc_error.code, ErrorCode::ServerError(28));
+    }
 }
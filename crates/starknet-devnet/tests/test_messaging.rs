@@ -16,6 +16,8 @@ mod test_messaging {
 
     use ethers::prelude::*;
     use serde_json::{json, Value};
+    use server::api::json_rpc::RpcSpecVersion;
+    use server::rpc_core::error::ErrorCode;
     use starknet_rs_accounts::{
         Account, AccountError, Call, ConnectedAccount, ExecutionEncoding, SingleOwnerAccount,
     };
@@ -341,6 +343,54 @@ mod test_messaging {
         }
     }
 
+    #[tokio::test]
+    async fn messages_status_of_l1_transaction_can_be_retrieved() {
+        let (devnet, _, l1l2_contract_address) = setup_devnet(&["--account-class", "cairo1"]).await;
+        let user = Felt::ONE;
+        let l1_transaction_hash =
+            "0x2f0a3b9c8d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8";
+
+        // Use postman to send a message to l2 as if it was sent by the given l1 transaction
+        let body = devnet.send_custom_rpc("devnet_postmanSendMessageToL2", json!({
+            "l1_contract_address": MESSAGING_L1_ADDRESS,
+            "l2_contract_address": format!("0x{:64x}", l1l2_contract_address),
+            "entry_point_selector": format!("0x{:64x}", get_selector_from_name("deposit").unwrap()),
+            "payload": [to_hex_felt(&user), "0x1"],
+            "paid_fee_on_l1": "0x1234",
+            "nonce": "0x1",
+            "l1_transaction_hash": l1_transaction_hash,
+        }))
+        .await.unwrap();
+        let l1_handler_tx_hash = body.get("transaction_hash").unwrap();
+
+        let messages_status = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_8.route_path(),
+                "starknet_getMessagesStatus",
+                json!({ "transaction_hash": l1_transaction_hash }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            messages_status,
+            json!([{
+                "transaction_hash": l1_handler_tx_hash,
+                "finality_status": "ACCEPTED_ON_L2",
+                "execution_status": "SUCCEEDED",
+            }])
+        );
+
+        let rpc_error = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_8.route_path(),
+                "starknet_getMessagesStatus",
+                json!({ "transaction_hash": "0x1" }),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(rpc_error.code, ErrorCode::ServerError(29));
+    }
+
     #[tokio::test]
     async fn can_deploy_l1_messaging_contract() {
         let anvil = BackgroundAnvil::spawn().await.unwrap();
@@ -533,15 +583,19 @@ mod test_messaging {
 
         // Use postman to send a message to l2 without l1 - the message increments user balance
         let increment_amount = Felt::from_hex_unchecked("0xff");
+        let l1_transaction_hash =
+            "0x5e1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f9";
 
-        dumping_devnet.send_custom_rpc("devnet_postmanSendMessageToL2", json!({
+        let body = dumping_devnet.send_custom_rpc("devnet_postmanSendMessageToL2", json!({
             "l1_contract_address": MESSAGING_WHITELISTED_L1_CONTRACT,
             "l2_contract_address": format!("0x{:64x}", l1l2_contract_address),
             "entry_point_selector": format!("0x{:64x}", get_selector_from_name("deposit").unwrap()),
             "payload": [to_hex_felt(&user), to_hex_felt(&increment_amount)],
             "paid_fee_on_l1": "0x1234",
-            "nonce": "0x1"
+            "nonce": "0x1",
+            "l1_transaction_hash": l1_transaction_hash,
         })).await.unwrap();
+        let l1_handler_tx_hash = body.get("transaction_hash").unwrap().clone();
 
         assert_eq!(
             get_balance(&dumping_devnet, l1l2_contract_address, user).await,
@@ -563,5 +617,16 @@ mod test_messaging {
             get_balance(&loading_devnet, l1l2_contract_address, user).await,
             [user_balance + increment_amount]
         );
+
+        // the L1 transaction which sent the message is restored as well
+        let messages_status = loading_devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_8.route_path(),
+                "starknet_getMessagesStatus",
+                json!({ "transaction_hash": l1_transaction_hash }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(messages_status[0]["transaction_hash"], l1_handler_tx_hash);
     }
 }
@@ -0,0 +1,160 @@
+// must use `pub`: https://github.com/rust-lang/rust/issues/46379#issuecomment-548787629
+pub mod common;
+
+mod snapshot_tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+    use starknet_core::utils::exported_test_utils::dummy_cairo_0_contract_class;
+    use starknet_rs_accounts::{Account, ExecutionEncoding, SingleOwnerAccount};
+    use starknet_rs_core::types::contract::legacy::LegacyContractClass;
+    use starknet_rs_core::types::{BlockId, BlockTag, Felt, StarknetError};
+    use starknet_rs_providers::{Provider, ProviderError};
+    use starknet_types::rpc::transaction_receipt::FeeUnit;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants;
+    use crate::common::utils::remove_file;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    async fn snapshot(devnet: &BackgroundDevnet) -> u64 {
+        let snapshot_resp = devnet.send_custom_rpc("devnet_snapshot", json!({})).await.unwrap();
+        snapshot_resp["snapshot_id"].as_u64().unwrap()
+    }
+
+    async fn revert(devnet: &BackgroundDevnet, snapshot_id: u64) {
+        let revert_resp = devnet
+            .send_custom_rpc("devnet_revert", json!({ "snapshot_id": snapshot_id }))
+            .await
+            .unwrap();
+        assert_eq!(revert_resp, json!({}));
+    }
+
+    #[tokio::test]
+    async fn revert_restores_blocks_transactions_and_balances() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let address = Felt::from(DUMMY_ADDRESS);
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let block_before = devnet.get_latest_block_with_tx_hashes().await.unwrap();
+
+        let snapshot_id = snapshot(&devnet).await;
+
+        let reverted_mint_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        devnet.create_block().await.unwrap();
+        assert_eq!(
+            devnet.get_balance_latest(&address, FeeUnit::WEI).await.unwrap(),
+            Felt::from(2 * DUMMY_AMOUNT)
+        );
+
+        revert(&devnet, snapshot_id).await;
+
+        let block_after = devnet.get_latest_block_with_tx_hashes().await.unwrap();
+        assert_eq!(block_after, block_before);
+        assert_eq!(
+            devnet.get_balance_latest(&address, FeeUnit::WEI).await.unwrap(),
+            Felt::from(DUMMY_AMOUNT)
+        );
+        match devnet.json_rpc_client.get_transaction_by_hash(reverted_mint_hash).await {
+            Err(ProviderError::StarknetError(StarknetError::TransactionHashNotFound)) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        // new transactions are added on top of the reverted state
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let new_block = devnet.get_latest_block_with_tx_hashes().await.unwrap();
+        assert_eq!(new_block.block_number, block_before.block_number + 1);
+        assert_eq!(
+            devnet.get_balance_latest(&address, FeeUnit::WEI).await.unwrap(),
+            Felt::from(2 * DUMMY_AMOUNT)
+        );
+    }
+
+    #[tokio::test]
+    async fn revert_undeclares_class_and_allows_declaring_it_again() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        let (signer, address) = devnet.get_first_predeployed_account().await;
+        let predeployed_account = SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            signer,
+            address,
+            constants::CHAIN_ID,
+            ExecutionEncoding::New,
+        );
+
+        let contract_json = dummy_cairo_0_contract_class();
+        let contract_artifact: Arc<LegacyContractClass> =
+            Arc::new(serde_json::from_value(contract_json.inner).unwrap());
+        let declare = || {
+            predeployed_account
+                .declare_legacy(contract_artifact.clone())
+                .nonce(Felt::ZERO)
+                .max_fee(Felt::from(1e18 as u128))
+        };
+
+        let snapshot_id = snapshot(&devnet).await;
+
+        let declaration = declare().send().await.unwrap();
+        devnet
+            .json_rpc_client
+            .get_class(BlockId::Tag(BlockTag::Latest), declaration.class_hash)
+            .await
+            .unwrap();
+
+        revert(&devnet, snapshot_id).await;
+
+        match devnet
+            .json_rpc_client
+            .get_class(BlockId::Tag(BlockTag::Latest), declaration.class_hash)
+            .await
+        {
+            Err(ProviderError::StarknetError(StarknetError::ClassHashNotFound)) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        // the account nonce is reverted as well, so the same transaction can be sent again
+        let redeclaration = declare().send().await.unwrap();
+        assert_eq!(redeclaration, declaration);
+    }
+
+    #[tokio::test]
+    async fn reverting_to_nonexistent_or_discarded_snapshot_fails() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        let first_snapshot_id = snapshot(&devnet).await;
+        let second_snapshot_id = snapshot(&devnet).await;
+        assert_eq!((first_snapshot_id, second_snapshot_id), (0, 1));
+
+        revert(&devnet, first_snapshot_id).await;
+
+        // reverting discards the snapshot reverted to and all the later ones
+        for snapshot_id in [first_snapshot_id, second_snapshot_id, 42] {
+            let rpc_error = devnet
+                .send_custom_rpc("devnet_revert", json!({ "snapshot_id": snapshot_id }))
+                .await
+                .unwrap_err();
+            assert_eq!(rpc_error.message, format!("No snapshot with id {snapshot_id}"));
+        }
+    }
+
+    #[tokio::test]
+    async fn snapshot_not_supported_when_dumping_on_block() {
+        let dump_file_name = "dump_on_block_snapshot";
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-path",
+            dump_file_name,
+            "--dump-on",
+            "block",
+        ])
+        .await
+        .unwrap();
+
+        let rpc_error = devnet.send_custom_rpc("devnet_snapshot", json!({})).await.unwrap_err();
+        assert!(rpc_error.message.contains("Snapshots are not supported"));
+
+        remove_file(dump_file_name);
+    }
+}
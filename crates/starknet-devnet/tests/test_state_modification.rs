@@ -0,0 +1,294 @@
+// must use `pub`: https://github.com/rust-lang/rust/issues/46379#issuecomment-548787629
+pub mod common;
+
+mod state_modification_tests {
+    use serde_json::json;
+    use starknet_core::constants::{CAIRO_1_ERC20_CONTRACT_CLASS_HASH, ETH_ERC20_CONTRACT_ADDRESS};
+    use starknet_rs_accounts::{Account, Call, ExecutionEncoding, SingleOwnerAccount};
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, Felt, FunctionCall, MaybePendingStateUpdate, StorageEntry,
+    };
+    use starknet_rs_core::utils::{get_selector_from_name, get_storage_var_address};
+    use starknet_rs_providers::Provider;
+    use starknet_types::felt::felt_from_prefixed_hex;
+    use starknet_types::rpc::transaction_receipt::FeeUnit;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants;
+    use crate::common::utils::{send_ctrl_c_signal_and_wait, UniqueAutoDeletableFile};
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1_000;
+
+    /// Sets the ETH balance of `DUMMY_ADDRESS` to `DUMMY_AMOUNT` by writing to the ERC20 storage
+    async fn set_dummy_balance(devnet: &BackgroundDevnet) -> (Felt, Felt) {
+        let contract_address = felt_from_prefixed_hex(ETH_ERC20_CONTRACT_ADDRESS).unwrap();
+        let key = get_storage_var_address("ERC20_balances", &[Felt::from(DUMMY_ADDRESS)]).unwrap();
+
+        let resp = devnet
+            .send_custom_rpc(
+                "devnet_setStorageAt",
+                json!({
+                    "contract_address": contract_address,
+                    "key": key,
+                    "value": Felt::from(DUMMY_AMOUNT),
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp, json!({}));
+
+        (contract_address, key)
+    }
+
+    #[tokio::test]
+    async fn set_storage_at_changes_pending_state() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (contract_address, key) = set_dummy_balance(&devnet).await;
+
+        let address = Felt::from(DUMMY_ADDRESS);
+        let pending_balance =
+            devnet.get_balance_by_tag(&address, FeeUnit::WEI, BlockTag::Pending).await.unwrap();
+        assert_eq!(pending_balance, Felt::from(DUMMY_AMOUNT));
+
+        let pending = BlockId::Tag(BlockTag::Pending);
+        match devnet.json_rpc_client.get_state_update(pending).await.unwrap() {
+            MaybePendingStateUpdate::PendingUpdate(state_update) => {
+                let storage_diff = state_update.state_diff.storage_diffs.first().unwrap();
+                assert_eq!(storage_diff.address, contract_address);
+                assert_eq!(
+                    storage_diff.storage_entries,
+                    vec![StorageEntry { key, value: Felt::from(DUMMY_AMOUNT) }]
+                );
+            }
+            other => panic!("Unexpected state update: {other:?}"),
+        }
+
+        // the change is included in the next block
+        let block_hash = devnet.create_block().await.unwrap();
+        let latest_balance = devnet.get_balance_latest(&address, FeeUnit::WEI).await.unwrap();
+        assert_eq!(latest_balance, Felt::from(DUMMY_AMOUNT));
+        match devnet.json_rpc_client.get_state_update(BlockId::Hash(block_hash)).await.unwrap() {
+            MaybePendingStateUpdate::Update(state_update) => {
+                assert_eq!(state_update.state_diff.storage_diffs.len(), 1)
+            }
+            other => panic!("Unexpected state update: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn set_storage_at_of_undeployed_contract_fails() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let error = devnet
+            .send_custom_rpc(
+                "devnet_setStorageAt",
+                json!({ "contract_address": "0x1", "key": "0x2", "value": "0x3" }),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.code.code(), 20);
+        assert_eq!(error.message, "Contract not found");
+    }
+
+    #[tokio::test]
+    async fn set_storage_at_is_dumped_and_loaded() {
+        let dump_file = UniqueAutoDeletableFile::new("dump_set_storage_at");
+        let devnet_dump = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-path",
+            &dump_file.path,
+            "--dump-on",
+            "exit",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        set_dummy_balance(&devnet_dump).await;
+        devnet_dump.create_block().await.unwrap();
+        send_ctrl_c_signal_and_wait(&devnet_dump.process).await;
+
+        let devnet_load =
+            BackgroundDevnet::spawn_with_additional_args(&["--dump-path", &dump_file.path])
+                .await
+                .expect("Could not start Devnet");
+        let loaded_balance =
+            devnet_load.get_balance_latest(&Felt::from(DUMMY_ADDRESS), FeeUnit::WEI).await.unwrap();
+        assert_eq!(loaded_balance, Felt::from(DUMMY_AMOUNT));
+    }
+
+    #[tokio::test]
+    async fn set_nonce_allows_sending_transaction_with_that_nonce() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        let nonce = Felt::from(5);
+
+        devnet
+            .send_custom_rpc(
+                "devnet_setNonce",
+                json!({ "contract_address": account_address, "nonce": nonce }),
+            )
+            .await
+            .unwrap();
+        let pending = BlockId::Tag(BlockTag::Pending);
+        assert_eq!(
+            devnet.json_rpc_client.get_nonce(pending, account_address).await.unwrap(),
+            nonce
+        );
+
+        let account = SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            signer,
+            account_address,
+            constants::CHAIN_ID,
+            ExecutionEncoding::New,
+        );
+        account
+            .execute_v1(vec![Call {
+                to: felt_from_prefixed_hex(ETH_ERC20_CONTRACT_ADDRESS).unwrap(),
+                selector: get_selector_from_name("transfer").unwrap(),
+                calldata: vec![Felt::from(DUMMY_ADDRESS), Felt::from(DUMMY_AMOUNT), Felt::ZERO],
+            }])
+            .nonce(nonce)
+            .max_fee(Felt::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+
+        let latest = BlockId::Tag(BlockTag::Latest);
+        assert_eq!(
+            devnet.json_rpc_client.get_nonce(latest, account_address).await.unwrap(),
+            nonce + Felt::ONE
+        );
+    }
+
+    #[tokio::test]
+    async fn set_nonce_of_undeployed_contract_fails() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let error = devnet
+            .send_custom_rpc(
+                "devnet_setNonce",
+                json!({ "contract_address": "0x1", "nonce": "0x2" }),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.code.code(), 20);
+        assert_eq!(error.message, "Contract not found");
+    }
+
+    #[tokio::test]
+    async fn replace_class_changes_code_of_contract() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (_, account_address) = devnet.get_first_predeployed_account().await;
+        let class_hash = felt_from_prefixed_hex(CAIRO_1_ERC20_CONTRACT_CLASS_HASH).unwrap();
+
+        let resp = devnet
+            .send_custom_rpc(
+                "devnet_replaceClass",
+                json!({ "contract_address": account_address, "class_hash": class_hash }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp, json!({}));
+
+        let pending = BlockId::Tag(BlockTag::Pending);
+        assert_eq!(
+            devnet.json_rpc_client.get_class_hash_at(pending, account_address).await.unwrap(),
+            class_hash
+        );
+
+        // the account now runs the code of the ERC20 class, but keeps its own storage
+        let balance = devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address: account_address,
+                    entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                    calldata: vec![Felt::from(DUMMY_ADDRESS)],
+                },
+                pending,
+            )
+            .await
+            .unwrap();
+        assert_eq!(balance, vec![Felt::ZERO, Felt::ZERO]);
+    }
+
+    #[tokio::test]
+    async fn replace_class_with_undeclared_class_fails() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (_, account_address) = devnet.get_first_predeployed_account().await;
+
+        let error = devnet
+            .send_custom_rpc(
+                "devnet_replaceClass",
+                json!({ "contract_address": account_address, "class_hash": "0x42" }),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.code.code(), 28);
+        assert_eq!(error.message, "Class hash not found");
+    }
+
+    #[tokio::test]
+    async fn deploy_at_places_contract_with_storage() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let contract_address = Felt::from(0x1234);
+        let class_hash = felt_from_prefixed_hex(CAIRO_1_ERC20_CONTRACT_CLASS_HASH).unwrap();
+        let key = get_storage_var_address("ERC20_balances", &[Felt::from(DUMMY_ADDRESS)]).unwrap();
+
+        let resp = devnet
+            .send_custom_rpc(
+                "devnet_deployAt",
+                json!({
+                    "contract_address": contract_address,
+                    "class_hash": class_hash,
+                    "storage": [{ "key": key, "value": Felt::from(DUMMY_AMOUNT) }],
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp, json!({}));
+
+        let pending = BlockId::Tag(BlockTag::Pending);
+        assert_eq!(
+            devnet.json_rpc_client.get_class_hash_at(pending, contract_address).await.unwrap(),
+            class_hash
+        );
+        let balance = devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address,
+                    entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                    calldata: vec![Felt::from(DUMMY_ADDRESS)],
+                },
+                pending,
+            )
+            .await
+            .unwrap();
+        assert_eq!(balance, vec![Felt::from(DUMMY_AMOUNT), Felt::ZERO]);
+    }
+
+    #[tokio::test]
+    async fn deploy_at_occupied_address_fails() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let class_hash = felt_from_prefixed_hex(CAIRO_1_ERC20_CONTRACT_CLASS_HASH).unwrap();
+
+        let error = devnet
+            .send_custom_rpc(
+                "devnet_deployAt",
+                json!({
+                    "contract_address": ETH_ERC20_CONTRACT_ADDRESS,
+                    "class_hash": class_hash,
+                }),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.message,
+            format!(
+                "A contract is already deployed at address {}",
+                ETH_ERC20_CONTRACT_ADDRESS.to_lowercase()
+            )
+        );
+    }
+}
@@ -0,0 +1,76 @@
+pub mod common;
+
+mod storage_proof_tests {
+    use serde_json::json;
+    use server::api::json_rpc::RpcSpecVersion;
+    use server::rpc_core::error::ErrorCode;
+    use starknet_rs_core::types::{BlockId, BlockTag, Felt};
+    use starknet_rs_core::utils::get_storage_var_address;
+    use starknet_rs_providers::Provider;
+    use starknet_types::rpc::state::StorageProof;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+
+    #[tokio::test]
+    async fn storage_proof_of_predeployed_account_matches_latest_state() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (_, account_address) = devnet.get_first_predeployed_account().await;
+
+        let public_key_key = get_storage_var_address("Account_public_key", &[]).unwrap();
+        let proof = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_8.route_path(),
+                "starknet_getStorageProof",
+                json!({
+                    "block_id": "latest",
+                    "contract_addresses": [account_address],
+                    "contracts_storage_keys": [{
+                        "contract_address": account_address,
+                        "storage_keys": [public_key_key],
+                    }],
+                }),
+            )
+            .await
+            .unwrap();
+        let proof: StorageProof = serde_json::from_value(proof).unwrap();
+
+        let latest_block = devnet.get_latest_block_with_tx_hashes().await.unwrap();
+        assert_eq!(proof.global_roots.block_hash, latest_block.block_hash);
+        assert_ne!(proof.global_roots.contracts_tree_root, Felt::ZERO);
+        assert_ne!(proof.global_roots.classes_tree_root, Felt::ZERO);
+
+        let class_hash = devnet
+            .json_rpc_client
+            .get_class_hash_at(BlockId::Tag(BlockTag::Latest), account_address)
+            .await
+            .unwrap();
+        let leaf_data = &proof.contracts_proof.contract_leaves_data;
+        assert_eq!(leaf_data.len(), 1);
+        assert_eq!(leaf_data[0].class_hash, class_hash);
+        assert_eq!(leaf_data[0].nonce, Felt::ZERO);
+        assert_ne!(leaf_data[0].storage_root, Felt::ZERO);
+
+        assert!(!proof.contracts_proof.nodes.is_empty());
+        assert!(proof.classes_proof.is_empty());
+        assert_eq!(proof.contracts_storage_proofs.len(), 1);
+        assert!(!proof.contracts_storage_proofs[0].is_empty());
+    }
+
+    #[tokio::test]
+    async fn storage_proof_not_supported_for_pending_and_unarchived_blocks() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        devnet.create_block().await.unwrap();
+
+        for block_id in [json!("pending"), json!({ "block_number": 0 })] {
+            let rpc_error = devnet
+                .send_custom_rpc_to_path(
+                    RpcSpecVersion::V0_8.route_path(),
+                    "starknet_getStorageProof",
+                    json!({ "block_id": block_id }),
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(rpc_error.code, ErrorCode::ServerError(42));
+        }
+    }
+}
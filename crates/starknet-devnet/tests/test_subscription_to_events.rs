@@ -0,0 +1,156 @@
+pub mod common;
+
+mod event_subscription_support {
+    use serde_json::json;
+    use server::rpc_core::error::ErrorCode;
+    use starknet_core::constants::{ETH_ERC20_CONTRACT_ADDRESS, STRK_ERC20_CONTRACT_ADDRESS};
+    use starknet_rs_core::types::{BlockId, BlockTag, EmittedEvent, EventFilter, Felt};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_providers::Provider;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::utils::{
+        assert_no_notifications, connect_to_ws, receive_notification, subscribe, WsStream,
+    };
+
+    const EVENTS_NOTIFICATION: &str = "starknet_subscriptionEvents";
+
+    async fn receive_event(
+        ws: &mut WsStream,
+        subscription_id: u64,
+    ) -> Result<EmittedEvent, anyhow::Error> {
+        let event = receive_notification(ws, EVENTS_NOTIFICATION, subscription_id).await?;
+        Ok(serde_json::from_value(event)?)
+    }
+
+    /// Returns the events of the latest block that satisfy the filter
+    async fn get_latest_events(
+        devnet: &BackgroundDevnet,
+        address: Option<Felt>,
+        keys: Option<Vec<Vec<Felt>>>,
+    ) -> Vec<EmittedEvent> {
+        let latest = BlockId::Tag(BlockTag::Latest);
+        let filter =
+            EventFilter { from_block: Some(latest), to_block: Some(latest), address, keys };
+        devnet.json_rpc_client.get_events(filter, None, 1000).await.unwrap().events
+    }
+
+    #[tokio::test]
+    async fn subscribe_to_all_events() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let subscription_id =
+            subscribe(&mut ws, "starknet_subscribeEvents", json!({})).await.unwrap();
+
+        devnet.mint(0x1, 1).await;
+        let expected_events = get_latest_events(&devnet, None, None).await;
+        assert!(!expected_events.is_empty());
+
+        for expected_event in expected_events {
+            let event = receive_event(&mut ws, subscription_id).await.unwrap();
+            assert_eq!(event, expected_event);
+        }
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn subscribe_to_events_filtered_by_address_and_keys() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let address = Felt::from_hex_unchecked(ETH_ERC20_CONTRACT_ADDRESS);
+        let keys = vec![vec![get_selector_from_name("Transfer").unwrap()]];
+        let subscription_id = subscribe(
+            &mut ws,
+            "starknet_subscribeEvents",
+            json!({ "from_address": address, "keys": keys }),
+        )
+        .await
+        .unwrap();
+
+        devnet.mint(0x1, 1).await;
+        let expected_events = get_latest_events(&devnet, Some(address), Some(keys)).await;
+        assert!(!expected_events.is_empty());
+
+        for expected_event in expected_events {
+            let event = receive_event(&mut ws, subscription_id).await.unwrap();
+            assert_eq!(event, expected_event);
+        }
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn should_not_receive_events_not_matching_the_filter() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        let mut address_ws = connect_to_ws(&devnet).await.unwrap();
+        let strk_address = Felt::from_hex_unchecked(STRK_ERC20_CONTRACT_ADDRESS);
+        subscribe(
+            &mut address_ws,
+            "starknet_subscribeEvents",
+            json!({ "from_address": strk_address }),
+        )
+        .await
+        .unwrap();
+
+        let mut keys_ws = connect_to_ws(&devnet).await.unwrap();
+        let dummy_key = Felt::from(0xdead);
+        subscribe(&mut keys_ws, "starknet_subscribeEvents", json!({ "keys": [[dummy_key]] }))
+            .await
+            .unwrap();
+
+        // minting in WEI and paying the fee in WEI only emits events of the ETH contract
+        devnet.mint(0x1, 1).await;
+
+        assert_no_notifications(&mut address_ws).await;
+        assert_no_notifications(&mut keys_ws).await;
+    }
+
+    #[tokio::test]
+    async fn subscription_backfills_events_from_old_block() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        devnet.mint(0x1, 1).await;
+        devnet.mint(0x1, 2).await;
+
+        let filter = EventFilter {
+            from_block: Some(BlockId::Number(1)),
+            to_block: Some(BlockId::Tag(BlockTag::Latest)),
+            address: None,
+            keys: None,
+        };
+        let expected_events =
+            devnet.json_rpc_client.get_events(filter, None, 1000).await.unwrap().events;
+
+        let subscription_id = subscribe(
+            &mut ws,
+            "starknet_subscribeEvents",
+            json!({ "block_id": { "block_number": 1 } }),
+        )
+        .await
+        .unwrap();
+
+        for expected_event in expected_events {
+            let event = receive_event(&mut ws, subscription_id).await.unwrap();
+            assert_eq!(event, expected_event);
+        }
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn subscription_to_events_from_nonexistent_block_fails() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let rpc_error = subscribe(
+            &mut ws,
+            "starknet_subscribeEvents",
+            json!({ "block_id": { "block_number": 1 } }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(rpc_error.code, ErrorCode::ServerError(24));
+    }
+}
@@ -0,0 +1,196 @@
+pub mod common;
+
+mod new_heads_subscription_support {
+    use serde_json::json;
+    use server::api::json_rpc::RpcSpecVersion;
+    use server::rpc_core::error::{ErrorCode, RpcError};
+    use starknet_rs_core::types::BlockTag;
+    use starknet_rs_providers::Provider;
+    use starknet_types::rpc::block::BlockHeader;
+    use starknet_types::starknet_api::block::BlockNumber;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::utils::{
+        assert_no_notifications, connect_to_ws, connect_to_ws_path, receive_notification,
+        send_text_rpc_via_ws, subscribe, unsubscribe, WsStream,
+    };
+
+    const NEW_HEADS_NOTIFICATION: &str = "starknet_subscriptionNewHeads";
+
+    async fn subscribe_new_heads(
+        ws: &mut WsStream,
+        block_specifier: serde_json::Value,
+    ) -> Result<u64, RpcError> {
+        subscribe(ws, "starknet_subscribeNewHeads", block_specifier).await
+    }
+
+    async fn receive_block_header(
+        ws: &mut WsStream,
+        subscription_id: u64,
+    ) -> Result<BlockHeader, anyhow::Error> {
+        let header = receive_notification(ws, NEW_HEADS_NOTIFICATION, subscription_id).await?;
+        Ok(serde_json::from_value(header)?)
+    }
+
+    #[tokio::test]
+    async fn subscribe_to_new_block_heads_happy_path() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let subscription_id = subscribe_new_heads(&mut ws, json!({})).await.unwrap();
+
+        for block_number in 1..=2 {
+            let created_block_hash = devnet.create_block().await.unwrap();
+
+            let header = receive_block_header(&mut ws, subscription_id).await.unwrap();
+            assert_eq!(header.block_hash, created_block_hash);
+            assert_eq!(header.block_number, BlockNumber(block_number));
+        }
+
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn new_block_heads_received_by_multiple_subscribers() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        let mut subscribers = vec![];
+        for _ in 0..3 {
+            let mut ws = connect_to_ws(&devnet).await.unwrap();
+            let subscription_id = subscribe_new_heads(&mut ws, json!({})).await.unwrap();
+            subscribers.push((ws, subscription_id));
+        }
+
+        let created_block_hash = devnet.create_block().await.unwrap();
+
+        for (mut ws, subscription_id) in subscribers {
+            let header = receive_block_header(&mut ws, subscription_id).await.unwrap();
+            assert_eq!(header.block_hash, created_block_hash);
+            assert_no_notifications(&mut ws).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn subscription_backfills_block_heads_from_old_block() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let mut created_block_hashes = vec![];
+        for _ in 0..2 {
+            created_block_hashes.push(devnet.create_block().await.unwrap());
+        }
+
+        let subscription_id =
+            subscribe_new_heads(&mut ws, json!({ "block_id": { "block_number": 1 } }))
+                .await
+                .unwrap();
+
+        for (i, created_block_hash) in created_block_hashes.into_iter().enumerate() {
+            let header = receive_block_header(&mut ws, subscription_id).await.unwrap();
+            assert_eq!(header.block_hash, created_block_hash);
+            assert_eq!(header.block_number, BlockNumber(i as u64 + 1));
+        }
+        assert_no_notifications(&mut ws).await;
+
+        // blocks created after subscribing are also received
+        let created_block_hash = devnet.create_block().await.unwrap();
+        let header = receive_block_header(&mut ws, subscription_id).await.unwrap();
+        assert_eq!(header.block_hash, created_block_hash);
+    }
+
+    #[tokio::test]
+    async fn subscription_with_latest_block_id_starts_with_latest_block_head() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let subscription_id =
+            subscribe_new_heads(&mut ws, json!({ "block_id": BlockTag::Latest })).await.unwrap();
+
+        let latest_block = devnet.get_latest_block_with_tx_hashes().await.unwrap();
+        let header = receive_block_header(&mut ws, subscription_id).await.unwrap();
+        assert_eq!(header.block_hash, latest_block.block_hash);
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn subscription_from_nonexistent_block_fails() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let rpc_error = subscribe_new_heads(&mut ws, json!({ "block_id": { "block_number": 1 } }))
+            .await
+            .unwrap_err();
+        assert_eq!(rpc_error.code, ErrorCode::ServerError(24));
+    }
+
+    #[tokio::test]
+    async fn should_not_receive_block_heads_after_unsubscribing() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let subscription_id = subscribe_new_heads(&mut ws, json!({})).await.unwrap();
+        assert_eq!(unsubscribe(&mut ws, subscription_id).await.unwrap(), json!(true));
+
+        devnet.create_block().await.unwrap();
+        assert_no_notifications(&mut ws).await;
+
+        let rpc_error = unsubscribe(&mut ws, subscription_id).await.unwrap_err();
+        assert_eq!(rpc_error.code, ErrorCode::ServerError(66));
+    }
+
+    #[tokio::test]
+    async fn subscription_survives_restart() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let subscription_id = subscribe_new_heads(&mut ws, json!({})).await.unwrap();
+        devnet.restart().await;
+        assert_no_notifications(&mut ws).await;
+
+        let created_block_hash = devnet.create_block().await.unwrap();
+        let header = receive_block_header(&mut ws, subscription_id).await.unwrap();
+        assert_eq!(header.block_hash, created_block_hash);
+        assert_eq!(header.block_number, BlockNumber(1));
+    }
+
+    #[tokio::test]
+    async fn json_rpc_methods_available_via_ws() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        devnet.create_block().await.unwrap();
+        let block_number =
+            send_text_rpc_via_ws(&mut ws, "starknet_blockNumber", json!([])).await.unwrap();
+        assert_eq!(block_number, json!(devnet.json_rpc_client.block_number().await.unwrap()));
+
+        let rpc_error = send_text_rpc_via_ws(&mut ws, "starknet_nonexistentMethod", json!([]))
+            .await
+            .unwrap_err();
+        assert_eq!(rpc_error.code, ErrorCode::MethodNotFound);
+    }
+
+    #[tokio::test]
+    async fn headers_received_in_format_of_version_of_ws_route() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        for (rpc_spec_version, expected_l2_gas_price) in [
+            (RpcSpecVersion::V0_7, None),
+            (RpcSpecVersion::V0_8, Some(json!({ "price_in_fri": "0x0", "price_in_wei": "0x0" }))),
+        ] {
+            let mut ws =
+                connect_to_ws_path(&devnet, rpc_spec_version.ws_route_path()).await.unwrap();
+
+            let spec_version =
+                send_text_rpc_via_ws(&mut ws, "starknet_specVersion", json!([])).await.unwrap();
+            assert_eq!(spec_version, rpc_spec_version.as_str());
+
+            let subscription_id = subscribe_new_heads(&mut ws, json!({})).await.unwrap();
+            devnet.create_block().await.unwrap();
+
+            let header = receive_notification(&mut ws, NEW_HEADS_NOTIFICATION, subscription_id)
+                .await
+                .unwrap();
+            assert_eq!(header.get("l2_gas_price").cloned(), expected_l2_gas_price);
+        }
+    }
+}
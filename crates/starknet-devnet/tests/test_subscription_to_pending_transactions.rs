@@ -0,0 +1,103 @@
+pub mod common;
+
+mod pending_transactions_subscription_support {
+    use serde_json::json;
+    use starknet_core::constants::CHARGEABLE_ACCOUNT_ADDRESS;
+    use starknet_rs_core::types::{Felt, Transaction};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::utils::{
+        assert_no_notifications, connect_to_ws, receive_notification, subscribe, WsStream,
+    };
+
+    const PENDING_TRANSACTIONS_NOTIFICATION: &str = "starknet_subscriptionPendingTransactions";
+
+    async fn subscribe_pending_transactions(ws: &mut WsStream, params: serde_json::Value) -> u64 {
+        subscribe(ws, "starknet_subscribePendingTransactions", params).await.unwrap()
+    }
+
+    async fn receive_pending_transaction(
+        ws: &mut WsStream,
+        subscription_id: u64,
+    ) -> serde_json::Value {
+        receive_notification(ws, PENDING_TRANSACTIONS_NOTIFICATION, subscription_id).await.unwrap()
+    }
+
+    async fn spawn_devnet_with_blocks_on_demand() -> BackgroundDevnet {
+        BackgroundDevnet::spawn_with_additional_args(&["--block-generation-on", "demand"])
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_receive_hashes_of_pending_transactions() {
+        let devnet = spawn_devnet_with_blocks_on_demand().await;
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let subscription_id = subscribe_pending_transactions(&mut ws, json!({})).await;
+
+        for mint_amount in 1..=2 {
+            let mint_hash = devnet.mint(0x1, mint_amount).await;
+            let notification = receive_pending_transaction(&mut ws, subscription_id).await;
+            assert_eq!(notification, json!(mint_hash));
+        }
+
+        devnet.create_block().await.unwrap();
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn should_receive_pending_transactions_with_details() {
+        let devnet = spawn_devnet_with_blocks_on_demand().await;
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let subscription_id =
+            subscribe_pending_transactions(&mut ws, json!({ "transaction_details": true })).await;
+
+        let mint_hash = devnet.mint(0x1, 1).await;
+        let notification = receive_pending_transaction(&mut ws, subscription_id).await;
+        let transaction: Transaction = serde_json::from_value(notification).unwrap();
+        assert_eq!(
+            transaction,
+            devnet.json_rpc_client.get_transaction_by_hash(mint_hash).await.unwrap()
+        );
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn should_receive_pending_transactions_with_blocks_on_transaction() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let subscription_id = subscribe_pending_transactions(&mut ws, json!({})).await;
+
+        let mint_hash = devnet.mint(0x1, 1).await;
+        let notification = receive_pending_transaction(&mut ws, subscription_id).await;
+        assert_eq!(notification, json!(mint_hash));
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn should_receive_only_pending_transactions_of_subscribed_senders() {
+        let devnet = spawn_devnet_with_blocks_on_demand().await;
+
+        let mut minter_ws = connect_to_ws(&devnet).await.unwrap();
+        let minter_address = Felt::from_hex_unchecked(CHARGEABLE_ACCOUNT_ADDRESS);
+        let subscription_id = subscribe_pending_transactions(
+            &mut minter_ws,
+            json!({ "sender_address": [minter_address] }),
+        )
+        .await;
+
+        let mut other_ws = connect_to_ws(&devnet).await.unwrap();
+        subscribe_pending_transactions(&mut other_ws, json!({ "sender_address": ["0x1"] })).await;
+
+        let mint_hash = devnet.mint(0x1, 1).await;
+        let notification = receive_pending_transaction(&mut minter_ws, subscription_id).await;
+        assert_eq!(notification, json!(mint_hash));
+
+        assert_no_notifications(&mut minter_ws).await;
+        assert_no_notifications(&mut other_ws).await;
+    }
+}
@@ -0,0 +1,271 @@
+pub mod common;
+
+mod transaction_status_subscription_support {
+    use serde_json::json;
+    use server::api::json_rpc::RpcSpecVersion;
+    use starknet_core::constants::ETH_ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_accounts::{Account, Call, ExecutionEncoding, SingleOwnerAccount};
+    use starknet_rs_core::types::{BlockId, BlockTag, Felt};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::felt::felt_from_prefixed_hex;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants;
+    use crate::common::utils::{
+        assert_no_notifications, connect_to_ws, receive_notification, subscribe, WsStream,
+    };
+
+    const TRANSACTION_STATUS_NOTIFICATION: &str = "starknet_subscriptionTransactionStatus";
+
+    async fn subscribe_transaction_status(ws: &mut WsStream, transaction_hash: Felt) -> u64 {
+        subscribe(
+            ws,
+            "starknet_subscribeTransactionStatus",
+            json!({ "transaction_hash": transaction_hash }),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn assert_received_status(
+        ws: &mut WsStream,
+        subscription_id: u64,
+        transaction_hash: Felt,
+        finality_status: &str,
+        execution_status: &str,
+    ) {
+        let expected_status =
+            json!({ "finality_status": finality_status, "execution_status": execution_status });
+        assert_received_status_object(ws, subscription_id, transaction_hash, expected_status).await;
+    }
+
+    async fn assert_received_reverted_status(
+        ws: &mut WsStream,
+        subscription_id: u64,
+        transaction_hash: Felt,
+        failure_reason: &str,
+    ) {
+        let expected_status = json!({
+            "finality_status": "ACCEPTED_ON_L2",
+            "execution_status": "REVERTED",
+            "failure_reason": failure_reason,
+        });
+        assert_received_status_object(ws, subscription_id, transaction_hash, expected_status).await;
+    }
+
+    async fn assert_received_status_object(
+        ws: &mut WsStream,
+        subscription_id: u64,
+        transaction_hash: Felt,
+        expected_status: serde_json::Value,
+    ) {
+        let status = receive_notification(ws, TRANSACTION_STATUS_NOTIFICATION, subscription_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            json!({ "transaction_hash": transaction_hash, "status": expected_status })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_receive_current_status_of_existing_transaction() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let mint_hash = devnet.mint(0x1, 1).await;
+        let subscription_id = subscribe_transaction_status(&mut ws, mint_hash).await;
+
+        assert_received_status(&mut ws, subscription_id, mint_hash, "ACCEPTED_ON_L2", "SUCCEEDED")
+            .await;
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn should_receive_status_when_transaction_accepted_in_block() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--block-generation-on", "demand"])
+                .await
+                .unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let mint_hash = devnet.mint(0x1, 1).await;
+        let subscription_id = subscribe_transaction_status(&mut ws, mint_hash).await;
+        assert_received_status(&mut ws, subscription_id, mint_hash, "ACCEPTED_ON_L2", "SUCCEEDED")
+            .await;
+
+        devnet.create_block().await.unwrap();
+        assert_received_status(&mut ws, subscription_id, mint_hash, "ACCEPTED_ON_L2", "SUCCEEDED")
+            .await;
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn should_receive_status_when_transaction_enters_pending_block() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--block-generation-on", "demand"])
+                .await
+                .unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        let account = SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            signer,
+            account_address,
+            constants::CHAIN_ID,
+            ExecutionEncoding::New,
+        );
+        let transfer = account
+            .execute_v1(vec![Call {
+                to: felt_from_prefixed_hex(ETH_ERC20_CONTRACT_ADDRESS).unwrap(),
+                selector: get_selector_from_name("transfer").unwrap(),
+                calldata: vec![Felt::ONE, Felt::from(1_000_000_000), Felt::ZERO],
+            }])
+            .nonce(Felt::ZERO)
+            .max_fee(Felt::from(1e18 as u128))
+            .prepared()
+            .unwrap();
+
+        // subscribing before the transaction is sent
+        let transfer_hash = transfer.transaction_hash(false);
+        let subscription_id = subscribe_transaction_status(&mut ws, transfer_hash).await;
+        assert_no_notifications(&mut ws).await;
+
+        transfer.send().await.unwrap();
+        assert_received_status(
+            &mut ws,
+            subscription_id,
+            transfer_hash,
+            "ACCEPTED_ON_L2",
+            "SUCCEEDED",
+        )
+        .await;
+
+        devnet.create_block().await.unwrap();
+        assert_received_status(
+            &mut ws,
+            subscription_id,
+            transfer_hash,
+            "ACCEPTED_ON_L2",
+            "SUCCEEDED",
+        )
+        .await;
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn should_receive_status_when_block_of_transaction_aborted() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--state-archive-capacity", "full"])
+                .await
+                .unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let mint_hash = devnet.mint(0x1, 1).await;
+        let subscription_id = subscribe_transaction_status(&mut ws, mint_hash).await;
+        assert_received_status(&mut ws, subscription_id, mint_hash, "ACCEPTED_ON_L2", "SUCCEEDED")
+            .await;
+
+        devnet
+            .send_custom_rpc(
+                "devnet_abortBlocks",
+                json!({ "starting_block_id": BlockId::Tag(BlockTag::Latest) }),
+            )
+            .await
+            .unwrap();
+        assert_received_reverted_status(
+            &mut ws,
+            subscription_id,
+            mint_hash,
+            "Block aborted manually",
+        )
+        .await;
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn should_receive_status_when_block_of_transaction_accepted_on_l1() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let mint_hash = devnet.mint(0x1, 1).await;
+        let subscription_id = subscribe_transaction_status(&mut ws, mint_hash).await;
+        assert_received_status(&mut ws, subscription_id, mint_hash, "ACCEPTED_ON_L2", "SUCCEEDED")
+            .await;
+
+        devnet
+            .send_custom_rpc(
+                "devnet_acceptOnL1",
+                json!({ "starting_block_id": BlockId::Tag(BlockTag::Latest) }),
+            )
+            .await
+            .unwrap();
+        assert_received_status(&mut ws, subscription_id, mint_hash, "ACCEPTED_ON_L1", "SUCCEEDED")
+            .await;
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn should_receive_failure_reason_of_reverted_transaction() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--block-generation-on", "demand"])
+                .await
+                .unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        let account = SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            signer,
+            account_address,
+            constants::CHAIN_ID,
+            ExecutionEncoding::New,
+        );
+        let transfer_execution = account.execute_v1(vec![Call {
+            to: felt_from_prefixed_hex(ETH_ERC20_CONTRACT_ADDRESS).unwrap(),
+            selector: get_selector_from_name("transfer").unwrap(),
+            calldata: vec![Felt::ONE, Felt::from(1_000_000_000), Felt::ZERO],
+        }]);
+
+        // a max fee lower than the estimated one makes the transaction revert
+        let fee = transfer_execution.estimate_fee().await.unwrap();
+        let transfer_hash = transfer_execution
+            .max_fee(fee.overall_fee - Felt::ONE)
+            .send()
+            .await
+            .unwrap()
+            .transaction_hash;
+
+        let status = devnet
+            .send_custom_rpc_to_path(
+                RpcSpecVersion::V0_8.route_path(),
+                "starknet_getTransactionStatus",
+                json!({ "transaction_hash": transfer_hash }),
+            )
+            .await
+            .unwrap();
+        let failure_reason = status["failure_reason"].as_str().unwrap();
+
+        let subscription_id = subscribe_transaction_status(&mut ws, transfer_hash).await;
+        assert_received_reverted_status(&mut ws, subscription_id, transfer_hash, failure_reason)
+            .await;
+
+        devnet.create_block().await.unwrap();
+        assert_received_reverted_status(&mut ws, subscription_id, transfer_hash, failure_reason)
+            .await;
+        assert_no_notifications(&mut ws).await;
+    }
+
+    #[tokio::test]
+    async fn should_not_receive_status_of_other_transactions() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let mut ws = connect_to_ws(&devnet).await.unwrap();
+
+        let dummy_hash = Felt::from(0xdead);
+        subscribe_transaction_status(&mut ws, dummy_hash).await;
+
+        devnet.mint(0x1, 1).await;
+        assert_no_notifications(&mut ws).await;
+    }
+}